@@ -1,4 +1,5 @@
 const fragmentDecorators = new WeakMap();
+const previousValues = new WeakMap();
 
 export function emptyNode() { return document.createTextNode(""); }
 export function fragment()
@@ -26,17 +27,197 @@ export function fragmentReplace(f,n)
 	f.appendChild(e);
 	f.insertBefore(b, f.firstChild);
 }
+export function fragmentMoveBefore(t, n)
+{
+	let d = fragmentDecorators.get(n);
+	// A mounted fragment is empty, its nodes are between the decorators in the DOM
+	if (d && !n.firstChild) {
+		let [b, e] = d;
+		for (let c = b, next; c !== e; c = next) {
+			next = c.nextSibling;
+			t.before(c);
+		}
+		t.before(e);
+	} else {
+		t.before(n);
+	}
+}
+export function fragmentChildren(f)
+{
+	let [b, e] = fragmentDecorators.get(f);
+	let c = [];
+	for (let n = b.nextSibling; n !== e; n = n.nextSibling) c.push(n);
+	return c;
+}
+const hiddenDisplay = new WeakMap();
+
+export function setShown(n, on)
+{
+	let nodes = fragmentDecorators.has(n) ? fragmentChildren(n) : [n];
+	for (let el of nodes) {
+		if (!el.style) continue;
+		if (on) {
+			if (hiddenDisplay.has(el)) {
+				el.style.display = hiddenDisplay.get(el);
+				hiddenDisplay.delete(el);
+			}
+		} else {
+			if (!hiddenDisplay.has(el)) hiddenDisplay.set(el, el.style.display);
+			el.style.display = "none";
+		}
+	}
+}
+export function template(h)
+{
+	let t = document.createElement("template");
+	t.innerHTML = h;
+	return t.content.firstChild;
+}
+export function cloneDeep(n) { return n.cloneNode(true); }
+
+// Parsed templates, least recently used first
+const templateCache = new Map();
+const templateCacheSize = 64;
+const templatePlaceholder = /\{\{\s*([\w-]+)\s*\}\}/;
+
+export function templateSlots(h)
+{
+	let t = templateCache.get(h);
+	if (t) {
+		templateCache.delete(h);
+	} else {
+		t = document.createElement("template");
+		t.innerHTML = h;
+		if (templateCache.size >= templateCacheSize) {
+			templateCache.delete(templateCache.keys().next().value);
+		}
+	}
+	templateCache.set(h, t);
+	let c = t.content.cloneNode(true);
+	let w = document.createTreeWalker(c, NodeFilter.SHOW_TEXT);
+	let texts = [];
+	while (w.nextNode()) texts.push(w.currentNode);
+	let s = [c];
+	for (let n of texts) {
+		let p = n.data.split(templatePlaceholder);
+		if (p.length === 1) continue;
+		for (let i = 1; i < p.length; i += 2) {
+			let x = document.createTextNode("");
+			s.push(p[i], x);
+			p[i] = x;
+		}
+		n.replaceWith(...p.filter(x => x !== ""));
+	}
+	return s;
+}
+
+export function formEntries(f)
+{
+	let e = [];
+	for (let [k,v] of new FormData(f)) if (typeof v === "string") e.push(k,v);
+	return e;
+}
 
 export function setChecked(n,v) { if (n.checked !== v) n.checked = v; }
+export function setViewTransitionName(n,v) { n.style.viewTransitionName = v; }
 
 export function addClass(n,v) { n.classList.add(v); }
 export function removeClass(n,v) { n.classList.remove(v); }
 export function replaceClass(n,o,v) { n.classList.replace(o,v); }
 export function toggleClass(n,c,v) { n.classList.toggle(c,v); }
 
+// Held weakly, so that shadow roots are collected along with their hosts
+const shadowRoots = new Set();
+
+export function injectStyle(c,css)
+{
+	let roots = [document.head];
+	for (let w of shadowRoots) {
+		let r = w.deref();
+		if (r) roots.push(r);
+		else shadowRoots.delete(w);
+	}
+	for (let r of roots) {
+		if (r.querySelector(`style[data-kobold="${c}"]`)) continue;
+		let s = document.createElement("style");
+		s.dataset.kobold = c;
+		s.textContent = css;
+		r.appendChild(s);
+	}
+}
+
+export function attachShadow(h,n)
+{
+	let r = h.attachShadow({ mode: "open" });
+	for (let s of document.head.querySelectorAll("style[data-kobold]")) r.appendChild(s.cloneNode(true));
+	shadowRoots.add(new WeakRef(r));
+	r.appendChild(n);
+}
+
+export function startViewTransition(f)
+{
+	if (document.startViewTransition) document.startViewTransition(f);
+	else f();
+}
+
+export function observeVisible(n,f)
+{
+	let o = new IntersectionObserver((es) => {
+		if (es.some((e) => e.isIntersecting)) { o.disconnect(); f(); }
+	});
+	o.observe(n);
+	return o;
+}
+export function disconnect(o) { o.disconnect(); }
+
+let trackingValues = false;
+
+function storeValue(e) {
+	let t = e.target;
+	if (t instanceof HTMLInputElement || t instanceof HTMLTextAreaElement || t instanceof HTMLSelectElement) {
+		previousValues.set(t, t.value);
+	}
+}
+function defaultValue(n) {
+	if (!(n instanceof HTMLSelectElement)) return n.defaultValue;
+	for (let o of n.options) if (o.defaultSelected) return o.value;
+	return n.options.length ? n.options[0].value : "";
+}
+export function previousValue(n)
+{
+	if (!trackingValues) {
+		trackingValues = true;
+		// Capture before any listeners on the control when it's focused,
+		// and after all of them once it's changed
+		document.addEventListener("focusin", storeValue, true);
+		document.addEventListener("change", storeValue);
+	}
+	return previousValues.has(n) ? previousValues.get(n) : defaultValue(n);
+}
+
+export function measureRender(n,s) { performance.measure(n, { start: s, end: performance.now() }); }
+
+export function scrollIntoView(n,s)
+{
+	if (n instanceof Element) n.scrollIntoView({ behavior: s ? "smooth" : "auto" });
+}
+
+export function insertAdjacentHtml(n,p,h)
+{
+	if (!(n instanceof Element)) return false;
+	try { n.insertAdjacentHTML(p,h); return true; } catch { return false; }
+}
+export function insertAdjacentElement(n,p,e)
+{
+	if (!(n instanceof Element)) return false;
+	try { return n.insertAdjacentElement(p,e) !== null; } catch { return false; }
+}
+
 export function makeEventHandler(c,f) { return (e) => wasmBindings.koboldCallback(e,c,f); }
-export function checkEventHandler() { if (typeof wasmBindings !== "object") console.error(
-`Missing \`wasmBindings\` in global scope.
-As of Kobold v0.10 and Trunk v0.17.16 you no longer need to export bindings manually, \
-please remove the custom \`pattern_script\' from your \`Trunk.toml\` file.
-`) }
+export function makeOptionalHandler() { const f = (e) => { if (f.h) f.h(e); }; return f; }
+export function setOptionalHandler(f,h) { f.h = h; }
+export function missingBindings()
+{
+	if (typeof wasmBindings !== "object") return "wasmBindings";
+	if (typeof wasmBindings.koboldCallback !== "function") return "wasmBindings.koboldCallback";
+}
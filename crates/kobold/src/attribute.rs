@@ -47,6 +47,53 @@ impl Property<bool> for &AttributeName {
     }
 }
 
+/// Attribute with a namespace, such as `xlink:href`:
+/// <https://developer.mozilla.org/en-US/docs/Web/API/Element/setAttributeNS>
+///
+/// The [`view!`](crate::view) macro uses it for attributes with the `xlink`, `xml`
+/// or `xmlns` prefix:
+///
+/// ```
+/// # use kobold::prelude::*;
+/// #[component]
+/// fn icon(name: &str) -> impl View + '_ {
+///     view! {
+///         <svg>
+///             <use xlink:href={name}>
+///         </svg>
+///     }
+/// }
+/// # fn main() {}
+/// ```
+pub struct NamespacedName {
+    namespace: &'static str,
+    name: &'static str,
+}
+
+impl NamespacedName {
+    pub const fn new(namespace: &'static str, name: &'static str) -> Self {
+        NamespacedName { namespace, name }
+    }
+}
+
+impl Property<&str> for NamespacedName {
+    fn set(self, this: &Node, value: &str) {
+        internal::obj(this).set_attr_ns(self.namespace, self.name, value);
+    }
+}
+
+impl Property<f64> for NamespacedName {
+    fn set(self, this: &Node, value: f64) {
+        internal::obj(this).set_attr_ns_num(self.namespace, self.name, value);
+    }
+}
+
+impl Property<bool> for NamespacedName {
+    fn set(self, this: &Node, value: bool) {
+        internal::obj(this).set_attr_ns_bool(self.namespace, self.name, value);
+    }
+}
+
 macro_rules! attribute {
     ($(#[doc = $doc:literal] $name:ident [ $($util:ident: $abi:ty),* ])*) => {
         $(
@@ -73,6 +120,16 @@ impl Property<bool> for Checked {
     }
 }
 
+/// The `view-transition-name` CSS property, set via `style.viewTransitionName`:
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/view-transition-name>
+pub struct ViewTransitionName;
+
+impl Property<&str> for ViewTransitionName {
+    fn set(self, this: &Node, value: &str) {
+        internal::view_transition_name(this, value);
+    }
+}
+
 /// The `Element.classList` property: <https://developer.mozilla.org/en-US/docs/Web/API/Element/classList>
 pub struct Class;
 
@@ -87,6 +144,8 @@ attribute!(
     Href [href: &str]
     /// The `value` attribute: <https://developer.mozilla.org/en-US/docs/Web/HTML/Element/input#value>
     Value [value: &str, value_num: f64]
+    /// The `defaultValue` of an uncontrolled input, only set on build: <https://developer.mozilla.org/en-US/docs/Web/API/HTMLInputElement/defaultValue>
+    DefaultValue [default_value: &str, default_value_num: f64]
 );
 
 pub trait Attribute<P> {
@@ -172,6 +231,46 @@ macro_rules! impl_attribute_view {
 impl_attribute_view!(&str, &String, &Ref<str>, &VString);
 impl_attribute_view!(u8, u16, u32, u64, u128, usize, isize, i8, i16, i32, i64, i128, f32, f64);
 
+/// Same as `&str`, for string fields borrowed from state such as `value={&entry.label}`.
+impl<'a, P> Attribute<P> for &&'a str
+where
+    &'a str: Text<P>,
+{
+    type Product = String;
+
+    fn build(self) -> String {
+        (*self).into_memo()
+    }
+
+    fn build_in(self, prop: P, node: &Node) -> String {
+        (*self).build_in(prop, node)
+    }
+
+    fn update_in(self, prop: P, node: &Node, prod: &mut String) {
+        (*self).update_in(prop, node, prod)
+    }
+}
+
+/// Same as `&String`, the string is only read.
+impl<'a, P> Attribute<P> for &'a mut String
+where
+    &'a String: Text<P>,
+{
+    type Product = String;
+
+    fn build(self) -> String {
+        self.as_str().into_memo()
+    }
+
+    fn build_in(self, prop: P, node: &Node) -> String {
+        (&*self).build_in(prop, node)
+    }
+
+    fn update_in(self, prop: P, node: &Node, prod: &mut String) {
+        (&*self).update_in(prop, node, prod)
+    }
+}
+
 #[inline]
 fn debug_test_class(class: &str) {
     debug_assert!(
@@ -186,6 +285,12 @@ fn set_class(node: &Node, class: &str) {
     }
 }
 
+fn set_class_off(node: &Node, class: &str) {
+    if !class.is_empty() {
+        internal::remove_class(node, class);
+    }
+}
+
 fn diff_class(node: &Node, new: &str, old: &str) -> bool {
     match (new, old) {
         (new, old) if new == old => return false,
@@ -301,6 +406,167 @@ where
     }
 }
 
+/// Space-separated list of classes collected from an iterator, created with the [`classes`] function.
+pub struct Classes<I>(I);
+
+/// Use an iterator of class names as the `class` attribute of an element. The classes are
+/// joined with spaces, and compared to the previous join on updates without allocating
+/// unless they have changed.
+///
+/// ```
+/// use kobold::prelude::*;
+/// use kobold::attribute::classes;
+///
+/// fn button<'a>(flags: &'a [&'a str]) -> impl View + 'a {
+///     view! {
+///         // Sets `className` to all the classes
+///         <button class={classes(flags)}>"Click"</button>
+///         // Combined with the `.class` shorthand, classes are added to `classList`
+///         // without touching the `btn` class
+///         <button.btn class={classes(flags.iter().filter(|f| f.starts_with("btn-")))}>"Click"</button>
+///     }
+/// }
+/// ```
+pub const fn classes<I>(iter: I) -> Classes<I>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    Classes(iter)
+}
+
+impl<I> Classes<I>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    fn join(self) -> String {
+        let mut joined = String::new();
+
+        for class in self.0 {
+            let class = class.as_ref();
+
+            if class.is_empty() {
+                continue;
+            }
+            if !joined.is_empty() {
+                joined.push(' ');
+            }
+            joined.push_str(class);
+        }
+
+        joined
+    }
+
+    /// Join the classes while comparing them to the `old` join, returns `None` without
+    /// allocating a new string if the classes haven't changed.
+    fn join_diff(self, old: &str) -> Option<String> {
+        let mut len = 0;
+        let mut joined: Option<String> = None;
+
+        for class in self.0 {
+            let class = class.as_ref();
+
+            if class.is_empty() {
+                continue;
+            }
+
+            let sep = if len > 0 { " " } else { "" };
+
+            match &mut joined {
+                Some(joined) => {
+                    joined.push_str(sep);
+                    joined.push_str(class);
+                }
+                None => {
+                    let rest = &old[len..];
+
+                    if rest.starts_with(sep) && rest[sep.len()..].starts_with(class) {
+                        len += sep.len() + class.len();
+                        continue;
+                    }
+
+                    let mut new = String::with_capacity(old.len().max(len + 1 + class.len()));
+
+                    new.push_str(&old[..len]);
+                    new.push_str(sep);
+                    new.push_str(class);
+                    joined = Some(new);
+                }
+            }
+
+            len += sep.len() + class.len();
+        }
+
+        match joined {
+            None if len == old.len() => None,
+            None => Some(old[..len].to_owned()),
+            joined => joined,
+        }
+    }
+}
+
+impl<I> Attribute<ClassName> for Classes<I>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    type Product = String;
+
+    fn build(self) -> String {
+        self.join()
+    }
+
+    fn build_in(self, _: ClassName, node: &Node) -> String {
+        let joined = self.join();
+        internal::obj(node).class_name(&joined);
+        joined
+    }
+
+    fn update_in(self, _: ClassName, node: &Node, old: &mut String) {
+        if let Some(joined) = self.join_diff(old) {
+            internal::obj(node).class_name(&joined);
+            *old = joined;
+        }
+    }
+}
+
+impl<I> Attribute<Class> for Classes<I>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    type Product = String;
+
+    fn build(self) -> String {
+        self.join()
+    }
+
+    fn build_in(self, _: Class, node: &Node) -> String {
+        let joined = self.join();
+
+        for class in joined.split(' ') {
+            set_class(node, class);
+        }
+        joined
+    }
+
+    fn update_in(self, _: Class, node: &Node, old: &mut String) {
+        if let Some(joined) = self.join_diff(old) {
+            // Only toggle the classes that were actually added or removed
+            let has = |list: &str, class: &str| list.split(' ').any(|c| c == class);
+
+            for class in old.split(' ').filter(|class| !has(&joined, class)) {
+                set_class_off(node, class);
+            }
+            for class in joined.split(' ').filter(|class| !has(old, class)) {
+                set_class(node, class);
+            }
+            *old = joined;
+        }
+    }
+}
+
 #[derive(Clone, Copy)]
 pub struct OptionalClass {
     class: &'static str,
@@ -356,3 +622,262 @@ impl Attribute<ClassName> for OptionalClass {
         }
     }
 }
+
+/// Conditionally present attribute, created by the `name=?{value}` syntax in
+/// [`view!`](crate::view):
+///
+/// * `bool` values add an empty attribute when `true`, for boolean attributes such as `disabled`.
+/// * `Option` values set the attribute to the contained value when `Some`.
+///
+/// Either way the attribute is removed from the element when the value changes
+/// to `false` or `None`.
+///
+/// ```
+/// use kobold::prelude::*;
+///
+/// #[component]
+/// fn submit(busy: bool, hint: Option<&'static str>) -> impl View {
+///     view! {
+///         <button disabled=?{busy} title=?{hint}>"Submit"</button>
+///     }
+/// }
+/// # fn main() {}
+/// ```
+pub struct Optional<T>(pub T);
+
+/// Property of an attribute that can be removed from an element.
+pub trait RemoveProperty: Property<&'static str> {
+    fn remove(self, this: &Node);
+}
+
+impl RemoveProperty for &AttributeName {
+    fn remove(self, this: &Node) {
+        internal::obj(this).remove_attr(self);
+    }
+}
+
+impl RemoveProperty for NamespacedName {
+    fn remove(self, this: &Node) {
+        internal::obj(this).remove_attr_ns(self.namespace, self.name);
+    }
+}
+
+impl<P: RemoveProperty> Attribute<P> for Optional<bool> {
+    type Product = bool;
+
+    fn build(self) -> bool {
+        self.0
+    }
+
+    fn build_in(self, prop: P, node: &Node) -> bool {
+        if self.0 {
+            prop.set(node, "");
+        }
+        self.0
+    }
+
+    fn update_in(self, prop: P, node: &Node, memo: &mut bool) {
+        if self.0 != *memo {
+            match self.0 {
+                true => prop.set(node, ""),
+                false => prop.remove(node),
+            }
+            *memo = self.0;
+        }
+    }
+}
+
+impl<P, T> Attribute<P> for Optional<Option<T>>
+where
+    P: RemoveProperty,
+    T: Attribute<P>,
+{
+    type Product = Option<T::Product>;
+
+    fn build(self) -> Self::Product {
+        self.0.map(T::build)
+    }
+
+    fn build_in(self, prop: P, node: &Node) -> Self::Product {
+        self.0.map(|value| value.build_in(prop, node))
+    }
+
+    fn update_in(self, prop: P, node: &Node, memo: &mut Self::Product) {
+        match (self.0, memo.as_mut()) {
+            (Some(value), Some(memo)) => value.update_in(prop, node, memo),
+            (Some(value), None) => *memo = Some(value.build_in(prop, node)),
+            (None, Some(_)) => {
+                prop.remove(node);
+                *memo = None;
+            }
+            (None, None) => (),
+        }
+    }
+}
+
+/// Scoped class name with its stylesheet, created by the [`css!`](crate::css) macro.
+///
+/// The stylesheet is injected into `<head>` the first time an element with this
+/// class is built, subsequent builds reuse it.
+#[derive(Clone, Copy)]
+pub struct ScopedStyle {
+    class: &'static str,
+    css: &'static str,
+}
+
+impl ScopedStyle {
+    pub const fn new(class: &'static str, css: &'static str) -> Self {
+        ScopedStyle { class, css }
+    }
+
+    /// Generated class name, such as `kobold-1a2b3c4d`.
+    pub const fn class(&self) -> &'static str {
+        self.class
+    }
+
+    /// Scoped stylesheet for this class.
+    pub const fn css(&self) -> &'static str {
+        self.css
+    }
+
+    fn inject(self) {
+        use std::cell::RefCell;
+        use std::collections::HashSet;
+
+        thread_local! {
+            static INJECTED: RefCell<HashSet<&'static str>> = RefCell::new(HashSet::new());
+        }
+
+        if INJECTED.with(|injected| injected.borrow_mut().insert(self.class)) {
+            internal::inject_style(self.class, self.css);
+        }
+    }
+}
+
+impl Deref for ScopedStyle {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.class
+    }
+}
+
+impl AsRef<str> for ScopedStyle {
+    fn as_ref(&self) -> &str {
+        self.class
+    }
+}
+
+impl Attribute<Class> for ScopedStyle {
+    type Product = ();
+
+    fn build(self) {
+        self.inject();
+    }
+
+    fn build_in(self, _: Class, node: &Node) {
+        self.inject();
+        internal::add_class(node, self.class);
+    }
+
+    fn update_in(self, _: Class, _: &Node, _: &mut ()) {}
+}
+
+impl Attribute<ClassName> for ScopedStyle {
+    type Product = ();
+
+    fn build(self) {
+        self.inject();
+    }
+
+    fn build_in(self, _: ClassName, node: &Node) {
+        self.inject();
+        internal::obj(node).class_name(self.class);
+    }
+
+    fn update_in(self, _: ClassName, _: &Node, _: &mut ()) {}
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+
+    use wasm_bindgen::JsCast;
+
+    use super::*;
+
+    #[test]
+    fn classes_join_diff() {
+        assert_eq!(classes(["a", "", "b"]).join(), "a b");
+        assert_eq!(classes(["a", "", "b"]).join_diff("a b"), None);
+        assert_eq!(classes(Vec::<&str>::new()).join_diff(""), None);
+        assert_eq!(classes(["a", "b"]).join_diff("a b c").unwrap(), "a b");
+        assert_eq!(classes(["a", "b", "c"]).join_diff("a b").unwrap(), "a b c");
+        assert_eq!(classes(["a", "bc"]).join_diff("a b").unwrap(), "a bc");
+        assert_eq!(classes(["ab"]).join_diff("a b").unwrap(), "ab");
+        assert_eq!(classes(["c"]).join_diff("").unwrap(), "c");
+    }
+
+    /// Property counting how many times it was written to.
+    #[derive(Clone, Copy)]
+    struct Writes<'a>(&'a Cell<u32>);
+
+    impl Property<&str> for Writes<'_> {
+        fn set(self, _: &Node, _: &str) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    impl Property<f64> for Writes<'_> {
+        fn set(self, _: &Node, _: f64) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    fn writes<A, F>(values: [A; 3], project: F) -> u32
+    where
+        F: Fn(&A) -> A,
+        for<'a> A: Attribute<Writes<'a>>,
+    {
+        let count = Cell::new(0);
+        let node: Node = wasm_bindgen::JsValue::UNDEFINED.unchecked_into();
+        let [first, rest @ ..] = values;
+        let mut prod = project(&first).build_in(Writes(&count), &node);
+
+        for value in rest.iter().map(&project) {
+            value.update_in(Writes(&count), &node, &mut prod);
+        }
+
+        count.get()
+    }
+
+    #[test]
+    fn unchanged_attributes_are_not_written() {
+        let description = String::from("Buy milk");
+        let edited = String::from("Buy oat milk");
+        let mut buffer = description.clone();
+
+        // Built once, then updated with the same value and a new one
+        assert_eq!(writes(["a", "a", "b"], |s| *s), 2);
+        assert_eq!(writes([&description, &description, &edited], |s| *s), 2);
+        assert_eq!(
+            writes([&&*description, &&*description, &&*edited], |s| *s),
+            2
+        );
+        assert_eq!(
+            writes(
+                [description.clone(), description.clone(), edited.clone()],
+                String::clone
+            ),
+            2
+        );
+        assert_eq!(writes([1_u32, 1, 2], |n| *n), 2);
+
+        let count = Cell::new(0);
+        let node: Node = wasm_bindgen::JsValue::UNDEFINED.unchecked_into();
+        let mut prod = (&mut buffer).build_in(Writes(&count), &node);
+
+        (&mut buffer).update_in(Writes(&count), &node, &mut prod);
+        assert_eq!(count.get(), 1);
+    }
+}
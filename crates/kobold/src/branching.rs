@@ -92,15 +92,36 @@
 //! }
 //! # fn main() {}
 //! ```
+//!
+//! Or the [`show_if`] and [`hide_if`] helpers for the same thing inline. Note that a bare
+//! `bool` in a view renders as the text `true` or `false`, which is rarely what you want:
+//!
+//! ```
+//! # use kobold::prelude::*;
+//! use kobold::branching::show_if;
+//!
+//! #[component]
+//! fn conditional(illuminatus: bool) -> impl View {
+//!     view! {
+//!         <div>
+//!         { show_if(illuminatus, view! { <p>"Fnord"</p> }) }
+//!         </div>
+//!     }
+//! }
+//! # fn main() {}
+//! ```
 
+use std::collections::hash_map::{Entry, HashMap};
+use std::hash::Hash;
 use std::mem::MaybeUninit;
 use std::pin::Pin;
 
 use wasm_bindgen::JsValue;
-use web_sys::Node;
+use web_sys::{DomRect, Node};
 
-use crate::dom::Anchor;
-use crate::internal::{empty_node, In, Out};
+use crate::dom::{Anchor, Fragment, FragmentBuilder};
+use crate::init;
+use crate::internal::{self, empty_node, In, Out};
 use crate::{Mountable, View};
 
 macro_rules! branch {
@@ -189,6 +210,14 @@ macro_rules! branch {
                     )*
                 }
             }
+
+            fn rect(&self) -> Option<DomRect> {
+                match self {
+                    $(
+                        $name::$var(p) => p.rect(),
+                    )*
+                }
+            }
         }
     };
 }
@@ -202,6 +231,271 @@ branch!(Branch7<A, B, C, D, E, F, G>);
 branch!(Branch8<A, B, C, D, E, F, G, H>);
 branch!(Branch9<A, B, C, D, E, F, G, H, I>);
 
+/// Render the `view` only if the `condition` is `true`.
+pub fn show_if<V: View>(condition: bool, view: V) -> Option<V> {
+    condition.then_some(view)
+}
+
+/// Render the `view` only if the `condition` is `false`.
+pub fn hide_if<V: View>(condition: bool, view: V) -> Option<V> {
+    show_if(!condition, view)
+}
+
+/// Render the `view` and keep it rendered, hiding it with `display: none` while `when`
+/// is `false` instead of dropping it. Scroll positions, focus, input values and any
+/// [`stateful`](crate::stateful::stateful) state inside of it survive toggling. Also
+/// available as a component taking children:
+///
+/// ```
+/// use kobold::prelude::*;
+/// use kobold::branching::show;
+///
+/// #[component]
+/// fn sidebar(open: bool) -> impl View {
+///     view! {
+///         <!show when={open}>
+///             <aside>
+///                 <input type="search" placeholder="Filter">
+///             </aside>
+///         </!show>
+///     }
+/// }
+/// # fn main() {}
+/// ```
+///
+/// Unlike [`show_if`] or an [`Option`], which drop the view when hidden and build it
+/// again from scratch when shown, the hidden view stays in the DOM and in memory, and
+/// is still updated on every render. Prefer it for panels that are toggled often or are
+/// costly to build, and an [`Option`] for content that is rarely shown.
+///
+/// Only elements at the root of the `view` are hidden, wrap bare text in an element.
+/// The `display` property of these elements is restored when shown again, so avoid
+/// setting their `style` attribute dynamically.
+pub fn show<V: View>(when: bool, view: V) -> Show<V> {
+    Show { when, view }
+}
+
+/// View toggling the visibility of its child, see [`show`].
+pub struct Show<V> {
+    when: bool,
+    view: V,
+}
+
+pub struct ShowProduct<P> {
+    product: P,
+    when: bool,
+}
+
+impl<P> ShowProduct<P> {
+    /// Store the new `when`, returning `true` if the root nodes have to be shown or hidden.
+    ///
+    /// Hiding is repeated on every update while hidden to catch new root nodes, such as
+    /// items added to a list. Roots added while shown are visible already.
+    fn toggle(&mut self, when: bool) -> bool {
+        let changed = self.when != when;
+
+        self.when = when;
+
+        changed || !when
+    }
+}
+
+impl<P: Mountable> Anchor for ShowProduct<P> {
+    type Js = P::Js;
+    type Target = P;
+
+    fn anchor(&self) -> &P {
+        &self.product
+    }
+}
+
+impl<V: View> View for Show<V> {
+    type Product = ShowProduct<V::Product>;
+
+    fn build(self, p: In<Self::Product>) -> Out<Self::Product> {
+        let out = p.in_place(|p| unsafe {
+            init!(p.product @ self.view.build(p));
+            init!(p.when = self.when);
+
+            Out::from_raw(p)
+        });
+
+        if !self.when {
+            internal::set_shown(out.js(), false);
+        }
+        out
+    }
+
+    fn update(self, p: &mut Self::Product) {
+        self.view.update(&mut p.product);
+
+        if p.toggle(self.when) {
+            internal::set_shown(p.js(), self.when);
+        }
+    }
+}
+
+/// `#[component]` handlers for `<!show when={..}>` taking children, see the [`show`] function.
+pub mod show {
+    use super::Show;
+    use crate::View;
+
+    #[doc(hidden)]
+    pub struct Props<C> {
+        when: bool,
+        children: C,
+    }
+
+    pub const fn props() -> Props<()> {
+        Props {
+            when: true,
+            children: (),
+        }
+    }
+
+    impl<C> Props<C> {
+        pub fn when(self, when: bool) -> Self {
+            Props { when, ..self }
+        }
+
+        pub fn children<V: View>(self, children: V) -> Props<V> {
+            Props {
+                when: self.when,
+                children,
+            }
+        }
+    }
+
+    pub fn render<V: View>(props: Props<V>) -> Show<V> {
+        super::show(props.when, props.children)
+    }
+}
+
+/// Render the `view` for the given `key`, keeping products of views rendered for other
+/// keys around instead of dropping them. When the `key` goes back to one that was rendered
+/// before, its product is updated and mounted again, including any
+/// [`stateful`](crate::stateful::stateful) state inside of it:
+///
+/// ```
+/// # use kobold::prelude::*;
+/// use kobold::branching::keep_alive;
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+/// enum Tab {
+///     Profile,
+///     Settings,
+/// }
+///
+/// #[component]
+/// fn profile() -> impl View {
+///     stateful(0_u32, |visits| {
+///         bind! { visits:
+///             let onclick = move |_| *visits += 1;
+///         }
+///
+///         view! { <button {onclick}>"Visited "{ visits.get() }" times"</button> }
+///     })
+/// }
+///
+/// #[component(auto_branch)]
+/// fn tab_content(tab: Tab) -> impl View {
+///     match tab {
+///         Tab::Profile => view! { <!profile> },
+///         Tab::Settings => view! { <p>"Settings"</p> },
+///     }
+/// }
+///
+/// #[component]
+/// fn tabs(tab: Tab) -> impl View {
+///     // Switching to settings and back keeps the count of the profile
+///     keep_alive(tab, view! { <!tab_content {tab}> })
+/// }
+/// # fn main() {}
+/// ```
+///
+/// Nothing is ever evicted, every key that was ever rendered keeps its product along with
+/// its DOM nodes, listeners and timers until the `keep_alive` view itself is dropped. Use it
+/// for a small, fixed set of keys such as tabs or steps of a wizard.
+pub fn keep_alive<K, V>(key: K, view: V) -> KeepAlive<K, V>
+where
+    K: Hash + Eq + Clone + 'static,
+    V: View,
+{
+    KeepAlive { key, view }
+}
+
+/// View rendering one of many cached products, see [`keep_alive`].
+pub struct KeepAlive<K, V> {
+    key: K,
+    view: V,
+}
+
+pub struct KeepAliveProduct<K, P> {
+    active: K,
+    products: HashMap<K, Box<P>>,
+    fragment: FragmentBuilder,
+}
+
+impl<K, V> View for KeepAlive<K, V>
+where
+    K: Hash + Eq + Clone + 'static,
+    V: View,
+{
+    type Product = KeepAliveProduct<K, V::Product>;
+
+    fn build(self, p: In<Self::Product>) -> Out<Self::Product> {
+        let fragment = FragmentBuilder::new();
+        let built = In::boxed(|p| self.view.build(p));
+
+        fragment.append(built.js());
+
+        p.put(KeepAliveProduct {
+            active: self.key.clone(),
+            products: HashMap::from([(self.key, built)]),
+            fragment,
+        })
+    }
+
+    fn update(self, p: &mut Self::Product) {
+        if self.key == p.active {
+            if let Some(product) = p.products.get_mut(&self.key) {
+                self.view.update(product);
+            }
+            return;
+        }
+
+        if let Some(previous) = p.products.get(&p.active) {
+            previous.unmount();
+        }
+
+        let product = match p.products.entry(self.key.clone()) {
+            Entry::Occupied(entry) => {
+                let product = entry.into_mut();
+
+                self.view.update(product);
+                product
+            }
+            Entry::Vacant(entry) => entry.insert(In::boxed(|p| self.view.build(p))),
+        };
+
+        p.fragment.append(product.js());
+        p.active = self.key;
+    }
+}
+
+impl<K, P> Anchor for KeepAliveProduct<K, P>
+where
+    K: 'static,
+    P: Mountable,
+{
+    type Js = Node;
+    type Target = Fragment;
+
+    fn anchor(&self) -> &Fragment {
+        &self.fragment
+    }
+}
+
 pub struct EmptyNode(Node);
 
 pub struct Empty;
@@ -263,3 +557,24 @@ impl<T: View> View for Option<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn show_toggles_on_change() {
+        let mut p = ShowProduct {
+            product: (),
+            when: true,
+        };
+
+        // Shown roots are left alone
+        assert!(!p.toggle(true));
+        assert!(p.toggle(false));
+        // Hidden again to catch new roots
+        assert!(p.toggle(false));
+        assert!(p.toggle(true));
+        assert!(!p.toggle(true));
+    }
+}
@@ -0,0 +1,49 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Logging to the browser console during development
+//!
+//! Anything implementing [`Display`] can be logged, use [`format_args!`] to log multiple
+//! values in one message. The message is formatted into a `String` before it's passed
+//! to the console:
+//!
+//! ```no_run
+//! use kobold::console;
+//!
+//! let (count, total) = (3, 10);
+//!
+//! console::log("rendering list");
+//! console::warn(format_args!("showing {count} of {total} items"));
+//! ```
+//!
+//! With the `quiet_release` feature enabled all functions in this module do nothing in
+//! release builds, so logging left in render logic costs nothing in production.
+
+use std::fmt::Display;
+
+use crate::internal;
+
+const ENABLED: bool = cfg!(any(debug_assertions, not(feature = "quiet_release")));
+
+macro_rules! console {
+    ($($(#[doc = $doc:literal])* $name:ident => $util:ident,)*) => {
+        $(
+            $(#[doc = $doc])*
+            pub fn $name(message: impl Display) {
+                if ENABLED {
+                    internal::$util(&message.to_string());
+                }
+            }
+        )*
+    };
+}
+
+console! {
+    /// Log the `message` with `console.log`.
+    log => console_log,
+    /// Log the `message` with `console.warn`.
+    warn => console_warn,
+    /// Log the `message` with `console.error`.
+    error => console_error,
+}
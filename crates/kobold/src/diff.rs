@@ -11,7 +11,7 @@ use web_sys::Node;
 use crate::attribute::Attribute;
 use crate::dom::{Anchor, TextContent};
 use crate::internal::{In, Out};
-use crate::value::{IntoText, Value};
+use crate::value::{IntoText, TextProduct, Value};
 use crate::{init, Mountable, View};
 
 mod vstring;
@@ -62,6 +62,42 @@ where
     }
 }
 
+/// Create a wrapper around a `view` that will prevent updates to it, unless
+/// `deps` are different from the ones of the previous render.
+///
+/// This is similar to [`fence`], but instead of the [`Diff`] trait it works with any
+/// owned value that implements `PartialEq`, such as a `String` or a tuple of values.
+/// The last `deps` are stored in the product and compared by value on every update.
+///
+/// ```
+/// use kobold::prelude::*;
+/// use kobold::diff::memo;
+///
+/// #[component]
+/// fn chart(points: &[(f64, f64)]) -> impl View + '_ {
+///     memo(points.to_vec(), move || view! {
+///         // Walking all the points is skipped unless the data has changed
+///         <ul>
+///         {
+///             for points.iter().map(|(x, y)| view! { <li>{ x }", "{ y }</li> })
+///         }
+///         </ul>
+///     })
+/// }
+/// # fn main() {}
+/// ```
+pub const fn memo<D, V, F>(deps: D, render: F) -> Memoized<D, F>
+where
+    D: PartialEq + 'static,
+    V: View,
+    F: FnOnce() -> V,
+{
+    Memoized {
+        deps,
+        inner: render,
+    }
+}
+
 /// Create a wrapper around a `view` that will prevent updates to it.
 ///
 /// This is effectively an unconditional [`fence`].
@@ -86,6 +122,280 @@ where
     Invar(render)
 }
 
+/// Render an optional string as a single text node that's empty when `text` is `None`.
+///
+/// Rendering an `Option<&str>` directly works too, but it swaps the text node for an
+/// empty node whenever the value goes between `Some` and `None`. With `or_empty` the
+/// same text node is kept and only its content is updated when it changes.
+///
+/// ```
+/// use kobold::prelude::*;
+/// use kobold::diff::or_empty;
+///
+/// #[component]
+/// fn error(message: Option<&str>) -> impl View + '_ {
+///     view! {
+///         <p.error>{ or_empty(message) }</p>
+///     }
+/// }
+/// # fn main() {}
+/// ```
+pub const fn or_empty<T>(text: Option<T>) -> OrEmpty<T>
+where
+    T: AsRef<str>,
+{
+    OrEmpty(text)
+}
+
+/// Smart [`View`] rendering an optional string, see [`or_empty`].
+#[repr(transparent)]
+pub struct OrEmpty<T>(Option<T>);
+
+impl<T> View for OrEmpty<T>
+where
+    T: AsRef<str>,
+{
+    type Product = TextProduct<String>;
+
+    fn build(self, p: In<Self::Product>) -> Out<Self::Product> {
+        let text = self.0.as_ref().map(AsRef::as_ref).unwrap_or_default();
+
+        p.put(TextProduct {
+            memo: text.into(),
+            node: text.into_text(),
+        })
+    }
+
+    fn update(self, p: &mut Self::Product) {
+        let text = self.0.as_ref().map(AsRef::as_ref).unwrap_or_default();
+
+        if p.memo != text {
+            text.clone_into(&mut p.memo);
+            text.set_prop(TextContent, &p.node);
+        }
+    }
+}
+
+/// Render all `items` as a single text node, separated by `separator`, such as
+/// `"1, 2, 3"` for a `&[1, 2, 3]` slice joined with `", "`. Items are formatted with
+/// their [`Display`](std::fmt::Display) implementation.
+///
+/// Rendering a slice directly creates a list with a text node per item. For quick
+/// displays of numeric data a single text node is cheaper:
+///
+/// ```
+/// use kobold::prelude::*;
+/// use kobold::diff::joined;
+///
+/// #[component]
+/// fn samples(samples: &[f64]) -> impl View + '_ {
+///     view! { <p>"Samples: "{ joined(samples, ", ") }</p> }
+/// }
+/// # fn main() {}
+/// ```
+///
+/// On updates the items are hashed and only formatted again if the slice or the
+/// separator has changed, in which case the previous `String` is reused. When the hash
+/// is unchanged the items are compared against the rendered text without allocating,
+/// so a hash collision never leaves stale text behind.
+pub const fn joined<'a, T>(items: &'a [T], separator: &'a str) -> Joined<'a, T>
+where
+    T: Joinable,
+{
+    Joined { items, separator }
+}
+
+/// Smart [`View`] rendering a slice as a single text node, see [`joined`].
+pub struct Joined<'a, T> {
+    items: &'a [T],
+    separator: &'a str,
+}
+
+mod sealed {
+    use std::fmt::Display;
+    use std::hash::{Hash, Hasher};
+
+    pub trait Joinable: Display {
+        fn hash_item<H: Hasher>(&self, hasher: &mut H);
+    }
+
+    macro_rules! hashed {
+        ($($ty:ty),*) => {
+            $(
+                impl Joinable for $ty {
+                    fn hash_item<H: Hasher>(&self, hasher: &mut H) {
+                        self.hash(hasher)
+                    }
+                }
+            )*
+        };
+    }
+
+    hashed!(
+        bool, char, &str, String, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+    );
+
+    impl Joinable for f32 {
+        fn hash_item<H: Hasher>(&self, hasher: &mut H) {
+            self.to_bits().hash(hasher)
+        }
+    }
+
+    impl Joinable for f64 {
+        fn hash_item<H: Hasher>(&self, hasher: &mut H) {
+            self.to_bits().hash(hasher)
+        }
+    }
+}
+
+pub(crate) use sealed::Joinable;
+
+/// Memo of a [`Joined`] view, the hash of the rendered items and their text.
+pub struct JoinedMemo {
+    hash: u64,
+    text: String,
+}
+
+impl<T: Joinable> Joined<'_, T> {
+    fn hash(&self) -> u64 {
+        use std::hash::{DefaultHasher, Hash, Hasher};
+
+        let mut hasher = DefaultHasher::new();
+
+        self.separator.hash(&mut hasher);
+        self.items.len().hash(&mut hasher);
+
+        for item in self.items {
+            item.hash_item(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    /// Check whether formatting the items would produce exactly the `text`.
+    fn matches(&self, text: &str) -> bool {
+        use std::fmt::{self, Write};
+
+        struct Compare<'a>(&'a str);
+
+        impl Write for Compare<'_> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                match self.0.strip_prefix(s) {
+                    Some(rest) => {
+                        self.0 = rest;
+                        Ok(())
+                    }
+                    None => Err(fmt::Error),
+                }
+            }
+        }
+
+        let mut compare = Compare(text);
+
+        for (idx, item) in self.items.iter().enumerate() {
+            if idx > 0 && compare.write_str(self.separator).is_err() {
+                return false;
+            }
+            if write!(compare, "{item}").is_err() {
+                return false;
+            }
+        }
+        compare.0.is_empty()
+    }
+
+    fn write_to(&self, text: &mut String) {
+        use std::fmt::Write;
+
+        text.clear();
+
+        for (idx, item) in self.items.iter().enumerate() {
+            if idx > 0 {
+                text.push_str(self.separator);
+            }
+            let _ = write!(text, "{item}");
+        }
+    }
+}
+
+impl<T: Joinable> View for Joined<'_, T> {
+    type Product = TextProduct<JoinedMemo>;
+
+    fn build(self, p: In<Self::Product>) -> Out<Self::Product> {
+        let mut text = String::new();
+
+        self.write_to(&mut text);
+
+        p.put(TextProduct {
+            node: text.as_str().into_text(),
+            memo: JoinedMemo {
+                hash: self.hash(),
+                text,
+            },
+        })
+    }
+
+    fn update(self, p: &mut Self::Product) {
+        let hash = self.hash();
+
+        if p.memo.hash != hash || !self.matches(&p.memo.text) {
+            p.memo.hash = hash;
+            self.write_to(&mut p.memo.text);
+            p.memo.text.as_str().set_prop(TextContent, &p.node);
+        }
+    }
+}
+
+/// Smart [`View`] rendering a float, or the placeholder string if the float is
+/// `NaN` or infinite. Bare floats are rendered the same way JavaScript formats
+/// them, including `NaN` and `Infinity`.
+///
+/// ```
+/// use kobold::prelude::*;
+/// use kobold::diff::FiniteOr;
+///
+/// #[component]
+/// fn average(total: f64, count: f64) -> impl View {
+///     // Renders "—" instead of "NaN" when `count` is 0
+///     view! { <p>"Average: "{ FiniteOr(total / count, "—") }</p> }
+/// }
+/// # fn main() {}
+/// ```
+#[derive(Clone, Copy)]
+pub struct FiniteOr(pub f64, pub &'static str);
+
+impl FiniteOr {
+    fn same(self, other: FiniteOr) -> bool {
+        match (self.0.is_finite(), other.0.is_finite()) {
+            (true, true) => self.0 == other.0,
+            (false, false) => self.1 == other.1,
+            _ => false,
+        }
+    }
+}
+
+impl View for FiniteOr {
+    type Product = TextProduct<FiniteOr>;
+
+    fn build(self, p: In<Self::Product>) -> Out<Self::Product> {
+        let node = match self.0.is_finite() {
+            true => self.0.into_text(),
+            false => self.1.into_text(),
+        };
+
+        p.put(TextProduct { memo: self, node })
+    }
+
+    fn update(self, p: &mut Self::Product) {
+        if !self.same(p.memo) {
+            p.memo = self;
+
+            match self.0.is_finite() {
+                true => self.0.set_prop(TextContent, &p.node),
+                false => self.1.set_prop(TextContent, &p.node),
+            }
+        }
+    }
+}
+
 /// Smart [`View`] that prevents updates, see [`invar`].
 #[repr(transparent)]
 pub struct Invar<F>(F);
@@ -146,6 +456,49 @@ where
     }
 }
 
+/// Smart [`View`] that only updates its child when dependencies change, see [`memo`].
+pub struct Memoized<D, F> {
+    deps: D,
+    inner: F,
+}
+
+impl<D, F, V> View for Memoized<D, F>
+where
+    D: PartialEq + 'static,
+    F: FnOnce() -> V,
+    V: View,
+{
+    type Product = Memoized<D, V::Product>;
+
+    fn build(self, p: In<Self::Product>) -> Out<Self::Product> {
+        p.in_place(|p| unsafe {
+            init!(p.deps = self.deps);
+            init!(p.inner @ (self.inner)().build(p));
+
+            Out::from_raw(p)
+        })
+    }
+
+    fn update(self, p: &mut Self::Product) {
+        if self.deps != p.deps {
+            p.deps = self.deps;
+            (self.inner)().update(&mut p.inner);
+        }
+    }
+}
+
+impl<D, P> Anchor for Memoized<D, P>
+where
+    P: Mountable,
+{
+    type Js = P::Js;
+    type Target = P;
+
+    fn anchor(&self) -> &P {
+        &self.inner
+    }
+}
+
 /// Trait that defines how different values can be _diffed_ at runtime.
 pub trait Diff: Copy {
     /// Data used to check if current value is different from the one in the past.
@@ -228,6 +581,49 @@ macro_rules! impl_diff {
 impl_diff_str!(&str, &String);
 impl_diff!(bool, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
 
+impl Diff for () {
+    type Memo = ();
+
+    fn into_memo(self) {}
+
+    fn diff(self, _: &mut ()) -> bool {
+        false
+    }
+}
+
+macro_rules! impl_diff_tuple {
+    ($($t:ident $n:tt),*) => {
+        /// Tuples are diffed field by field, they have changed if any of the fields has.
+        impl<$($t: Diff),*> Diff for ($($t,)*) {
+            type Memo = ($($t::Memo,)*);
+
+            fn into_memo(self) -> Self::Memo {
+                ($(self.$n.into_memo(),)*)
+            }
+
+            fn diff(self, memo: &mut Self::Memo) -> bool {
+                let mut changed = false;
+
+                // Not short-circuiting, all memos must be updated
+                $(
+                    changed |= self.$n.diff(&mut memo.$n);
+                )*
+
+                changed
+            }
+        }
+    };
+}
+
+impl_diff_tuple!(A 0);
+impl_diff_tuple!(A 0, B 1);
+impl_diff_tuple!(A 0, B 1, C 2);
+impl_diff_tuple!(A 0, B 1, C 2, D 3);
+impl_diff_tuple!(A 0, B 1, C 2, D 3, E 4);
+impl_diff_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_diff_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_diff_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
 /// Smart [`View`] that only updates its content when the reference to T has changed.
 /// See [`ref`](crate::keywords::ref).
 #[repr(transparent)]
@@ -365,3 +761,104 @@ macro_rules! impl_no_diff {
 
 impl_no_diff!(Eager, true);
 impl_no_diff!(Static, false);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn finite_or_diffs_rendered_output() {
+        assert!(FiniteOr(1.5, "-").same(FiniteOr(1.5, "-")));
+        assert!(!FiniteOr(1.5, "-").same(FiniteOr(2.0, "-")));
+
+        // All non-finite values render the placeholder
+        assert!(FiniteOr(f64::NAN, "-").same(FiniteOr(f64::NAN, "-")));
+        assert!(FiniteOr(f64::NAN, "-").same(FiniteOr(f64::INFINITY, "-")));
+        assert!(!FiniteOr(f64::NAN, "-").same(FiniteOr(f64::NAN, "n/a")));
+        assert!(!FiniteOr(f64::NAN, "-").same(FiniteOr(0.0, "-")));
+    }
+
+    #[test]
+    fn joined_formatting() {
+        let mut text = String::from("stale");
+
+        joined(&[1.5, -2.0, 3.25], ", ").write_to(&mut text);
+        assert_eq!(text, "1.5, -2, 3.25");
+
+        joined::<u8>(&[], " ").write_to(&mut text);
+        assert_eq!(text, "");
+
+        joined(&["a", "b"], "").write_to(&mut text);
+        assert_eq!(text, "ab");
+    }
+
+    #[test]
+    fn joined_hash_tracks_items_and_separator() {
+        let hash = joined(&[1, 2, 3], ",").hash();
+
+        assert_eq!(hash, joined(&[1, 2, 3], ",").hash());
+        assert_ne!(hash, joined(&[1, 2, 4], ",").hash());
+        assert_ne!(hash, joined(&[1, 2, 3], " ").hash());
+        assert_ne!(hash, joined(&[1, 2], ",").hash());
+    }
+
+    #[test]
+    fn joined_matches_rendered_text() {
+        assert!(joined(&[1, 2, 3], ", ").matches("1, 2, 3"));
+        assert!(joined::<u8>(&[], ", ").matches(""));
+        assert!(!joined(&[1, 2, 3], ", ").matches("1, 2, 4"));
+        assert!(!joined(&[1, 2, 3], ", ").matches("1, 2, 3, 4"));
+        assert!(!joined(&[1, 2, 3], ", ").matches("1, 2"));
+        assert!(!joined(&[12], "").matches("1"));
+    }
+
+    #[test]
+    fn memo_skips_update_with_unchanged_deps() {
+        use std::cell::Cell;
+
+        use wasm_bindgen::{JsCast, JsValue};
+
+        /// View counting its updates, without touching the DOM
+        struct Counted<'a>(&'a Cell<u32>);
+
+        impl View for Counted<'_> {
+            type Product = TextProduct<u32>;
+
+            fn build(self, _: In<Self::Product>) -> Out<Self::Product> {
+                unreachable!()
+            }
+
+            fn update(self, _: &mut Self::Product) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let updates = Cell::new(0);
+        let mut product = Memoized {
+            deps: (String::from("foo"), vec![1, 2]),
+            inner: TextProduct {
+                memo: 0,
+                node: JsValue::UNDEFINED.unchecked_into(),
+            },
+        };
+
+        memo((String::from("foo"), vec![1, 2]), || Counted(&updates)).update(&mut product);
+        assert_eq!(updates.get(), 0);
+
+        memo((String::from("foo"), vec![1, 2, 3]), || Counted(&updates)).update(&mut product);
+        assert_eq!(updates.get(), 1);
+        assert_eq!(product.deps.1, [1, 2, 3]);
+
+        memo((String::from("foo"), vec![1, 2, 3]), || Counted(&updates)).update(&mut product);
+        assert_eq!(updates.get(), 1);
+    }
+
+    #[test]
+    fn tuple_diff_updates_all_fields() {
+        let mut memo = ("foo", 1).into_memo();
+
+        assert!(!("foo", 1).diff(&mut memo));
+        assert!(("bar", 2).diff(&mut memo));
+        assert_eq!(memo, ("bar".to_string(), 2));
+    }
+}
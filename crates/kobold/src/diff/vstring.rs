@@ -99,6 +99,8 @@ where
     }
 }
 
+// `From<VString> for String` would conflict with the blanket `From<S> for VString` above.
+#[allow(clippy::from_over_into)]
 impl Into<String> for VString {
     fn into(self) -> String {
         self.inner
@@ -124,7 +126,7 @@ impl Eq for VString {}
 
 impl PartialOrd<VString> for VString {
     fn partial_cmp(&self, other: &VString) -> Option<std::cmp::Ordering> {
-        self.inner.partial_cmp(&other.inner)
+        Some(self.cmp(other))
     }
 }
 
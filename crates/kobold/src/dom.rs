@@ -5,11 +5,13 @@
 //! Utilities for mounting elements in the DOM
 
 use std::ops::Deref;
+use std::thread::LocalKey;
 
 use wasm_bindgen::{JsCast, JsValue};
-use web_sys::Node;
+use web_sys::{DomRect, Element, Node};
 
-use crate::internal;
+use crate::internal::{self, In, Out, Precompiled};
+use crate::View;
 
 /// A type that can be mounted in the DOM
 pub trait Mountable: 'static {
@@ -20,11 +22,114 @@ pub trait Mountable: 'static {
     /// Returns a reference to the root DOM node of this product.
     fn js(&self) -> &JsValue;
 
+    /// Returns the root DOM node of this product as a [`Node`], for passing it to
+    /// `web-sys` APIs. For fragments this is the `DocumentFragment`, which is empty
+    /// while the fragment is mounted.
+    fn node(&self) -> Node {
+        self.js().unchecked_ref::<Node>().clone()
+    }
+
     /// Unmount the root of this product from the DOM.
     fn unmount(&self);
 
     /// Replace the root of this product in the DOM with another.
     fn replace_with(&self, new: &JsValue);
+
+    /// Returns the size and position of the root of this product relative to the viewport,
+    /// via [`getBoundingClientRect`](https://developer.mozilla.org/en-US/docs/Web/API/Element/getBoundingClientRect).
+    ///
+    /// Returns `None` if the root is not an element, such as a text node or a fragment.
+    fn rect(&self) -> Option<DomRect> {
+        self.js()
+            .dyn_ref::<Element>()
+            .map(Element::get_bounding_client_rect)
+    }
+
+    /// Scroll the root of this product into view via
+    /// [`scrollIntoView`](https://developer.mozilla.org/en-US/docs/Web/API/Element/scrollIntoView),
+    /// with a smooth animation if `smooth` is `true`.
+    ///
+    /// Does nothing if the root is not an element, such as a text node or a fragment.
+    fn scroll_into_view(&self, smooth: bool) {
+        internal::scroll_into_view(self.js(), smooth);
+    }
+
+    /// Returns the [`tagName`](https://developer.mozilla.org/en-US/docs/Web/API/Element/tagName)
+    /// of the root of this product, upper case for HTML elements.
+    ///
+    /// Returns `None` if the root is not an element, such as a text node or a fragment.
+    fn tag_name(&self) -> Option<String> {
+        self.js().dyn_ref::<Element>().map(Element::tag_name)
+    }
+
+    /// Parse `html` and insert the resulting nodes at `position` relative to the root of
+    /// this product, via
+    /// [`insertAdjacentHTML`](https://developer.mozilla.org/en-US/docs/Web/API/Element/insertAdjacentHTML).
+    ///
+    /// Returns `false` if nothing was inserted, because the root is not an element, or
+    /// `position` is outside of the root while it has no parent. See [`Position`] for
+    /// how inserted nodes interact with the rest of the product.
+    ///
+    /// # Security
+    ///
+    /// The HTML is parsed as markup, so it must come from a trusted source.
+    fn insert_adjacent_html(&self, position: Position, html: &str) -> bool {
+        internal::insert_adjacent_html(self.js(), position.as_str(), html)
+    }
+
+    /// Insert `element` at `position` relative to the root of this product, via
+    /// [`insertAdjacentElement`](https://developer.mozilla.org/en-US/docs/Web/API/Element/insertAdjacentElement),
+    /// moving it if it's already in the document.
+    ///
+    /// Returns `false` if nothing was inserted, same as [`insert_adjacent_html`](Mountable::insert_adjacent_html).
+    fn insert_adjacent_element(&self, position: Position, element: &Element) -> bool {
+        internal::insert_adjacent_element(self.js(), position.as_str(), element)
+    }
+
+    /// Returns `true` if the root of this product is an element.
+    fn is_element(&self) -> bool {
+        self.js().has_type::<Element>()
+    }
+
+    /// Returns `true` if the root of this product is a [`Fragment`].
+    fn is_fragment(&self) -> bool {
+        self.js()
+            .dyn_ref::<Node>()
+            .is_some_and(|node| node.node_type() == Node::DOCUMENT_FRAGMENT_NODE)
+    }
+}
+
+/// Position relative to an element for [`Mountable::insert_adjacent_html`] and
+/// [`Mountable::insert_adjacent_element`].
+///
+/// Inserted nodes are not managed by **Kobold**: they are never updated or removed
+/// when the product is, only along with their parent. Inserting before or after
+/// the root puts the nodes in its parent, which for the first or last element of a
+/// mounted [`Fragment`] means inside the fragment, as its head and tail anchors are
+/// empty text nodes around all of its children. Such nodes are then moved along with
+/// the fragment when it's unmounted or replaced.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Position {
+    /// Before the element itself
+    BeforeBegin,
+    /// Inside the element, before its first child
+    AfterBegin,
+    /// Inside the element, after its last child
+    BeforeEnd,
+    /// After the element itself
+    AfterEnd,
+}
+
+impl Position {
+    /// Name of the position as used by the DOM, such as `"beforebegin"`.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Position::BeforeBegin => "beforebegin",
+            Position::AfterBegin => "afterbegin",
+            Position::BeforeEnd => "beforeend",
+            Position::AfterEnd => "afterend",
+        }
+    }
 }
 
 /// A light-weight [`Deref`]-like trait that
@@ -54,6 +159,93 @@ where
     fn replace_with(&self, new: &JsValue) {
         self.anchor().replace_with(new);
     }
+
+    fn rect(&self) -> Option<DomRect> {
+        self.anchor().rect()
+    }
+}
+
+/// Handle to a [`View`] mounted with [`mount`](crate::mount), owning its product.
+///
+/// Unlike [`start`](crate::start), which keeps the view alive for the lifetime of the
+/// page, the view is removed from the DOM when the `Root` is dropped. Views can be
+/// updated or replaced through the handle, to drive **Kobold** from outside of a
+/// stateful view:
+///
+/// ```no_run
+/// use kobold::prelude::*;
+///
+/// #[component]
+/// fn loading() -> impl View {
+///     view! { <p.loading>"Loading..."</p> }
+/// }
+///
+/// #[component]
+/// fn greeting(name: &str) -> impl View + '_ {
+///     view! { <p>"Hello "{ name }"!"</p> }
+/// }
+///
+/// fn main() {
+///     let root = kobold::mount(view! { <!loading> });
+///
+///     // Different view type, its product replaces the old one in the DOM
+///     let mut root = root.replace(view! { <!greeting name="Kobold"> });
+///
+///     // Same view type, the product is diffed in place
+///     root.update(view! { <!greeting name="World"> });
+///
+///     // Keep the view mounted after `main` returns
+///     std::mem::forget(root);
+/// }
+/// ```
+pub struct Root<P: Mountable> {
+    product: Box<P>,
+}
+
+impl<P: Mountable> Root<P> {
+    pub(crate) fn new(product: Box<P>) -> Self {
+        Root { product }
+    }
+
+    /// Update the mounted product with a new view of the same type.
+    pub fn update<V>(&mut self, view: V)
+    where
+        V: View<Product = P>,
+    {
+        view.update(&mut self.product);
+    }
+
+    /// Build a new view of any type, replacing the currently mounted one in the DOM.
+    pub fn replace<V: View>(self, view: V) -> Root<V::Product> {
+        let product = In::boxed(|p| view.build(p));
+
+        // Dropping the old root afterwards unmounts a product that's already out of
+        // the DOM, which does nothing
+        self.product.replace_with(product.js());
+
+        Root { product }
+    }
+
+    /// Returns the root DOM node of the mounted product, see [`Mountable::node`].
+    pub fn node(&self) -> Node {
+        self.product.node()
+    }
+
+    /// Returns a reference to the mounted product.
+    pub fn product(&self) -> &P {
+        &self.product
+    }
+
+    /// Remove the view from the DOM and drop its product, along with any event listeners,
+    /// state and timers of stateful views in it. This is the same as dropping the `Root`,
+    /// spelled out for when the view is torn down as part of a larger lifecycle.
+    pub fn unmount(self) {}
+}
+
+impl<P: Mountable> Drop for Root<P> {
+    fn drop(&mut self) {
+        self.product.unmount();
+    }
 }
 
 /// Thin-wrapper around a [`DocumentFragment`](https://developer.mozilla.org/en-US/docs/Web/API/DocumentFragment) node.
@@ -73,6 +265,28 @@ impl From<Node> for Fragment {
     }
 }
 
+impl Fragment {
+    /// Number of child nodes currently in this fragment, whether it's mounted or not.
+    ///
+    /// This walks the DOM, it's meant for tests and debugging rather than for use
+    /// in render functions.
+    pub fn len(&self) -> usize {
+        internal::fragment_children(&self.0).len()
+    }
+
+    /// Returns `true` if this fragment has no child nodes, see [`len`](Fragment::len).
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterate over the child nodes currently in this fragment, see [`len`](Fragment::len).
+    pub fn children(&self) -> impl Iterator<Item = Node> {
+        internal::fragment_children(&self.0)
+            .into_iter()
+            .map(JsCast::unchecked_into)
+    }
+}
+
 impl AsRef<JsValue> for Fragment {
     fn as_ref(&self) -> &JsValue {
         self.0.as_ref()
@@ -120,6 +334,13 @@ impl FragmentBuilder {
     pub fn append(&self, child: &JsValue) {
         internal::obj(&self.tail).append_before(child);
     }
+
+    /// Move an already mounted `child` to the end of this fragment. Unlike
+    /// [`append`](FragmentBuilder::append) this also moves the nodes of a child
+    /// whose root is a mounted fragment, which is empty while it's mounted.
+    pub fn move_to_end(&self, child: &JsValue) {
+        internal::fragment_move_before(&self.tail, child);
+    }
 }
 
 impl Deref for FragmentBuilder {
@@ -160,4 +381,222 @@ impl Mountable for Fragment {
     fn replace_with(&self, new: &JsValue) {
         internal::fragment_replace(&self.0, new)
     }
+
+    fn rect(&self) -> Option<DomRect> {
+        None
+    }
+}
+
+/// A static subtree parsed once from HTML into a
+/// [`<template>`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/template)
+/// element, and then cloned for every use.
+///
+/// Cloning a parsed subtree with [`clone_deep`](Template::clone_deep) is cheaper than
+/// creating the same subtree node by node, which makes templates useful for large
+/// static parts of views that are instantiated many times.
+///
+/// `Template` holds a DOM node, so it cannot be put in a `static`. Use `thread_local!`
+/// instead, and render it with [`Template::view`]:
+///
+/// ```
+/// use kobold::prelude::*;
+/// use kobold::dom::Template;
+///
+/// thread_local! {
+///     static ICON: Template = Template::new("<svg viewBox=\"0 0 8 8\"><circle r=\"4\"/></svg>");
+/// }
+///
+/// let view = view! {
+///     <div.icon>{ Template::view(&ICON) }</div>
+/// };
+/// ```
+pub struct Template {
+    html: &'static str,
+    node: std::cell::OnceCell<Node>,
+}
+
+impl Template {
+    /// Create a new template from HTML with a single root node. The HTML is
+    /// parsed lazily the first time the template is cloned.
+    pub const fn new(html: &'static str) -> Self {
+        Template {
+            html,
+            node: std::cell::OnceCell::new(),
+        }
+    }
+
+    /// HTML the template was created with.
+    pub const fn html(&self) -> &'static str {
+        self.html
+    }
+
+    /// Deep clone the root node of this template, including all of its descendants.
+    pub fn clone_deep(&self) -> Node {
+        clone_deep(self.node.get_or_init(|| internal::template(self.html)))
+    }
+
+    /// Static view of a thread-local template. The template is cloned when the view is
+    /// built, and never updated afterwards.
+    pub fn view(key: &'static LocalKey<Template>) -> Precompiled<impl Fn() -> Node> {
+        Precompiled(move || key.with(Template::clone_deep))
+    }
+}
+
+/// Deep clone a DOM node, including all of its descendants, via
+/// [`cloneNode(true)`](https://developer.mozilla.org/en-US/docs/Web/API/Node/cloneNode).
+pub fn clone_deep(node: &Node) -> Node {
+    internal::clone_deep(node)
+}
+
+/// Render HTML that is only known at runtime, such as a user-provided theme, filling
+/// any `{{name}}` placeholders in its text with the matching `bindings`:
+///
+/// ```
+/// use kobold::prelude::*;
+/// use kobold::dom::template;
+///
+/// #[component]
+/// fn greeting<'a>(theme: &'a str, name: &'a str) -> impl View + 'a {
+///     // `theme` could be `<p class="hello">Hello, <b>{{name}}</b>!</p>`
+///     template(theme, [("name", name)])
+/// }
+/// # fn main() {}
+/// ```
+///
+/// The HTML is parsed once per distinct string and then cloned for every use, with the
+/// 64 most recently used strings kept parsed. Each placeholder becomes a text node that
+/// is updated whenever its binding changes, and placeholders without a binding are left
+/// empty. Changing the HTML itself rebuilds the whole template. Placeholders are only
+/// recognized in text, not in attributes.
+///
+/// # Security
+///
+/// The HTML is parsed as markup, so it must come from a trusted source. Inline event
+/// handlers such as `<img onerror="...">` in it will run scripts with the same access
+/// as your app. Bindings on the other hand are always rendered as text, so they can
+/// safely contain user input.
+pub const fn template<'a, const N: usize>(
+    html: &'a str,
+    bindings: [(&'a str, &'a str); N],
+) -> RuntimeTemplate<'a, N> {
+    RuntimeTemplate { html, bindings }
+}
+
+/// View rendering HTML parsed at runtime, see [`template`].
+pub struct RuntimeTemplate<'a, const N: usize> {
+    html: &'a str,
+    bindings: [(&'a str, &'a str); N],
+}
+
+pub struct RuntimeTemplateProduct {
+    html: String,
+    slots: Vec<Slot>,
+    fragment: FragmentBuilder,
+}
+
+struct Slot {
+    name: String,
+    node: Node,
+    text: String,
+}
+
+impl<const N: usize> RuntimeTemplate<'_, N> {
+    fn value(&self, name: &str) -> &str {
+        self.bindings
+            .iter()
+            .find(|(binding, _)| *binding == name)
+            .map(|(_, value)| *value)
+            .unwrap_or_default()
+    }
+}
+
+impl<const N: usize> View for RuntimeTemplate<'_, N> {
+    type Product = RuntimeTemplateProduct;
+
+    fn build(self, p: In<Self::Product>) -> Out<Self::Product> {
+        let fragment = FragmentBuilder::new();
+        let mut parsed = internal::template_slots(self.html).into_iter();
+        let mut slots = Vec::new();
+
+        if let Some(content) = parsed.next() {
+            fragment.append(&content);
+        }
+
+        while let (Some(name), Some(node)) = (parsed.next(), parsed.next()) {
+            let name = name.as_string().unwrap_or_default();
+            let node: Node = node.unchecked_into();
+            let text = self.value(&name);
+
+            TextContent.set(&node, text);
+
+            slots.push(Slot {
+                text: text.into(),
+                name,
+                node,
+            });
+        }
+
+        p.put(RuntimeTemplateProduct {
+            html: self.html.into(),
+            slots,
+            fragment,
+        })
+    }
+
+    fn update(self, p: &mut Self::Product) {
+        if p.html != self.html {
+            let old = In::replace(p, move |p| self.build(p));
+
+            old.replace_with(p.js());
+            return;
+        }
+
+        for slot in &mut p.slots {
+            let text = self.value(&slot.name);
+
+            if slot.text != text {
+                text.clone_into(&mut slot.text);
+                TextContent.set(&slot.node, text);
+            }
+        }
+    }
+}
+
+impl Anchor for RuntimeTemplateProduct {
+    type Js = Node;
+    type Target = Fragment;
+
+    fn anchor(&self) -> &Fragment {
+        &self.fragment
+    }
+}
+
+/// Set the attribute `name` to `value` on an element, for use in custom [`View`](crate::View)
+/// products built outside of the [`view!`](crate::view) macro.
+///
+/// # Panics
+///
+/// Panics if `node` is not an element.
+pub fn set_attribute(node: &Node, name: &str, value: &str) {
+    assert_element(node);
+
+    internal::obj(node).set_attr(name, value);
+}
+
+/// Remove the attribute `name` from an element, see [`set_attribute`].
+///
+/// # Panics
+///
+/// Panics if `node` is not an element.
+pub fn remove_attribute(node: &Node, name: &str) {
+    assert_element(node);
+
+    internal::obj(node).remove_attr(name);
+}
+
+fn assert_element(node: &Node) {
+    assert!(
+        node.node_type() == Node::ELEMENT_NODE,
+        "Attributes can only be set on elements"
+    );
 }
@@ -3,13 +3,105 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 //! Utilities for handling DOM events
+//!
+//! Event handlers declared in the [`view!`](crate::view) macro receive a wrapper around
+//! the `web-sys` type matching the event name, such as [`MouseEvent`] for `onclick`,
+//! [`WheelEvent`] for `onwheel`, [`PointerEvent`] for `onpointermove`, [`TouchEvent`]
+//! for `ontouchstart`, [`DragEvent`] for `ondrop`, [`AnimationEvent`] for `onanimationend`,
+//! [`TransitionEvent`] for `ontransitionend`, or [`ClipboardEvent`] for `onpaste`. Other
+//! events, including `onscroll`, receive a plain [`Event`].
+//!
+//! ```
+//! use kobold::prelude::*;
+//!
+//! let view = stateful(0.0, |zoom: &Hook<f64>| {
+//!     bind! { zoom:
+//!         let onwheel = move |e: WheelEvent<_>| *zoom += e.delta_y();
+//!     }
+//!
+//!     view! { <div {onwheel}>"Zoom: "{ zoom.get() }</div> }
+//! });
+//! ```
+//!
+//! Animation and transition events can be used to remove an element once it has faded out:
+//!
+//! ```
+//! use kobold::prelude::*;
+//!
+//! let view = stateful(true, |visible: &Hook<bool>| {
+//!     bind! { visible:
+//!         let onanimationend = move |e: AnimationEvent<_>| {
+//!             if e.animation_name() == "fade-out" {
+//!                 *visible = false;
+//!             }
+//!         };
+//!     }
+//!
+//!     visible.get().then(|| view! { <div.fade-out {onanimationend}>"Saved!"</div> })
+//! });
+//! ```
+//!
+//! Clipboard events give access to the copied or pasted data with
+//! [`clipboard_data`](web_sys::ClipboardEvent::clipboard_data), for example to paste
+//! rich text into an editor as plain text:
+//!
+//! ```
+//! use kobold::prelude::*;
+//!
+//! let view = stateful(String::new, |text: &Hook<String>| {
+//!     bind! { text:
+//!         let onpaste = move |e: ClipboardEvent<_>| {
+//!             e.prevent_default();
+//!
+//!             if let Some(data) = e.clipboard_data() {
+//!                 text.push_str(&data.get_data("text/plain").unwrap_or_default());
+//!             }
+//!         };
+//!     }
+//!
+//!     view! { <div.editor contenteditable="true" {onpaste}>{ ref text }</div> }
+//! });
+//! ```
+//!
+//! A single listener on a parent element can handle events for all of its children,
+//! telling them apart by their `data-*` attributes with [`data`](MouseEvent::data):
+//!
+//! ```
+//! use kobold::prelude::*;
+//!
+//! const ITEMS: [(u32, &str); 3] = [(1, "Home"), (2, "Settings"), (3, "Log out")];
+//!
+//! #[component]
+//! fn menu() -> impl View {
+//!     stateful(|| None, |selected: &Hook<Option<u32>>| {
+//!         bind! { selected:
+//!             let onclick = move |e: MouseEvent<_>| {
+//!                 *selected = e.data("id").and_then(|id| id.parse().ok());
+//!             };
+//!         }
+//!
+//!         view! {
+//!             <ul {onclick}>
+//!             {
+//!                 for ITEMS.iter().map(|(id, label)| view! {
+//!                     <li data-id={*id}><span>{ static *label }</span></li>
+//!                 })
+//!             }
+//!             </ul>
+//!             <p>"Selected: "{ selected.map(|id| id.to_string()) }</p>
+//!         }
+//!     })
+//! }
+//! # fn main() {}
+//! ```
 
 use std::marker::PhantomData;
 use std::ops::Deref;
 
 use wasm_bindgen::prelude::wasm_bindgen;
 use wasm_bindgen::{JsCast, JsValue};
-use web_sys::{HtmlElement, HtmlInputElement};
+use web_sys::HtmlTextAreaElement;
+use web_sys::{File, HtmlElement, HtmlFormElement, HtmlInputElement, HtmlSelectElement};
 
 use crate::internal::{self, In, Out};
 
@@ -72,11 +164,36 @@ macro_rules! event {
                 {
                     EventTarget(self.event.unchecked_ref::<EventWithTarget>().current_target().unchecked_into())
                 }
+
+                /// Value of a `data-*` attribute on the target element, or on its closest
+                /// ancestor that has it up to the current target. The `key` is camelCased
+                /// like in [`HtmlElement::dataset`], so `data-item-id` is read as `itemId`.
+                ///
+                /// See the [module documentation](self) for an example of delegating
+                /// events from a list.
+                pub fn data(&self, key: &str) -> Option<String> {
+                    closest_data(self.event.unchecked_ref(), key)
+                }
             }
         )*
     };
 }
 
+fn closest_data(event: &EventWithTarget, key: &str) -> Option<String> {
+    let current = event.current_target();
+    let mut el = event.target();
+
+    loop {
+        if let Some(value) = el.dataset().get(key) {
+            return Some(value);
+        }
+        if el == current {
+            return None;
+        }
+        el = el.parent_element()?.unchecked_into();
+    }
+}
+
 mod sealed {
     pub trait EventCast {}
 
@@ -92,6 +209,20 @@ event! {
     KeyboardEvent,
     /// [`web_sys::MouseEvent`](web_sys::MouseEvent)
     MouseEvent,
+    /// [`web_sys::WheelEvent`](web_sys::WheelEvent)
+    WheelEvent,
+    /// [`web_sys::PointerEvent`](web_sys::PointerEvent)
+    PointerEvent,
+    /// [`web_sys::TouchEvent`](web_sys::TouchEvent)
+    TouchEvent,
+    /// [`web_sys::DragEvent`](web_sys::DragEvent)
+    DragEvent,
+    /// [`web_sys::AnimationEvent`](web_sys::AnimationEvent)
+    AnimationEvent,
+    /// [`web_sys::TransitionEvent`](web_sys::TransitionEvent)
+    TransitionEvent,
+    /// [`web_sys::ClipboardEvent`](web_sys::ClipboardEvent)
+    ClipboardEvent,
 }
 
 pub trait IntoListener<E: EventCast> {
@@ -143,6 +274,91 @@ where
     }
 }
 
+/// Optional listener, `None` leaves the event unhandled. The element keeps a single
+/// JS listener that forwards events only while there is a listener to call, so it
+/// can change between `Some` and `None` on updates:
+///
+/// ```
+/// # use kobold::prelude::*;
+/// #[component]
+/// fn submit(disabled: bool) -> impl View {
+///     let onclick = (!disabled).then_some(|_: MouseEvent<_>| {
+///         // ...
+///     });
+///
+///     view! { <button {onclick}>"Submit"</button> }
+/// }
+/// # fn main() {}
+/// ```
+///
+/// Listeners bound to a [`Hook`](crate::stateful::Hook) need to be converted with
+/// `into_listener` first, such as `(!disabled).then(|| hook.bind(...).into_listener())`.
+impl<E, L> Listener<E> for Option<L>
+where
+    L: Listener<E>,
+    E: EventCast,
+{
+    type Product = OptionalListener<L::Product>;
+
+    fn build(self, p: In<Self::Product>) -> Out<Self::Product> {
+        p.put(OptionalListener {
+            handler: internal::make_optional_handler(),
+            listener: self.map(|listener| In::boxed(|p| listener.build(p))),
+        })
+    }
+
+    fn update(self, p: &mut Self::Product) {
+        if p.replace(self) {
+            p.forward();
+        }
+    }
+}
+
+pub struct OptionalListener<P> {
+    handler: JsValue,
+    listener: Option<Box<P>>,
+}
+
+impl<P> OptionalListener<P> {
+    /// Update the current listener with the `new` one, or swap it out when one of them
+    /// is `None`. Returns `true` if the handler has to forward to a different listener.
+    fn replace<E, L>(&mut self, new: Option<L>) -> bool
+    where
+        E: EventCast,
+        L: Listener<E, Product = P>,
+    {
+        match (new, &mut self.listener) {
+            (Some(new), Some(old)) => {
+                new.update(old);
+                false
+            }
+            (None, None) => false,
+            (new, old) => {
+                *old = new.map(|listener| In::boxed(|p| listener.build(p)));
+                true
+            }
+        }
+    }
+}
+
+impl<P: ListenerHandle> OptionalListener<P> {
+    fn forward(&mut self) {
+        let inner = match &mut self.listener {
+            Some(listener) => listener.js_value(),
+            None => JsValue::UNDEFINED,
+        };
+
+        internal::set_optional_handler(&self.handler, &inner);
+    }
+}
+
+impl<P: ListenerHandle> ListenerHandle for OptionalListener<P> {
+    fn js_value(&mut self) -> JsValue {
+        self.forward();
+        self.handler.clone()
+    }
+}
+
 pub struct ListenerProduct<F, E> {
     closure: F,
     _event: PhantomData<E>,
@@ -176,8 +392,243 @@ impl<T> Deref for EventTarget<T> {
     }
 }
 
-impl EventTarget<HtmlInputElement> {
-    pub fn focus(&self) {
-        drop(self.0.focus());
+macro_rules! focusable {
+    ($($el:ident),*) => {
+        $(
+            impl EventTarget<$el> {
+                /// Move keyboard focus to this element.
+                pub fn focus(&self) {
+                    drop(self.0.focus());
+                }
+
+                /// Remove keyboard focus from this element, does nothing if it isn't focused.
+                pub fn blur(&self) {
+                    drop(self.0.blur());
+                }
+
+                /// Select all of the text in this element, such as to replace it
+                /// when editing begins.
+                pub fn select(&self) {
+                    self.0.select();
+                }
+            }
+        )*
+    };
+}
+
+focusable!(HtmlInputElement, HtmlTextAreaElement);
+
+impl Event<HtmlInputElement> {
+    /// Files selected in the `<input type="file">` this event was fired on,
+    /// empty for other inputs.
+    ///
+    /// ```no_run
+    /// use kobold::prelude::*;
+    /// use kobold::reexport::wasm_bindgen::JsCast;
+    /// use wasm_bindgen_futures::JsFuture;
+    ///
+    /// #[component]
+    /// fn upload() -> impl View {
+    ///     stateful(String::new, |text| {
+    ///         let onchange = text.bind_async(|text, e: Event<HtmlInputElement>| async move {
+    ///             let Some(file) = e.files().pop() else {
+    ///                 return;
+    ///             };
+    ///
+    ///             if let Ok(content) = JsFuture::from(file.text()).await {
+    ///                 text.set(content.as_string().unwrap_or_default());
+    ///             }
+    ///         });
+    ///
+    ///         view! {
+    ///             <input type="file" {onchange}>
+    ///             <pre>{ ref text }</pre>
+    ///         }
+    ///     })
+    /// }
+    /// # use kobold::reexport::web_sys::HtmlInputElement;
+    /// # fn main() {}
+    /// ```
+    pub fn files(&self) -> Vec<File> {
+        let Some(files) = self.current_target().files() else {
+            return Vec::new();
+        };
+
+        (0..files.length()).filter_map(|n| files.get(n)).collect()
+    }
+
+    /// Value the `<input>` had before it was changed, useful for undo stacks or validating
+    /// how much the value has changed in `onchange` listeners.
+    ///
+    /// This is the value the input had when it was focused, or when the last `change`
+    /// event on it has finished. The values are tracked by a single pair of listeners on
+    /// the `document`, installed the first time this method is called, and are held weakly,
+    /// so no closures are created per element. Until a control has been focused with the
+    /// listeners installed its default value is returned, which is also the case for the
+    /// very first call.
+    ///
+    /// The same method is available for `<textarea>` and `<select>` events.
+    ///
+    /// ```no_run
+    /// use kobold::prelude::*;
+    /// # use kobold::reexport::web_sys::HtmlInputElement;
+    ///
+    /// #[component]
+    /// fn editor() -> impl View {
+    ///     stateful(Vec::<String>::new, |undo| {
+    ///         let onchange = undo.bind(|undo, e: Event<HtmlInputElement>| {
+    ///             undo.push(e.previous_value());
+    ///         });
+    ///
+    ///         view! {
+    ///             <input {onchange}>
+    ///             <p>{ undo.len() }" changes to undo"</p>
+    ///         }
+    ///     })
+    /// }
+    /// # fn main() {}
+    /// ```
+    pub fn previous_value(&self) -> String {
+        internal::previous_value(&self.current_target())
+    }
+}
+
+impl Event<HtmlFormElement> {
+    /// Names and values of all fields in the `<form>` this event was fired on, such as
+    /// on `onsubmit`, in document order. Fields without a `name` are skipped, as are
+    /// file inputs.
+    ///
+    /// ```no_run
+    /// use kobold::prelude::*;
+    /// # async fn sign_up(_: Vec<(String, String)>) -> String { String::new() }
+    ///
+    /// #[component]
+    /// fn signup() -> impl View {
+    ///     stateful(String::new, |status| {
+    ///         let onsubmit = status.bind_async(|status, e: Event<HtmlFormElement>| {
+    ///             // Must be called before the returned future is polled
+    ///             e.prevent_default();
+    ///
+    ///             let fields: Vec<_> = e.form_data().collect();
+    ///
+    ///             async move {
+    ///                 status.set(sign_up(fields).await);
+    ///             }
+    ///         });
+    ///
+    ///         view! {
+    ///             <form {onsubmit}>
+    ///                 <input name="user">
+    ///                 <input name="email" type="email">
+    ///                 <button type="submit">"Sign up"</button>
+    ///             </form>
+    ///             <p>{ ref status }</p>
+    ///         }
+    ///     })
+    /// }
+    /// # use kobold::reexport::web_sys::HtmlFormElement;
+    /// # fn main() {}
+    /// ```
+    pub fn form_data(&self) -> impl Iterator<Item = (String, String)> {
+        let mut entries = internal::form_entries(&self.current_target()).into_iter();
+
+        std::iter::from_fn(move || Some((entries.next()?, entries.next()?)))
+    }
+}
+
+impl Event<HtmlTextAreaElement> {
+    /// Value the `<textarea>` had before it was changed, see
+    /// [`Event<HtmlInputElement>::previous_value`].
+    pub fn previous_value(&self) -> String {
+        internal::previous_value(&self.current_target())
+    }
+}
+
+impl Event<HtmlSelectElement> {
+    /// Value the `<select>` had before it was changed, see
+    /// [`Event<HtmlInputElement>::previous_value`]. Selects that haven't been focused yet
+    /// return the value of the option marked as `selected` when it was rendered.
+    pub fn previous_value(&self) -> String {
+        internal::previous_value(&self.current_target())
+    }
+
+    /// Typed value of the selected `<option>`, looked up by its index in the `options`
+    /// the `<select>` was rendered from. Returns `None` if nothing is selected.
+    ///
+    /// This avoids parsing the `value` of the option back from a string:
+    ///
+    /// ```
+    /// use kobold::prelude::*;
+    /// # use kobold::reexport::web_sys::HtmlSelectElement;
+    ///
+    /// #[derive(Clone, Copy, PartialEq)]
+    /// enum Priority {
+    ///     Low,
+    ///     High,
+    /// }
+    ///
+    /// const PRIORITIES: [(Priority, &str); 2] = [(Priority::Low, "Low"), (Priority::High, "High")];
+    ///
+    /// #[component]
+    /// fn priority() -> impl View {
+    ///     stateful(|| Priority::Low, |priority| {
+    ///         bind! { priority:
+    ///             let onchange = move |e: Event<HtmlSelectElement>| {
+    ///                 if let Some((selected, _)) = e.selected_in(&PRIORITIES) {
+    ///                     *priority = *selected;
+    ///                 }
+    ///             };
+    ///         }
+    ///
+    ///         view! {
+    ///             <select {onchange}>
+    ///             {
+    ///                 for PRIORITIES.iter().map(|(value, label)| view! {
+    ///                     <option selected={*value == priority.get()}>{ static *label }</option>
+    ///                 })
+    ///             }
+    ///             </select>
+    ///         }
+    ///     })
+    /// }
+    /// # fn main() {}
+    /// ```
+    ///
+    /// To have the options built from the slice as well, see [`form::select`](crate::form::select).
+    pub fn selected_in<'a, T>(&self, options: &'a [T]) -> Option<&'a T> {
+        let index = self.current_target().selected_index();
+
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| options.get(index))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn optional_listener_toggle() {
+        let click: fn(web_sys::Event) = |_| {};
+        let none: Option<fn(web_sys::Event)> = None;
+        let mut p = OptionalListener {
+            handler: JsValue::UNDEFINED,
+            listener: None,
+        };
+
+        assert!(p.replace(Some(click)));
+        assert!(p.listener.is_some());
+
+        // Same listener is updated in place
+        assert!(!p.replace(Some(click)));
+        assert!(p.listener.is_some());
+
+        assert!(p.replace(none));
+        assert!(p.listener.is_none());
+
+        assert!(!p.replace(none));
+        assert!(p.replace(Some(click)));
+        assert!(p.listener.is_some());
     }
 }
@@ -0,0 +1,614 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Utilities for reading forms
+//!
+//! [`FromForm`] reads all values of a `<form>` into a struct at once, matching its
+//! fields to the `name`s of the form fields. It's usually derived:
+//!
+//! ```no_run
+//! use kobold::prelude::*;
+//! use kobold::form::FromForm;
+//!
+//! #[derive(FromForm)]
+//! struct SignUp {
+//!     user: String,
+//!     age: u8,
+//!     // Empty or missing fields are `None`
+//!     referral: Option<String>,
+//!     // Checkboxes are `true` when checked
+//!     newsletter: bool,
+//!     #[form(name = "favorite-color")]
+//!     color: String,
+//! }
+//!
+//! #[component]
+//! fn signup() -> impl View {
+//!     stateful(String::new, |status| {
+//!         let onsubmit = status.bind(|status, e: Event<HtmlFormElement>| {
+//!             e.prevent_default();
+//!
+//!             *status = match SignUp::from_form(&e.current_target()) {
+//!                 Ok(signup) => format!("Welcome, {}!", signup.user),
+//!                 Err(err) => err.to_string(),
+//!             };
+//!         });
+//!
+//!         view! {
+//!             <form {onsubmit}>
+//!                 <input name="user">
+//!                 <input name="age" type="number">
+//!                 <input name="referral">
+//!                 <input name="newsletter" type="checkbox">
+//!                 <input name="favorite-color" type="color">
+//!                 <button type="submit">"Sign up"</button>
+//!             </form>
+//!             <p>{ ref status }</p>
+//!         }
+//!     })
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! Derived implementations parse each field with [`FromStr`]. `Option` fields are
+//! `None` if the form field is missing or empty, `bool` fields are `true` if the form
+//! field is present, as with checked checkboxes, and `Vec` fields collect all values
+//! with the field's name, such as from a `<select multiple>`.
+
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use web_sys::{HtmlFormElement, HtmlOptionElement, HtmlSelectElement, Node};
+
+use crate::dom::Anchor;
+use crate::internal::{self, In, Out};
+use crate::View;
+
+/// Type that can be read from the values of a `<form>`, see the [module documentation](self).
+pub trait FromForm: Sized {
+    /// Read from name and value pairs of form fields, in document order.
+    fn from_fields(fields: &[(String, String)]) -> Result<Self, FormError>;
+
+    /// Read from the current values of all named fields in the `form`. File inputs
+    /// are skipped.
+    fn from_form(form: &HtmlFormElement) -> Result<Self, FormError> {
+        Self::from_fields(&entries(form))
+    }
+}
+
+/// Derive [`FromForm`] for a struct with named fields. Fields are matched by their
+/// name, or the one set with `#[form(name = "...")]`.
+///
+/// ```
+/// use kobold::form::{FieldErrorKind, FromForm};
+///
+/// #[derive(FromForm)]
+/// struct Filter {
+///     query: String,
+///     #[form(name = "max-price")]
+///     max_price: Option<u32>,
+/// }
+///
+/// let fields = [("query".to_string(), "lamp".to_string())];
+/// let filter = Filter::from_fields(&fields).unwrap();
+///
+/// assert_eq!(filter.query, "lamp");
+/// assert_eq!(filter.max_price, None);
+///
+/// let fields = [("max-price".to_string(), "cheap".to_string())];
+/// let err = Filter::from_fields(&fields).err().unwrap();
+///
+/// assert_eq!(err.errors()[0].kind, FieldErrorKind::Missing);
+/// assert_eq!(err.get("max-price").unwrap().field, "max-price");
+/// ```
+pub use kobold_macros::FromForm;
+
+fn entries(form: &HtmlFormElement) -> Vec<(String, String)> {
+    let mut entries = internal::form_entries(form).into_iter();
+
+    std::iter::from_fn(move || Some((entries.next()?, entries.next()?))).collect()
+}
+
+/// Errors of all fields that couldn't be read with [`FromForm`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FormError {
+    errors: Vec<FieldError>,
+}
+
+/// Error reading a single field of a form.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldError {
+    /// Name of the form field
+    pub field: &'static str,
+    pub kind: FieldErrorKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FieldErrorKind {
+    /// The form has no field with this name
+    Missing,
+    /// The value couldn't be parsed, with the message of the parse error
+    Invalid { value: String, message: String },
+}
+
+impl FormError {
+    #[doc(hidden)]
+    pub fn new(errors: Vec<FieldError>) -> Self {
+        FormError { errors }
+    }
+
+    /// Errors of all fields in the order of the struct fields.
+    pub fn errors(&self) -> &[FieldError] {
+        &self.errors
+    }
+
+    /// Error of the field with the given form `name`, for showing it next to the input.
+    pub fn get(&self, name: &str) -> Option<&FieldError> {
+        self.errors.iter().find(|error| error.field == name)
+    }
+}
+
+impl Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.kind {
+            FieldErrorKind::Missing => write!(f, "{} is missing", self.field),
+            FieldErrorKind::Invalid { value, message } => {
+                write!(
+                    f,
+                    "{} has an invalid value {value:?}: {message}",
+                    self.field
+                )
+            }
+        }
+    }
+}
+
+impl Display for FormError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (n, error) in self.errors.iter().enumerate() {
+            if n > 0 {
+                f.write_str(", ")?;
+            }
+            error.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for FieldError {}
+
+impl Error for FormError {}
+
+fn values<'a>(fields: &'a [(String, String)], name: &'a str) -> impl Iterator<Item = &'a str> {
+    fields
+        .iter()
+        .filter(move |(key, _)| key == name)
+        .map(|(_, value)| value.as_str())
+}
+
+fn parse<T>(field: &'static str, value: &str) -> Result<T, FieldError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value.parse().map_err(|err: T::Err| FieldError {
+        field,
+        kind: FieldErrorKind::Invalid {
+            value: value.into(),
+            message: err.to_string(),
+        },
+    })
+}
+
+/// Parse the first value of a required field.
+#[doc(hidden)]
+pub fn required<T>(fields: &[(String, String)], field: &'static str) -> Result<T, FieldError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    match values(fields, field).next() {
+        Some(value) => parse(field, value),
+        None => Err(FieldError {
+            field,
+            kind: FieldErrorKind::Missing,
+        }),
+    }
+}
+
+/// Parse the first value of a field, `None` if it's missing or empty.
+#[doc(hidden)]
+pub fn optional<T>(
+    fields: &[(String, String)],
+    field: &'static str,
+) -> Result<Option<T>, FieldError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    match values(fields, field).next() {
+        Some(value) if !value.is_empty() => parse(field, value).map(Some),
+        _ => Ok(None),
+    }
+}
+
+/// `true` if the field is present, as checkboxes are only submitted when checked.
+#[doc(hidden)]
+pub fn checked(fields: &[(String, String)], field: &'static str) -> Result<bool, FieldError> {
+    Ok(values(fields, field).next().is_some())
+}
+
+/// Parse all values of a field.
+#[doc(hidden)]
+pub fn all<T>(fields: &[(String, String)], field: &'static str) -> Result<Vec<T>, FieldError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    values(fields, field)
+        .map(|value| parse(field, value))
+        .collect()
+}
+
+/// Render a `<select>` with an `<option>` for each of the `options`, labeled with the
+/// string paired with its value. The option whose value is equal to `selected` is
+/// selected, and `onchange` is called with the value of the option the user picks,
+/// without parsing it back from a string. Also available as a component:
+///
+/// ```
+/// use kobold::prelude::*;
+/// use kobold::form::select;
+///
+/// #[derive(Clone, Copy, PartialEq)]
+/// enum Priority {
+///     Low,
+///     High,
+/// }
+///
+/// const PRIORITIES: [(Priority, &str); 2] = [(Priority::Low, "Low"), (Priority::High, "High")];
+///
+/// #[component]
+/// fn priority() -> impl View {
+///     stateful(|| Priority::Low, |priority| {
+///         let signal = priority.weak_signal();
+///
+///         view! {
+///             <!select
+///                 options={&PRIORITIES}
+///                 selected={priority.get()}
+///                 onchange={move |p: &Priority| signal.set(*p)}
+///             >
+///         }
+///     })
+/// }
+/// # fn main() {}
+/// ```
+///
+/// Options are diffed by the address of the slice, so they should be a `static` or
+/// `const` array. For a `<select>` whose options are rendered with the
+/// [`view!`](crate::view) macro use [`Event::selected_in`](crate::event::Event::selected_in)
+/// in its `onchange` instead.
+pub const fn select<T, F>(
+    options: &'static [(T, &'static str)],
+    selected: T,
+    onchange: F,
+) -> Select<T, F>
+where
+    T: PartialEq + 'static,
+    F: Fn(&T) + 'static,
+{
+    Select {
+        options,
+        selected,
+        onchange,
+    }
+}
+
+/// View rendering a `<select>` of typed options, see [`select`].
+pub struct Select<T: 'static, F> {
+    options: &'static [(T, &'static str)],
+    selected: T,
+    onchange: F,
+}
+
+pub struct SelectProduct<T: 'static, F> {
+    state: Rc<SelectState<T, F>>,
+    element: HtmlSelectElement,
+    selected: Option<usize>,
+    onchange: Closure<dyn FnMut(web_sys::Event)>,
+}
+
+struct SelectState<T: 'static, F> {
+    options: Cell<&'static [(T, &'static str)]>,
+    onchange: RefCell<Option<F>>,
+}
+
+impl<T, F> SelectState<T, F>
+where
+    F: Fn(&T),
+{
+    /// Call `onchange` with the value of the option at `index`. The callback is taken
+    /// out while it runs, so that the view can be updated with a new one from inside of it.
+    fn dispatch(&self, index: i32) {
+        let Some((value, _)) = usize::try_from(index)
+            .ok()
+            .and_then(|index| self.options.get().get(index))
+        else {
+            return;
+        };
+
+        let Some(onchange) = self.onchange.borrow_mut().take() else {
+            return;
+        };
+
+        onchange(value);
+
+        self.onchange.borrow_mut().get_or_insert(onchange);
+    }
+}
+
+impl<T: 'static, F> Select<T, F>
+where
+    T: PartialEq,
+{
+    fn selected_index(&self) -> Option<usize> {
+        self.options
+            .iter()
+            .position(|(value, _)| *value == self.selected)
+    }
+}
+
+fn render_options<T>(element: &HtmlSelectElement, options: &[(T, &str)]) {
+    element.set_inner_html("");
+
+    for (_, label) in options {
+        let option: HtmlOptionElement = internal::create_element("option").unchecked_into();
+
+        option.set_text(label);
+        element
+            .append_child(&option)
+            .expect_throw("failed to append option");
+    }
+}
+
+fn select_index(element: &HtmlSelectElement, index: Option<usize>) {
+    element.set_selected_index(index.map(|index| index as i32).unwrap_or(-1));
+}
+
+impl<T, F> View for Select<T, F>
+where
+    T: PartialEq + 'static,
+    F: Fn(&T) + 'static,
+{
+    type Product = SelectProduct<T, F>;
+
+    fn build(self, p: In<Self::Product>) -> Out<Self::Product> {
+        let element: HtmlSelectElement = internal::create_element("select").unchecked_into();
+        let selected = self.selected_index();
+
+        render_options(&element, self.options);
+        select_index(&element, selected);
+
+        let state = Rc::new(SelectState {
+            options: Cell::new(self.options),
+            onchange: RefCell::new(Some(self.onchange)),
+        });
+
+        let onchange = {
+            let state = Rc::downgrade(&state);
+
+            Closure::<dyn FnMut(web_sys::Event)>::new(move |e: web_sys::Event| {
+                if let Some(state) = state.upgrade() {
+                    let element: HtmlSelectElement =
+                        e.current_target().unwrap_throw().unchecked_into();
+
+                    state.dispatch(element.selected_index());
+                }
+            })
+        };
+
+        element
+            .add_event_listener_with_callback("change", onchange.as_ref().unchecked_ref())
+            .expect_throw("failed to add change listener");
+
+        p.put(SelectProduct {
+            state,
+            element,
+            selected,
+            onchange,
+        })
+    }
+
+    fn update(self, p: &mut Self::Product) {
+        let selected = self.selected_index();
+
+        if !std::ptr::eq(p.state.options.get(), self.options) {
+            p.state.options.set(self.options);
+            render_options(&p.element, self.options);
+            select_index(&p.element, selected);
+        } else if p.selected != selected {
+            select_index(&p.element, selected);
+        }
+
+        p.selected = selected;
+        *p.state.onchange.borrow_mut() = Some(self.onchange);
+    }
+}
+
+impl<T: 'static, F> Drop for SelectProduct<T, F> {
+    fn drop(&mut self) {
+        let _ = self
+            .element
+            .remove_event_listener_with_callback("change", self.onchange.as_ref().unchecked_ref());
+    }
+}
+
+impl<T: 'static, F: 'static> Anchor for SelectProduct<T, F> {
+    type Js = Node;
+    type Target = Node;
+
+    fn anchor(&self) -> &Node {
+        &self.element
+    }
+}
+
+/// `#[component]` handlers for `<!select options={..} selected={..} onchange={..}>`,
+/// see the [`select`] function.
+pub mod select {
+    use super::Select;
+
+    #[doc(hidden)]
+    pub struct Props<O, S, F> {
+        options: O,
+        selected: S,
+        onchange: F,
+    }
+
+    pub const fn props() -> Props<(), (), ()> {
+        Props {
+            options: (),
+            selected: (),
+            onchange: (),
+        }
+    }
+
+    impl<O, S, F> Props<O, S, F> {
+        pub fn options<T>(
+            self,
+            options: &'static [(T, &'static str)],
+        ) -> Props<&'static [(T, &'static str)], S, F> {
+            Props {
+                options,
+                selected: self.selected,
+                onchange: self.onchange,
+            }
+        }
+
+        pub fn selected<T>(self, selected: T) -> Props<O, T, F> {
+            Props {
+                options: self.options,
+                selected,
+                onchange: self.onchange,
+            }
+        }
+
+        pub fn onchange<C>(self, onchange: C) -> Props<O, S, C> {
+            Props {
+                options: self.options,
+                selected: self.selected,
+                onchange,
+            }
+        }
+    }
+
+    pub fn render<T, F>(props: Props<&'static [(T, &'static str)], T, F>) -> Select<T, F>
+    where
+        T: PartialEq + 'static,
+        F: Fn(&T) + 'static,
+    {
+        super::select(props.options, props.selected, props.onchange)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn fields(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn select_dispatches_typed_values() {
+        const OPTIONS: [(char, &str); 2] = [('a', "A"), ('b', "B")];
+
+        let picked = Rc::new(Cell::new(None));
+        let state = Rc::new(SelectState {
+            options: Cell::new(&OPTIONS),
+            onchange: RefCell::new(None::<Box<dyn Fn(&char)>>),
+        });
+
+        let replace = {
+            let picked = picked.clone();
+            let inner = Rc::downgrade(&state);
+
+            move |c: &char| {
+                picked.set(Some(*c));
+
+                // Updating the view from inside the callback replaces it
+                let picked = picked.clone();
+                if let Some(state) = inner.upgrade() {
+                    *state.onchange.borrow_mut() = Some(Box::new(move |c: &char| {
+                        picked.set(Some(c.to_ascii_uppercase()))
+                    }));
+                }
+            }
+        };
+
+        *state.onchange.borrow_mut() = Some(Box::new(replace));
+
+        state.dispatch(1);
+        assert_eq!(picked.get(), Some('b'));
+
+        // The replaced callback is kept
+        state.dispatch(0);
+        assert_eq!(picked.get(), Some('A'));
+
+        // Nothing is selected, or the index is out of bounds
+        state.dispatch(-1);
+        state.dispatch(2);
+        assert_eq!(picked.get(), Some('A'));
+    }
+
+    #[test]
+    fn field_helpers() {
+        let fields = fields(&[
+            ("age", "42"),
+            ("referral", ""),
+            ("tag", "1"),
+            ("tag", "2"),
+            ("newsletter", "on"),
+        ]);
+
+        assert_eq!(required::<u8>(&fields, "age"), Ok(42));
+        assert_eq!(optional::<String>(&fields, "referral"), Ok(None));
+        assert_eq!(optional::<u8>(&fields, "age"), Ok(Some(42)));
+        assert_eq!(checked(&fields, "newsletter"), Ok(true));
+        assert_eq!(checked(&fields, "terms"), Ok(false));
+        assert_eq!(all::<u32>(&fields, "tag"), Ok(vec![1, 2]));
+        assert_eq!(all::<u32>(&fields, "none"), Ok(vec![]));
+    }
+
+    #[test]
+    fn field_errors() {
+        let fields = fields(&[("age", "old")]);
+
+        let missing = required::<String>(&fields, "user").unwrap_err();
+        let invalid = required::<u8>(&fields, "age").unwrap_err();
+
+        assert_eq!(missing.kind, FieldErrorKind::Missing);
+        assert_eq!(
+            invalid.to_string(),
+            "age has an invalid value \"old\": invalid digit found in string"
+        );
+
+        let error = FormError::new(vec![missing, invalid]);
+
+        assert_eq!(error.get("user").unwrap().field, "user");
+        assert!(error.get("email").is_none());
+        assert_eq!(
+            error.to_string(),
+            "user is missing, age has an invalid value \"old\": invalid digit found in string"
+        );
+    }
+}
@@ -0,0 +1,100 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Helpers for rendering HTML entities in text
+//!
+//! Text in **Kobold** is always set through `textContent`, so entities such as `&nbsp;`
+//! are rendered literally rather than interpreted by the browser. The [`nbsp`] and
+//! [`entity`] functions decode a known set of common entities into the characters they
+//! represent, producing regular text views:
+//!
+//! ```
+//! use kobold::prelude::*;
+//! use kobold::html::{entity, nbsp};
+//!
+//! let view = view! {
+//!     <p>"10"{ nbsp() }"km "{ entity("&mdash;") }" give or take"</p>
+//! };
+//! ```
+//!
+//! Entities outside of that set are not decoded. If you need to render arbitrary markup,
+//! including any entity, use the `html` attribute which sets `innerHTML` on the element.
+//! Never use it with untrusted input:
+//!
+//! ```
+//! use kobold::prelude::*;
+//!
+//! let view = view! {
+//!     <p html={"&frac12; cup of sugar"} />
+//! };
+//! ```
+
+/// Non-breaking space, the same as `&nbsp;` in HTML.
+pub const fn nbsp() -> &'static str {
+    "\u{a0}"
+}
+
+/// Decode a named HTML entity such as `&mdash;` into the character it represents.
+///
+/// Only a known set of common entities is decoded, see [`decode`]. Anything else is
+/// returned as is, and will be rendered literally.
+pub fn entity(entity: &str) -> &str {
+    decode(entity).unwrap_or(entity)
+}
+
+/// Decode a named HTML entity such as `&mdash;`, returns `None` if the entity is not
+/// in the known set.
+pub fn decode(entity: &str) -> Option<&'static str> {
+    let decoded = match entity {
+        "&nbsp;" => "\u{a0}",
+        "&amp;" => "&",
+        "&lt;" => "<",
+        "&gt;" => ">",
+        "&quot;" => "\"",
+        "&apos;" => "'",
+        "&shy;" => "\u{ad}",
+        "&ndash;" => "–",
+        "&mdash;" => "—",
+        "&hellip;" => "…",
+        "&bull;" => "•",
+        "&middot;" => "·",
+        "&lsquo;" => "‘",
+        "&rsquo;" => "’",
+        "&ldquo;" => "“",
+        "&rdquo;" => "”",
+        "&laquo;" => "«",
+        "&raquo;" => "»",
+        "&copy;" => "©",
+        "&reg;" => "®",
+        "&trade;" => "™",
+        "&deg;" => "°",
+        "&plusmn;" => "±",
+        "&times;" => "×",
+        "&divide;" => "÷",
+        "&cent;" => "¢",
+        "&pound;" => "£",
+        "&euro;" => "€",
+        "&yen;" => "¥",
+        "&larr;" => "←",
+        "&uarr;" => "↑",
+        "&rarr;" => "→",
+        "&darr;" => "↓",
+        _ => return None,
+    };
+
+    Some(decoded)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decodes_known_entities() {
+        assert_eq!(entity("&nbsp;"), nbsp());
+        assert_eq!(entity("&mdash;"), "\u{2014}");
+        assert_eq!(entity("&lt;b&gt;"), "&lt;b&gt;");
+        assert_eq!(decode("&frac12;"), None);
+    }
+}
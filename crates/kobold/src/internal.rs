@@ -9,7 +9,7 @@ use std::ops::{Deref, DerefMut};
 use std::pin::Pin;
 
 use wasm_bindgen::prelude::*;
-use web_sys::Node;
+use web_sys::{Element, Node};
 
 use crate::View;
 
@@ -218,6 +218,8 @@ extern "C" {
 
     #[wasm_bindgen(js_namespace = ["document", "body"], js_name = appendChild)]
     pub(crate) fn append_body(node: &JsValue);
+    #[wasm_bindgen(js_namespace = document, js_name = createElement)]
+    pub(crate) fn create_element(tag: &str) -> Node;
     #[wasm_bindgen(js_namespace = document, js_name = createTextNode)]
     pub(crate) fn text_node(t: &str) -> Node;
     #[wasm_bindgen(js_namespace = document, js_name = createTextNode)]
@@ -225,6 +227,25 @@ extern "C" {
     #[wasm_bindgen(js_namespace = document, js_name = createTextNode)]
     pub(crate) fn text_node_bool(t: bool) -> Node;
 
+    #[wasm_bindgen(js_namespace = console, js_name = log)]
+    pub(crate) fn console_log(message: &str);
+    #[wasm_bindgen(js_namespace = console, js_name = warn)]
+    pub(crate) fn console_warn(message: &str);
+    #[wasm_bindgen(js_namespace = console, js_name = error)]
+    pub(crate) fn console_error(message: &str);
+
+    #[wasm_bindgen(js_name = setTimeout)]
+    pub(crate) fn set_timeout(callback: &JsValue, ms: i32) -> i32;
+    #[wasm_bindgen(js_name = clearTimeout)]
+    pub(crate) fn clear_timeout(id: i32);
+    #[wasm_bindgen(js_name = queueMicrotask)]
+    pub(crate) fn queue_microtask(callback: &JsValue);
+    #[wasm_bindgen(js_name = setInterval)]
+    pub(crate) fn set_interval(callback: &JsValue, ms: i32) -> i32;
+    #[cfg(feature = "profiling")]
+    #[wasm_bindgen(js_namespace = performance, js_name = now)]
+    pub(crate) fn performance_now() -> f64;
+
     // dom manipulation ----------------
 
     #[wasm_bindgen(method, js_name = "before")]
@@ -251,6 +272,16 @@ extern "C" {
     pub(crate) fn set_attr_num(this: &UnsafeNode, a: &str, v: f64);
     #[wasm_bindgen(method, js_name = "setAttribute")]
     pub(crate) fn set_attr_bool(this: &UnsafeNode, a: &str, v: bool);
+    #[wasm_bindgen(method, js_name = "setAttributeNS")]
+    pub(crate) fn set_attr_ns(this: &UnsafeNode, ns: &str, a: &str, v: &str);
+    #[wasm_bindgen(method, js_name = "setAttributeNS")]
+    pub(crate) fn set_attr_ns_num(this: &UnsafeNode, ns: &str, a: &str, v: f64);
+    #[wasm_bindgen(method, js_name = "setAttributeNS")]
+    pub(crate) fn set_attr_ns_bool(this: &UnsafeNode, ns: &str, a: &str, v: bool);
+    #[wasm_bindgen(method, js_name = "removeAttribute")]
+    pub(crate) fn remove_attr(this: &UnsafeNode, a: &str);
+    #[wasm_bindgen(method, js_name = "removeAttributeNS")]
+    pub(crate) fn remove_attr_ns(this: &UnsafeNode, ns: &str, a: &str);
 
     // provided attribute setters ----------------
 
@@ -266,6 +297,10 @@ extern "C" {
     pub(crate) fn value(this: &UnsafeNode, value: &str);
     #[wasm_bindgen(method, setter, js_name = "value")]
     pub(crate) fn value_num(this: &UnsafeNode, value: f64);
+    #[wasm_bindgen(method, setter, js_name = "defaultValue")]
+    pub(crate) fn default_value(this: &UnsafeNode, value: &str);
+    #[wasm_bindgen(method, setter, js_name = "defaultValue")]
+    pub(crate) fn default_value_num(this: &UnsafeNode, value: f64);
 }
 
 pub(crate) fn obj(node: &Node) -> &UnsafeNode {
@@ -295,11 +330,25 @@ extern "C" {
     pub(crate) fn fragment_unmount(f: &Node);
     #[wasm_bindgen(js_name = "fragmentReplace")]
     pub(crate) fn fragment_replace(f: &Node, new: &JsValue);
+    #[wasm_bindgen(js_name = "fragmentMoveBefore")]
+    pub(crate) fn fragment_move_before(tail: &Node, node: &JsValue);
+    #[wasm_bindgen(js_name = "fragmentChildren")]
+    pub(crate) fn fragment_children(f: &Node) -> Vec<JsValue>;
+    #[wasm_bindgen(js_name = "setShown")]
+    pub(crate) fn set_shown(n: &JsValue, on: bool);
+    #[wasm_bindgen(js_name = "template")]
+    pub(crate) fn template(html: &str) -> Node;
+    #[wasm_bindgen(js_name = "cloneDeep")]
+    pub(crate) fn clone_deep(node: &Node) -> Node;
+    #[wasm_bindgen(js_name = "templateSlots")]
+    pub(crate) fn template_slots(html: &str) -> Vec<JsValue>;
 
     // provided attribute setters ----------------
 
     #[wasm_bindgen(js_name = "setChecked")]
     pub(crate) fn checked(node: &Node, value: bool);
+    #[wasm_bindgen(js_name = "setViewTransitionName")]
+    pub(crate) fn view_transition_name(node: &Node, value: &str);
 
     // ----------------
 
@@ -312,13 +361,56 @@ extern "C" {
     #[wasm_bindgen(js_name = "toggleClass")]
     pub(crate) fn toggle_class(node: &Node, class: &str, value: bool);
 
+    #[wasm_bindgen(js_name = "formEntries")]
+    pub(crate) fn form_entries(form: &JsValue) -> Vec<String>;
+
+    #[wasm_bindgen(js_name = "previousValue")]
+    pub(crate) fn previous_value(input: &JsValue) -> String;
+
+    #[wasm_bindgen(js_name = "injectStyle")]
+    pub(crate) fn inject_style(class: &str, css: &str);
+
+    #[wasm_bindgen(js_name = "attachShadow")]
+    pub(crate) fn attach_shadow(host: &Element, node: &JsValue);
+
+    #[wasm_bindgen(js_name = "startViewTransition")]
+    pub(crate) fn start_view_transition(callback: JsValue);
+
+    // ----------------
+
+    #[wasm_bindgen(js_name = "observeVisible")]
+    pub(crate) fn observe_visible(node: &Node, callback: &JsValue) -> JsValue;
+    #[wasm_bindgen(js_name = "disconnect")]
+    pub(crate) fn disconnect(observer: &JsValue);
+
+    // ----------------
+
+    #[wasm_bindgen(js_name = "scrollIntoView")]
+    pub(crate) fn scroll_into_view(node: &JsValue, smooth: bool);
+    #[wasm_bindgen(js_name = "insertAdjacentHtml")]
+    pub(crate) fn insert_adjacent_html(node: &JsValue, position: &str, html: &str) -> bool;
+    #[wasm_bindgen(js_name = "insertAdjacentElement")]
+    pub(crate) fn insert_adjacent_element(
+        node: &JsValue,
+        position: &str,
+        element: &Element,
+    ) -> bool;
+
     // ----------------
 
     #[wasm_bindgen(js_name = "makeEventHandler")]
     pub(crate) fn make_event_handler(closure: *mut (), vcall: usize) -> JsValue;
+    #[wasm_bindgen(js_name = "makeOptionalHandler")]
+    pub(crate) fn make_optional_handler() -> JsValue;
+    #[wasm_bindgen(js_name = "setOptionalHandler")]
+    pub(crate) fn set_optional_handler(handler: &JsValue, inner: &JsValue);
+
+    #[wasm_bindgen(js_name = "missingBindings")]
+    pub(crate) fn missing_bindings() -> Option<String>;
 
-    #[wasm_bindgen(js_name = "checkEventHandler")]
-    pub(crate) fn check_event_handler();
+    #[cfg(feature = "profiling")]
+    #[wasm_bindgen(js_name = "measureRender")]
+    pub(crate) fn measure_render(name: &str, start: f64);
 }
 
 #[cfg(test)]
@@ -346,6 +438,7 @@ mod test {
     //
     // This is a small mock of what the macro does however.
     #[test]
+    #[allow(clippy::approx_constant)]
     fn build_in_place() {
         fn meaning_builder(p: In<u32>) -> Out<u32> {
             p.put(42)
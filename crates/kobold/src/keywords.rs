@@ -20,6 +20,40 @@ use crate::View;
 /// }
 /// # ;
 /// ```
+///
+/// Views produced by the iterator can borrow from the items, including items borrowed
+/// from the state of a [`stateful`](crate::stateful::stateful) view. Those values are
+/// diffed against the previous render, so mutating the state in place updates the rows:
+///
+/// ```
+/// # use kobold::prelude::*;
+/// let table = stateful(
+///     || vec![("Apples".to_string(), 3), ("Pears".to_string(), 0)],
+///     |stock: &Hook<Vec<(String, u32)>>| {
+///         bind! { stock:
+///             let restock = move |_: MouseEvent<_>| {
+///                 for (_, count) in stock.iter_mut() {
+///                     *count += 1;
+///                 }
+///             };
+///         }
+///
+///         view! {
+///             <table>
+///             {
+///                 for stock
+///                     .iter()
+///                     .filter(|(_, count)| *count > 0)
+///                     .map(|(name, count)| view! {
+///                         <tr><td>{ name }</td><td>{ count }</td></tr>
+///                     })
+///             }
+///             </table>
+///             <button onclick={restock}>"Restock"</button>
+///         }
+///     },
+/// );
+/// ```
 pub const fn r#for<T>(iterator: T) -> List<T>
 where
     T: IntoIterator,
@@ -96,5 +130,25 @@ pub const fn r#static<T>(value: T) -> Static<T> {
     Static(value)
 }
 
+/// `{ ?... }`: render an [`Option`] if it's `Some`, or nothing if it's `None`.
+///
+/// ```
+/// # use kobold::prelude::*;
+/// #[component]
+/// fn profile<'a>(name: &'a str, bio: Option<&'a str>) -> impl View + 'a {
+///     view! {
+///         <h2>{ name }</h2>
+///         <p>{ ?bio }</p>
+///     }
+/// }
+/// # fn main() {}
+/// ```
+///
+/// The `?` applies to the entire expression, which has to be an `Option`. A trailing `?`
+/// is the regular `?` operator, such as `{ entry? }` in a closure that returns a `Result`.
+pub const fn optional<V: View>(view: Option<V>) -> Option<V> {
+    view
+}
+
 /// `{ do ... }` is an alias for [`{ event!(...) }`](../macro.event.html)
 pub use crate::event as r#do;
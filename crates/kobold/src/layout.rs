@@ -0,0 +1,171 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Utilities for text-fit layouts
+//!
+//! [`text_width`] measures how wide a piece of text renders in a given font, without
+//! inserting anything into the document, so measuring never forces a reflow. Results
+//! are cached, making it cheap to call on every render:
+//!
+//! ```no_run
+//! use kobold::prelude::*;
+//! use kobold::layout::text_width;
+//!
+//! #[component]
+//! fn label<'a>(text: &'a str, max_width: f64) -> impl View + 'a {
+//!     // Shrink the label until it fits, down to 10px
+//!     let size = (10..=16)
+//!         .rev()
+//!         .find(|px| text_width(text, &format!("{px}px sans-serif")) <= max_width)
+//!         .unwrap_or(10);
+//!
+//!     view! {
+//!         <span style={format!("font: {size}px sans-serif")}>{ text }</span>
+//!     }
+//! }
+//! # fn main() {}
+//! ```
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use web_sys::CanvasRenderingContext2d;
+
+/// Maximum number of measurements cached per font, the cache for a font is cleared
+/// once it grows past this.
+const MAX_CACHED: usize = 4096;
+
+/// Width in pixels of `text` rendered in `font`, using the same syntax as the CSS
+/// [`font`](https://developer.mozilla.org/en-US/docs/Web/CSS/font) shorthand,
+/// such as `"bold 16px sans-serif"`.
+///
+/// Text is measured on a hidden canvas with
+/// [`measureText`](https://developer.mozilla.org/en-US/docs/Web/API/CanvasRenderingContext2D/measureText)
+/// and memoized by `(text, font)`. Fonts that haven't loaded yet are measured using
+/// their fallback, call [`clear_text_width_cache`] once web fonts are ready.
+pub fn text_width(text: &str, font: &str) -> f64 {
+    MEASURE.with(|measure| measure.borrow_mut().width(text, font))
+}
+
+/// Forget all cached [`text_width`] measurements.
+pub fn clear_text_width_cache() {
+    MEASURE.with(|measure| measure.borrow_mut().cache.clear());
+}
+
+thread_local! {
+    static MEASURE: RefCell<Measure> = RefCell::new(Measure::default());
+}
+
+/// Canvas context, created on first measurement, with the font it's set to.
+#[derive(Default)]
+struct Measure {
+    ctx: Option<CanvasRenderingContext2d>,
+    font: String,
+    cache: Cache,
+}
+
+impl Measure {
+    fn width(&mut self, text: &str, font: &str) -> f64 {
+        let Measure {
+            ctx,
+            font: current,
+            cache,
+        } = self;
+
+        cache.get_or_measure(text, font, || {
+            let ctx = ctx.get_or_insert_with(context);
+
+            if current != font {
+                ctx.set_font(font);
+                current.clear();
+                current.push_str(font);
+            }
+
+            ctx.measure_text(text).unwrap_throw().width()
+        })
+    }
+}
+
+fn context() -> CanvasRenderingContext2d {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.create_element("canvas").ok())
+        .and_then(|canvas| {
+            canvas
+                .unchecked_into::<web_sys::HtmlCanvasElement>()
+                .get_context("2d")
+                .ok()
+                .flatten()
+        })
+        .expect_throw("Couldn't create a canvas to measure text")
+        .unchecked_into()
+}
+
+/// Measurements keyed by font, then text, so lookups don't allocate.
+#[derive(Default)]
+struct Cache {
+    fonts: HashMap<String, HashMap<String, f64>>,
+}
+
+impl Cache {
+    fn get_or_measure(&mut self, text: &str, font: &str, measure: impl FnOnce() -> f64) -> f64 {
+        if let Some(width) = self.fonts.get(font).and_then(|texts| texts.get(text)) {
+            return *width;
+        }
+
+        let width = measure();
+        let texts = self.fonts.entry(font.into()).or_default();
+
+        if texts.len() >= MAX_CACHED {
+            texts.clear();
+        }
+
+        texts.insert(text.into(), width);
+
+        width
+    }
+
+    fn clear(&mut self) {
+        self.fonts.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cache_measures_once() {
+        let mut cache = Cache::default();
+        let mut measured = 0;
+        let mut width = |cache: &mut Cache, text: &str, font: &str| {
+            cache.get_or_measure(text, font, || {
+                measured += 1;
+                text.len() as f64 * 8.0
+            })
+        };
+
+        assert_eq!(width(&mut cache, "kobold", "16px serif"), 48.0);
+        assert_eq!(width(&mut cache, "kobold", "16px serif"), 48.0);
+        assert_eq!(width(&mut cache, "kobold", "bold 16px serif"), 48.0);
+        assert_eq!(width(&mut cache, "elf", "16px serif"), 24.0);
+
+        cache.clear();
+
+        assert_eq!(width(&mut cache, "kobold", "16px serif"), 48.0);
+        assert_eq!(measured, 4);
+    }
+
+    #[test]
+    fn cache_is_bounded() {
+        let mut cache = Cache::default();
+
+        for n in 0..MAX_CACHED + 10 {
+            cache.get_or_measure(&n.to_string(), "16px serif", || 1.0);
+        }
+
+        assert!(cache.fonts["16px serif"].len() <= MAX_CACHED);
+    }
+}
@@ -0,0 +1,175 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Utilities for deferring the building of views until they are needed
+//!
+//! ```no_run
+//! use kobold::prelude::*;
+//! use kobold::lazy::lazy;
+//!
+//! #[component]
+//! fn heavy() -> impl View {
+//!     view! { <p>"Lots of content"</p> }
+//! }
+//!
+//! #[component]
+//! fn page() -> impl View {
+//!     view! {
+//!         <header>"Above the fold"</header>
+//!         // `heavy` is only built once it's scrolled into view
+//!         { lazy(view! { <!heavy> }) }
+//!     }
+//! }
+//! # fn main() {}
+//! ```
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsValue;
+use web_sys::Node;
+
+use crate::dom::{Anchor, Fragment, FragmentBuilder};
+use crate::internal::{self, In, Out};
+use crate::{Mountable, View};
+
+/// Defer building the `view` until its placeholder, an empty `<div>`, first becomes
+/// visible in the viewport, as reported by an
+/// [`IntersectionObserver`](https://developer.mozilla.org/en-US/docs/Web/API/IntersectionObserver).
+///
+/// Since the view is kept around until it's built, it needs to be `'static`.
+pub const fn lazy<V>(view: V) -> Lazy<V>
+where
+    V: View + 'static,
+{
+    Lazy {
+        view,
+        reveal: false,
+    }
+}
+
+/// View created by the [`lazy`] function.
+pub struct Lazy<V> {
+    view: V,
+    reveal: bool,
+}
+
+impl<V> Lazy<V> {
+    /// Build the view right away if `reveal` is `true`, without waiting for it to
+    /// become visible. This can be used to trigger the build manually from state.
+    pub const fn reveal_if(mut self, reveal: bool) -> Self {
+        self.reveal = reveal;
+        self
+    }
+}
+
+enum Deferred<V: View> {
+    Pending { view: V, placeholder: Node },
+    Built(Box<V::Product>),
+    Empty,
+}
+
+impl<V: View> Deferred<V> {
+    fn reveal(&mut self) {
+        if let Deferred::Pending { .. } = self {
+            let Deferred::Pending { view, placeholder } = std::mem::replace(self, Deferred::Empty)
+            else {
+                return;
+            };
+
+            let built = In::boxed(|p| view.build(p));
+
+            internal::obj(&placeholder).replace(built.js());
+
+            *self = Deferred::Built(built);
+        }
+    }
+}
+
+pub struct LazyProduct<V: View> {
+    deferred: Rc<RefCell<Deferred<V>>>,
+    fragment: FragmentBuilder,
+    observer: JsValue,
+    _onvisible: Closure<dyn FnMut()>,
+}
+
+impl<V> View for Lazy<V>
+where
+    V: View + 'static,
+{
+    type Product = LazyProduct<V>;
+
+    fn build(self, p: In<Self::Product>) -> Out<Self::Product> {
+        let placeholder = internal::create_element("div");
+        let fragment = FragmentBuilder::new();
+
+        fragment.append(&placeholder);
+
+        let deferred = Rc::new(RefCell::new(Deferred::Pending {
+            view: self.view,
+            placeholder: placeholder.clone(),
+        }));
+
+        let onvisible = {
+            let deferred = Rc::downgrade(&deferred);
+
+            Closure::<dyn FnMut()>::new(move || {
+                if let Some(deferred) = deferred.upgrade() {
+                    deferred.borrow_mut().reveal();
+                }
+            })
+        };
+
+        let observer = internal::observe_visible(&placeholder, onvisible.as_ref());
+
+        if self.reveal {
+            internal::disconnect(&observer);
+            deferred.borrow_mut().reveal();
+        }
+
+        p.put(LazyProduct {
+            deferred,
+            fragment,
+            observer,
+            _onvisible: onvisible,
+        })
+    }
+
+    fn update(self, p: &mut Self::Product) {
+        let mut deferred = p.deferred.borrow_mut();
+
+        match &mut *deferred {
+            Deferred::Pending { view, .. } => {
+                *view = self.view;
+
+                if self.reveal {
+                    internal::disconnect(&p.observer);
+                    deferred.reveal();
+                }
+            }
+            Deferred::Built(product) => self.view.update(product),
+            Deferred::Empty => (),
+        }
+    }
+}
+
+impl<V: View> Drop for LazyProduct<V> {
+    fn drop(&mut self) {
+        // The observer must not call the closure after it's dropped
+        internal::disconnect(&self.observer);
+    }
+}
+
+impl<V> Anchor for LazyProduct<V>
+where
+    V: View + 'static,
+{
+    type Js = Node;
+    type Target = Fragment;
+
+    fn anchor(&self) -> &Fragment {
+        &self.fragment
+    }
+}
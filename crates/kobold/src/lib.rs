@@ -166,6 +166,76 @@
 //!
 //! For more details visit the [`branching` module documentation](branching).
 //!
+//! An `Option` of a view renders nothing when it's `None`. Child expressions starting with `?`, such as
+//! `{ ?entry }`, are a shorthand for that which only accepts an `Option`, see [`optional`](keywords::optional).
+//!
+//! Attribute values on the other hand are plain Rust expressions, so `if`, `match` or method calls
+//! can be used inline as long as all arms evaluate to the same type. Like any other attribute values
+//! the resulting strings are diffed on updates:
+//!
+//! ```
+//! # use kobold::prelude::*;
+//! # enum Filter { All, Active }
+//! #[component]
+//! fn filter_link(selected: bool, filter: &Filter) -> impl View + '_ {
+//!     view! {
+//!         <a
+//!             class={if selected { "selected" } else { "" }}
+//!             href={match filter { Filter::All => "#/", Filter::Active => "#/active" }}
+//!         >
+//!             "Filter"
+//!         </a>
+//!     }
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! Classes can be toggled by a condition with the `.{condition => "class"}` shorthand, which is equivalent to
+//! using [`class!`](class)`("class" if condition)` and leaves any other classes of the element untouched:
+//!
+//! ```
+//! # use kobold::prelude::*;
+//! #[component]
+//! fn todo(done: bool) -> impl View {
+//!     view! {
+//!         <li.todo.{done => "completed"}>"Write docs"</li>
+//!     }
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! Several classes can be set at once with an array in `.{[...]}`, which is equivalent to
+//! [`classes`](attribute::classes)`([...])`. Empty strings are skipped, and on updates only
+//! the classes that changed are added or removed:
+//!
+//! ```
+//! # use kobold::prelude::*;
+//! #[component]
+//! fn todo(done: bool, priority: &'static str) -> impl View {
+//!     let completed = if done { "completed" } else { "" };
+//!
+//!     view! {
+//!         <li.todo.{[completed, priority]}>"Write docs"</li>
+//!     }
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! Similarly `#name` sets a static `id`, and `#{expression}` sets it from any value that can
+//! be used as an attribute, which is diffed on updates like any other attribute. Since Rust
+//! reserves `name#` syntax, there has to be a space before the `#`:
+//!
+//! ```
+//! # use kobold::prelude::*;
+//! #[component]
+//! fn todo(id: u32) -> impl View {
+//!     view! {
+//!         <li #{format!("todo-{id}")}.todo>"Write docs"</li>
+//!     }
+//! }
+//! # fn main() {}
+//! ```
+//!
 //! ### Lists and Iterators
 //!
 //! To render an iterator use the [`for`](keywords::for) keyword:
@@ -191,6 +261,37 @@
 //!
 //! For more information about keywords visit the [`keywords` module documentation](keywords).
 //!
+//! ### Fragments
+//!
+//! A view with multiple root nodes is rendered as a fragment without a wrapper element. To make
+//! that grouping explicit, such as when each item of a list renders multiple siblings, wrap the
+//! nodes in `<>` and `</>`:
+//!
+//! ```
+//! use kobold::prelude::*;
+//!
+//! const TERMS: &[(&str, &str)] = &[("Kobold", "A small goblin"), ("Rust", "A language")];
+//!
+//! #[component]
+//! fn glossary() -> impl View {
+//!     view! {
+//!         <dl>
+//!         {
+//!             for TERMS.iter().map(|&(term, definition)| view! {
+//!                 <>
+//!                     <dt>{ static term }</dt>
+//!                     <dd>{ static definition }</dd>
+//!                 </>
+//!             })
+//!         }
+//!         </dl>
+//!     }
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! Fragments can be nested, and unlike elements they always need the closing `</>`.
+//!
 //! ### Borrowed Values
 //!
 //! [`View`] types are truly transient and only need to live for the duration of the initial render,
@@ -255,6 +356,77 @@
 //! }
 //! ```
 //!
+//! ### Controlled and Uncontrolled Inputs
+//!
+//! Setting `value={...}` makes the input _controlled_: the value is diffed against the
+//! last render and written back to the element whenever it changes, so the state stays
+//! the source of truth. Use `default-value={...}` instead for an _uncontrolled_ `<input>`
+//! or `<textarea>`, its `defaultValue` is only set when the element is built and is never
+//! diffed again, leaving the DOM in charge of whatever the user types or your event
+//! handlers write into it. Resetting the form restores the default value:
+//!
+//! ```
+//! use kobold::prelude::*;
+//!
+//! #[component]
+//! fn rename<'a>(name: &'a str) -> impl View + 'a {
+//!     let onchange = |e: Event<HtmlInputElement>| {
+//!         // Clearing the input won't be undone by the next render
+//!         e.current_target().set_value("");
+//!     };
+//!
+//!     view! {
+//!         <input type="text" default-value={name} {onchange}>
+//!     }
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! ### Keyboard Accessible Elements
+//!
+//! Elements that aren't natively interactive, such as `<div>`, can't be focused or clicked
+//! with a keyboard. Setting `interactive=true` on them adds `role="button"` and `tabindex="0"`
+//! (unless you set those attributes yourself), and makes pressing Enter or Space while the
+//! element is focused trigger its `onclick` handler. Holding Space down clicks only once, and
+//! keys pressed on focusable children such as inputs are left alone. Since this is generated
+//! at compile time, `interactive` only accepts the literals `true` or `false`, not expressions:
+//!
+//! ```
+//! use kobold::prelude::*;
+//!
+//! let view = stateful(0_u32, |count| {
+//!     bind! { count:
+//!         let onclick = move |_| *count += 1;
+//!     }
+//!
+//!     view! {
+//!         <div.card interactive=true {onclick}>"Clicked "{ count.get() }" times"</div>
+//!     }
+//! });
+//! ```
+//!
+//! ### Template Elements
+//!
+//! Children of a `<template>` element are placed in its
+//! [`content`](https://developer.mozilla.org/en-US/docs/Web/API/HTMLTemplateElement/content)
+//! fragment, leaving them inert for scripts or web components to clone later. Since Kobold never
+//! activates them, they can only contain static markup: expressions, components and dynamic
+//! attributes inside a `<template>` are a compile error.
+//!
+//! ```
+//! use kobold::prelude::*;
+//!
+//! let view = view! {
+//!     <template #row-template>
+//!         <tr><td.name>"Name"</td><td.email>"Email"</td></tr>
+//!     </template>
+//! };
+//! ```
+//!
+//! This is unrelated to [`dom::Template`], which parses static HTML into a detached `<template>`
+//! once and clones it into your views on every render. An inline `<template>` is rendered as is and
+//! stays in the DOM, its contents are never cloned by Kobold.
+//!
 //! ## More Examples
 //!
 //! To run **Kobold** you'll need to install [`trunk`](https://trunkrs.dev/):
@@ -292,6 +464,42 @@
 /// # fn main() {}
 /// ```
 ///
+/// ## Generic components
+///
+/// Components can be generic over lifetimes and types, bounds can be declared inline or in a
+/// `where` clause. Generic parameters are inferred from the values passed to the component:
+///
+/// ```
+/// # use kobold::prelude::*;
+/// #[component]
+/// fn table<'a, T, F, V>(rows: &'a [T], render: F) -> impl View + 'a
+/// where
+///     F: Fn(&T) -> V + Copy + 'a,
+///     V: View,
+/// {
+///     view! {
+///         <table>
+///         {
+///             for rows.iter().map(move |row| view! { <tr><td>{ render(row) }</td></tr> })
+///         }
+///         </table>
+///     }
+/// }
+///
+/// #[component]
+/// fn labeled<T: View>(label: &'static str, value: T) -> impl View {
+///     view! { <p>{ static label }": "{ value }</p> }
+/// }
+///
+/// # fn main() {
+/// # let _ =
+/// view! {
+///     <!table rows={&[1, 2, 3]} render={|n: &i32| *n * 10}>
+///     <!labeled label="Total" value={60}>
+/// }
+/// # ; }
+/// ```
+///
 /// ## Flags
 ///
 /// The `#[component]` attribute accepts a few optional flags using syntax: `#[component(<flag>)]`.
@@ -394,11 +602,149 @@
 ///
 /// * `#[component(children)]`: children will be captured by the `children` argument on the function.
 /// * `#[component(children: my_name)]`: children will be captured by the `my_name` argument on the function.
+///
+/// ### Skip unchanged updates: `#[component(memo)]`
+///
+/// Memoizes the parameters of the component, and skips calling the function on update as long as they
+/// remain the same. This is equivalent to wrapping the rendered view in a [`fence`](diff::fence)
+/// guarded by all of the parameters, so each of them has to implement [`Diff`](diff::Diff).
+/// That covers strings, numbers, `bool`s, and [`ref`](keywords::ref) references, but not
+/// [`Hook`](stateful::Hook)s or children:
+///
+/// ```
+/// use kobold::prelude::*;
+///
+/// #[component(memo)]
+/// fn price(label: &str, cents: u64) -> impl View + '_ {
+///     // Expensive formatting only happens when `label` or `cents` changes
+///     let price = format!("{}.{:02}", cents / 100, cents % 100);
+///
+///     view! {
+///         <p>{ label }": $"{ price }</p>
+///     }
+/// }
+/// # fn main() {}
+/// ```
+///
+/// ### Rename the render function: `#[component(render = view)]`
+///
+/// A component `<!name>` is rendered by calling `name::render(name::props()...)`. The
+/// `render` argument changes the name of the generated function, accepting both
+/// `render = view` and `render = "view"`. Pick the function at the call site with
+/// `<!name.view>`:
+///
+/// ```
+/// use kobold::prelude::*;
+///
+/// #[component(render = view)]
+/// fn chart(title: &str) -> impl View + '_ {
+///     view! {
+///         <figure><figcaption>{ title }</figcaption></figure>
+///     }
+/// }
+///
+/// # fn main() { let _ =
+/// view! {
+///     <!chart.view title="Sales">
+/// }
+/// # ; }
+/// ```
+///
+/// The same call syntax works for any path with a `props` function, such as a type
+/// implementing its components by hand, whose `render` method already means something
+/// else.
+///
+/// Arguments listed after the name, as in `render = view(theme)`, aren't props. They are
+/// passed to the render function after the props instead, which is also how hand written
+/// render functions can take extra arguments. Pass them at the call site the same way:
+///
+/// ```
+/// use kobold::prelude::*;
+///
+/// pub struct Theme {
+///     accent: &'static str,
+/// }
+///
+/// #[component(render = view(theme))]
+/// fn chart<'a>(title: &'a str, theme: &'a Theme) -> impl View + 'a {
+///     view! {
+///         <figure style={theme.accent}><figcaption>{ title }</figcaption></figure>
+///     }
+/// }
+///
+/// # fn main() {
+/// let theme = Theme { accent: "color: teal" };
+/// # let _ =
+/// view! {
+///     <!chart.view(&theme) title="Sales">
+/// }
+/// # ; }
+/// ```
 pub use kobold_macros::component;
 
 /// Macro for creating transient [`View`] types. See the [main documentation](crate) for details.
 pub use kobold_macros::{class, view};
 
+/// Macro for component-scoped CSS.
+///
+/// Takes a string literal with CSS and produces a [`ScopedStyle`](attribute::ScopedStyle)
+/// that can be used as a class on the root element of a component. All selectors
+/// are rewritten to be descendants of a generated `kobold-<hash>` class, with `&`
+/// referring to the root element itself. The stylesheet is injected into `<head>`
+/// once, when the first element using it is built.
+///
+/// ```no_run
+/// use kobold::prelude::*;
+///
+/// #[component]
+/// fn card(title: &str) -> impl View + '_ {
+///     let style = css!("
+///         & { padding: 1em; border: 1px solid #ccc; }
+///         &:hover { border-color: #000; }
+///         h2 { margin: 0; }
+///     ");
+///
+///     view! {
+///         <div class={style}>
+///             <h2>{ title }</h2>
+///         </div>
+///     }
+/// }
+///
+/// fn main() {
+///     kobold::start(view! {
+///         <!card title="Scoped">
+///     });
+/// }
+/// ```
+pub use kobold_macros::css;
+
+/// Derive [`View`] for an enum without fields, rendering the name of each variant as
+/// text. Labels can be changed with a `#[view(label = "...")]` attribute on the variant:
+///
+/// ```
+/// use kobold::prelude::*;
+///
+/// #[derive(View)]
+/// enum Status {
+///     Active,
+///     #[view(label = "On hold")]
+///     Paused,
+///     Closed,
+/// }
+///
+/// #[component]
+/// fn badge(status: Status) -> impl View {
+///     view! { <span.badge>{ status }</span> }
+/// }
+/// # fn main() {}
+/// ```
+///
+/// Labels are static strings, so the text node is only updated when the variant
+/// changes. The derive also implements `View` for references to the enum.
+pub use kobold_macros::View;
+
+use wasm_bindgen::closure::Closure;
 use wasm_bindgen::JsCast;
 
 #[cfg(all(
@@ -411,19 +757,30 @@ static A: rlsf::SmallGlobalTlsf = rlsf::SmallGlobalTlsf::new();
 
 pub mod attribute;
 pub mod branching;
+pub mod console;
 pub mod diff;
 pub mod dom;
 pub mod event;
+pub mod form;
+pub mod html;
 pub mod internal;
 pub mod keywords;
+pub mod layout;
+pub mod lazy;
 pub mod list;
 pub mod maybe;
+pub mod net;
+pub mod profiling;
+pub mod url;
 
 mod value;
 
 #[cfg(feature = "stateful")]
 pub mod stateful;
 
+#[cfg(feature = "markdown")]
+pub mod markdown;
+
 use internal::{In, Out};
 
 /// The prelude module with most commonly used types.
@@ -433,10 +790,14 @@ use internal::{In, Out};
 /// use kobold::prelude::*;
 /// ```
 pub mod prelude {
-    pub use crate::event::{Event, KeyboardEvent, MouseEvent};
-    pub use crate::{bind, class, event};
+    pub use crate::event::{AnimationEvent, ClipboardEvent, DragEvent, Event, KeyboardEvent};
+    pub use crate::event::{MouseEvent, PointerEvent, TouchEvent, TransitionEvent, WheelEvent};
+    pub use crate::{bind, class, css, event};
     pub use crate::{component, view, View};
 
+    // Element types commonly used as event targets
+    pub use web_sys::{HtmlFormElement, HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
+
     #[cfg(feature = "stateful")]
     pub use crate::stateful::{stateful, Hook, IntoState, Signal, Then};
 }
@@ -539,12 +900,50 @@ where
     }
 }
 
+/// Closures without arguments are deferred views, only called once the view is built
+/// or updated. Inside a branch that's rarely taken this avoids constructing the view
+/// in the first place:
+///
+/// ```
+/// # use kobold::prelude::*;
+/// # fn expensive_report() -> impl View { "" }
+/// #[component]
+/// fn dashboard(show_report: bool) -> impl View {
+///     view! {
+///         <h1>"Dashboard"</h1>
+///         { show_report.then_some(|| view! { <div.report>{ expensive_report() }</div> }) }
+///     }
+/// }
+/// # fn main() {}
+/// ```
+///
+/// The closure is called on every update of the view, same as the view it returns would
+/// be created. Combine it with [`fence`](crate::diff::fence) to skip updates when
+/// nothing has changed.
+impl<F, V> View for F
+where
+    F: FnOnce() -> V,
+    V: View,
+{
+    type Product = V::Product;
+
+    fn build(self, p: In<Self::Product>) -> Out<Self::Product> {
+        self().build(p)
+    }
+
+    fn update(self, p: &mut Self::Product) {
+        self().update(p)
+    }
+}
+
 /// Start the Kobold app by mounting given [`View`] in the document `body`.
+///
+/// The app stays mounted for the lifetime of the page. To remove it later on, such
+/// as for a micro-app embedded in a modal, use [`mount`] instead and keep the returned
+/// [`Root`](dom::Root) around until the app is torn down.
 pub fn start(view: impl View) {
     init_panic_hook();
-
-    #[cfg(debug_assertions)]
-    internal::check_event_handler();
+    init_check();
 
     use std::mem::MaybeUninit;
     use std::pin::pin;
@@ -555,6 +954,135 @@ pub fn start(view: impl View) {
     internal::append_body(product.js());
 }
 
+/// Mount the given [`View`] in the document `body`, same as [`start`], returning a
+/// [`Root`](dom::Root) handle that can update or replace the view later on.
+///
+/// The handle owns the whole app: calling [`unmount`](dom::Root::unmount) on it, or
+/// dropping it, removes the view from the DOM and drops its product.
+///
+/// ```no_run
+/// use kobold::prelude::*;
+/// use kobold::dom::{Mountable, Root};
+///
+/// #[component]
+/// fn dialog() -> impl View {
+///     view! { <div.modal>"Micro-app in a modal"</div> }
+/// }
+///
+/// fn open() -> Root<impl Mountable> {
+///     kobold::mount(view! { <!dialog> })
+/// }
+///
+/// fn close(app: Root<impl Mountable>) {
+///     app.unmount();
+/// }
+/// # fn main() {}
+/// ```
+pub fn mount<V: View>(view: V) -> dom::Root<V::Product> {
+    init_panic_hook();
+    init_check();
+
+    let product = In::boxed(move |p| view.build(p));
+
+    internal::append_body(product.js());
+
+    dom::Root::new(product)
+}
+
+/// Same as [`start`], but attaches an open shadow root to the `host` element and mounts
+/// the [`View`] inside it, so that the styles of the host page don't apply to it.
+///
+/// Stylesheets of [`css!`](css) scoped components are injected into every shadow root
+/// created this way, as well as the document `head`.
+///
+/// ```no_run
+/// use kobold::prelude::*;
+///
+/// fn main() {
+///     let host = web_sys::window()
+///         .and_then(|window| window.document())
+///         .and_then(|document| document.get_element_by_id("widget"))
+///         .expect("missing #widget element");
+///
+///     kobold::start_shadow(host, view! {
+///         <p>"Rendered in a shadow root"</p>
+///     });
+/// }
+/// ```
+///
+/// # Panics
+///
+/// If a shadow root can't be attached to the `host`, which is the case if it already
+/// has one, or if it's an element that doesn't support shadow roots, such as `<input>`.
+pub fn start_shadow(host: web_sys::Element, view: impl View) {
+    init_panic_hook();
+    init_check();
+
+    use std::mem::MaybeUninit;
+    use std::pin::pin;
+
+    let product = pin!(MaybeUninit::uninit());
+    let product = In::pinned(product, move |p| view.build(p));
+
+    internal::attach_shadow(&host, product.js());
+}
+
+/// Run the `update` closure, typically mutating some state, inside a
+/// [view transition](https://developer.mozilla.org/en-US/docs/Web/API/View_Transitions_API)
+/// so that the browser animates between the old and the new render. Elements can be
+/// paired between the two states with the `view-transition-name` attribute.
+///
+/// In browsers that don't support `document.startViewTransition` the closure is
+/// simply called right away without any animation.
+///
+/// ```
+/// # use kobold::prelude::*;
+/// fn card(expanded: bool) -> impl View {
+///     view! {
+///         <div.card view-transition-name="card">
+///             { if expanded { "Lots of content" } else { "Teaser" } }
+///         </div>
+///     }
+/// }
+///
+/// fn toggle(expanded: Signal<bool>) {
+///     kobold::start_view_transition(move || expanded.update(|e| *e = !*e));
+/// }
+/// ```
+pub fn start_view_transition<F>(update: F)
+where
+    F: FnOnce() + 'static,
+{
+    internal::start_view_transition(Closure::once_into_js(update));
+}
+
+/// Check that the JavaScript glue **Kobold** relies on has been loaded, and panic with
+/// a description of what's missing if it hasn't. Without it event handlers would fail
+/// with a cryptic `undefined is not a function` error the first time they fire.
+///
+/// This is called by [`start`] and [`start_shadow`], and only checks once.
+pub fn init_check() {
+    use std::cell::Cell;
+
+    thread_local! {
+        static CHECKED: Cell<bool> = const { Cell::new(false) };
+    }
+
+    if CHECKED.with(|checked| checked.replace(true)) {
+        return;
+    }
+
+    if let Some(missing) = internal::missing_bindings() {
+        panic!(
+            "Missing `{missing}` in global scope. \
+            As of Kobold v0.10 and Trunk v0.17.16 the wasm-bindgen bindings are exported \
+            as `wasmBindings` automatically, if you are using Trunk please remove the custom \
+            `pattern_script` from your `Trunk.toml` file. Other setups have to make the \
+            bindings available as `window.wasmBindings` before starting the app."
+        );
+    }
+}
+
 fn init_panic_hook() {
     // Only enable console hook on debug builds
     #[cfg(debug_assertions)]
@@ -633,3 +1161,74 @@ macro_rules! event {
         $state.bind(move |$state, _| $state $($body)*)
     };
 }
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+
+    use wasm_bindgen::JsValue;
+
+    use super::*;
+    use crate::branching::show_if;
+
+    // Mock view that can be built outside of wasm
+    struct Num(u32);
+
+    struct NumProduct {
+        js: JsValue,
+        num: u32,
+    }
+
+    impl Mountable for NumProduct {
+        type Js = JsValue;
+
+        fn js(&self) -> &JsValue {
+            &self.js
+        }
+
+        fn unmount(&self) {}
+
+        fn replace_with(&self, _: &JsValue) {}
+    }
+
+    impl View for Num {
+        type Product = NumProduct;
+
+        fn build(self, p: In<NumProduct>) -> Out<NumProduct> {
+            p.put(NumProduct {
+                js: JsValue::UNDEFINED,
+                num: self.0,
+            })
+        }
+
+        fn update(self, p: &mut NumProduct) {
+            p.num = self.0;
+        }
+    }
+
+    #[test]
+    fn closure_views_are_deferred() {
+        let calls = Cell::new(0);
+        let render = |num| {
+            let calls = &calls;
+
+            move || {
+                calls.set(calls.get() + 1);
+                Num(num)
+            }
+        };
+
+        let _hidden = show_if(false, render(1));
+
+        assert_eq!(calls.get(), 0);
+
+        let mut product = In::boxed(|p| show_if(true, render(2)).build(p));
+
+        assert_eq!(calls.get(), 1);
+
+        show_if(true, render(3)).update(&mut product);
+
+        assert_eq!(calls.get(), 2);
+        assert!(matches!(&*product, branching::Branch2::A(prod) if prod.num == 3));
+    }
+}
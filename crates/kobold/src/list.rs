@@ -10,11 +10,18 @@ use crate::internal::{In, Out};
 use crate::View;
 
 pub mod bounded;
+pub mod keyed;
+pub mod results;
 pub mod unbounded;
+pub mod virtualized;
 
 use bounded::BoundedProduct;
 use unbounded::ListProduct;
 
+pub use keyed::{keyed, keyed_by, Keyed};
+pub use results::results;
+pub use virtualized::VirtualList;
+
 /// Zero-sized marker making the [`List`] unbounded: it can grow to arbitrary
 /// size but will require memory allocation.
 pub struct Unbounded;
@@ -70,6 +77,16 @@ where
     }
 }
 
+/// Owned `Vec` of views renders each element in order, same as the [`for`](crate::keywords::for)
+/// keyword would.
+///
+/// Diffing is positional: on update the element at index `n` is diffed against the product
+/// previously built at index `n`, new elements past the old length are built and mounted, and
+/// excess products are unmounted (but kept around for reuse should the list grow again).
+///
+/// Since items are not keyed, reordering a list will update every element whose position
+/// has changed. For lists with frequent reorders use a [`keyed`] list instead, which moves
+/// the products of reordered items along with them.
 impl<V: View> View for Vec<V> {
     type Product = ListProduct<V::Product>;
 
@@ -82,6 +99,7 @@ impl<V: View> View for Vec<V> {
     }
 }
 
+/// Borrowed counterpart to the `Vec<V>` implementation, with the same positional diffing.
 impl<'a, V> View for &'a [V]
 where
     &'a V: View,
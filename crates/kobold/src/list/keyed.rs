@@ -0,0 +1,430 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Keyed lists that keep track of items as they are reordered
+//!
+//! Lists rendered with the [`for`](crate::keywords::for) keyword or from a `Vec` are
+//! diffed positionally, which is the cheapest option for lists that only grow or shrink
+//! at the end. If items can be reordered, or inserted and removed in the middle of the
+//! list, a keyed list will instead move the existing DOM nodes of the items and only
+//! update their content:
+//!
+//! ```
+//! use kobold::prelude::*;
+//! use kobold::list::{keyed, Keyed};
+//!
+//! struct Todo {
+//!     id: u32,
+//!     text: String,
+//! }
+//!
+//! impl Keyed for Todo {
+//!     type Key = u32;
+//!
+//!     fn key(&self) -> u32 {
+//!         self.id
+//!     }
+//! }
+//!
+//! #[component]
+//! fn todos(todos: &[Todo]) -> impl View + '_ {
+//!     view! {
+//!         <ul>
+//!         {
+//!             keyed(todos, |todo| view! { <li>{ ref todo.text }</li> })
+//!         }
+//!     }
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! The DOM always ends up in the order of the iterator, keys are only used to find
+//! the product of each item from the previous render. Moving an item moves its nodes,
+//! while filtering items out of the list only unmounts them without moving the rest.
+//! In a positional list the same filter would instead update every item after the
+//! first removed one to render its new neighbour.
+//!
+//! Items can render more than one root node, all of them are moved together:
+//!
+//! ```
+//! # use kobold::prelude::*;
+//! # use kobold::list::keyed;
+//! #[component]
+//! fn glossary(terms: &[(u32, String, String)]) -> impl View + '_ {
+//!     let terms = terms.iter().map(|(id, term, definition)| (*id, (term, definition)));
+//!
+//!     view! {
+//!         <dl>
+//!         {
+//!             keyed(terms, |(_, (term, definition))| view! {
+//!                 <dt>{ ref term }</dt>
+//!                 <dd>{ ref definition }</dd>
+//!             })
+//!         }
+//!         </dl>
+//!     }
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! Pairs are keyed by their first element, so ordered collections of `(Id, T)` can be
+//! rendered as they are, and maps iterating over `(&Id, &T)` only need to copy the ids:
+//!
+//! ```
+//! # use kobold::prelude::*;
+//! # use kobold::list::keyed;
+//! # type Scores = std::collections::BTreeMap<u32, u32>;
+//! #[component]
+//! fn names(names: &[(u32, String)]) -> impl View + '_ {
+//!     keyed(names, |(_, name)| view! { <li>{ ref name }</li> })
+//! }
+//!
+//! #[component]
+//! fn scores(scores: &Scores) -> impl View + '_ {
+//!     let scores = scores.iter().map(|(id, score)| (*id, *score));
+//!
+//!     keyed(scores, |(_, score)| view! { <li>{ score }</li> })
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! Types that can't implement [`Keyed`] can provide the key with a closure using
+//! [`keyed_by`].
+//!
+//! Products of items that are removed from a keyed list are dropped along with their
+//! DOM nodes. For lists whose contents are replaced wholesale at a high frequency,
+//! such as sprites redrawn every frame of a game loop, [`pooled`](KeyedList::pooled)
+//! lists keep them around instead and update them to render new items.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use web_sys::Node;
+
+use crate::dom::{Anchor, Fragment, FragmentBuilder};
+use crate::internal::{In, Out};
+use crate::{Mountable, View};
+
+/// Data with a key that uniquely identifies it within a list, see [`keyed`].
+pub trait Keyed {
+    type Key: Hash + Eq + 'static;
+
+    fn key(&self) -> Self::Key;
+}
+
+impl<T: Keyed> Keyed for &T {
+    type Key = T::Key;
+
+    fn key(&self) -> T::Key {
+        (**self).key()
+    }
+}
+
+/// Pairs are keyed by their first element, such as the ids of a `Vec<(Id, T)>`.
+impl<K, T> Keyed for (K, T)
+where
+    K: Hash + Eq + Clone + 'static,
+{
+    type Key = K;
+
+    fn key(&self) -> K {
+        self.0.clone()
+    }
+}
+
+/// Render each of the `items` with the `render` closure, diffing them by their
+/// [`Keyed::key`]. Keys should be unique, should there be duplicates only the first
+/// item with a given key will reuse its previous product.
+pub fn keyed<I, F, V>(
+    items: I,
+    render: F,
+) -> KeyedList<I, impl Fn(&I::Item) -> <I::Item as Keyed>::Key, F>
+where
+    I: IntoIterator,
+    I::Item: Keyed,
+    F: Fn(I::Item) -> V,
+    V: View,
+{
+    keyed_by(items, Keyed::key, render)
+}
+
+/// Same as [`keyed`], but with the key of each item provided by the `key` closure.
+pub const fn keyed_by<I, K, F>(items: I, key: K, render: F) -> KeyedList<I, K, F> {
+    KeyedList {
+        items,
+        key,
+        render,
+        pooled: false,
+    }
+}
+
+/// Smart [`View`] rendering a keyed list, see [`keyed`].
+pub struct KeyedList<I, K, F> {
+    items: I,
+    key: K,
+    render: F,
+    pooled: bool,
+}
+
+impl<I, K, F> KeyedList<I, K, F> {
+    /// Keep the products of removed items in a pool instead of dropping them, and
+    /// reuse them along with their DOM nodes to render items with new keys.
+    ///
+    /// ```
+    /// # use kobold::prelude::*;
+    /// # use kobold::list::{keyed, Keyed};
+    /// struct Sprite {
+    ///     id: u64,
+    ///     x: f64,
+    ///     y: f64,
+    /// }
+    ///
+    /// impl Keyed for Sprite {
+    ///     type Key = u64;
+    ///
+    ///     fn key(&self) -> u64 {
+    ///         self.id
+    ///     }
+    /// }
+    ///
+    /// #[component]
+    /// fn frame(sprites: &[Sprite]) -> impl View + '_ {
+    ///     keyed(sprites, |sprite| {
+    ///         view! { <div.sprite data-x={sprite.x} data-y={sprite.y}> }
+    ///     })
+    ///     .pooled()
+    /// }
+    /// # fn main() {}
+    /// ```
+    ///
+    /// The pool grows to the number of items removed at once and is never shrunk, so
+    /// this is only worth it when the same number of items keeps being replaced.
+    ///
+    /// A pooled product built for one key is updated with the view of an item with a
+    /// different key, so anything it holds on to besides what the new view overwrites
+    /// carries over to the new item. That includes the state of
+    /// [`stateful`](crate::stateful) components and hooks, uncontrolled `value`s of
+    /// inputs, focus and scroll positions. Only pool lists whose items are rendered
+    /// entirely from their data, like the sprites above.
+    pub const fn pooled(mut self) -> Self {
+        self.pooled = true;
+        self
+    }
+}
+
+pub struct KeyedProduct<K, P: Mountable> {
+    keys: Vec<K>,
+    products: Vec<Box<P>>,
+    pool: Vec<Box<P>>,
+    fragment: FragmentBuilder,
+}
+
+impl<I, K, F, V, Key> View for KeyedList<I, K, F>
+where
+    I: IntoIterator,
+    K: Fn(&I::Item) -> Key,
+    Key: Hash + Eq + 'static,
+    F: Fn(I::Item) -> V,
+    V: View,
+{
+    type Product = KeyedProduct<Key, V::Product>;
+
+    fn build(self, p: In<Self::Product>) -> Out<Self::Product> {
+        let mut list = p.put(KeyedProduct {
+            keys: Vec::new(),
+            products: Vec::new(),
+            pool: Vec::new(),
+            fragment: FragmentBuilder::new(),
+        });
+
+        for item in self.items {
+            let key = (self.key)(&item);
+            let built = In::boxed(|p| (self.render)(item).build(p));
+
+            list.fragment.append(built.js());
+            list.keys.push(key);
+            list.products.push(built);
+        }
+
+        list
+    }
+
+    fn update(self, p: &mut Self::Product) {
+        let mut keys = Vec::new();
+        let mut views = Vec::new();
+
+        for item in self.items {
+            keys.push((self.key)(&item));
+            views.push((self.render)(item));
+        }
+
+        let sources = reconcile(&p.keys, &keys);
+        let mut old: Vec<_> = p.products.drain(..).map(Some).collect();
+
+        if self.pooled {
+            for index in removed(&sources, old.len()) {
+                if let Some(product) = old[index].take() {
+                    product.unmount();
+                    p.pool.push(product);
+                }
+            }
+        }
+
+        let moved_from = moved_from(&sources);
+
+        for (view, source) in views.into_iter().zip(sources) {
+            let product = match source.and_then(|source| old[source].take()) {
+                Some(mut product) => {
+                    view.update(&mut product);
+                    product
+                }
+                None => match p.pool.pop() {
+                    Some(mut product) => {
+                        view.update(&mut product);
+                        product
+                    }
+                    None => In::boxed(|p| view.build(p)),
+                },
+            };
+
+            p.products.push(product);
+        }
+
+        for removed in old.into_iter().flatten() {
+            removed.unmount();
+        }
+
+        if let Some(from) = moved_from {
+            for product in &p.products[from..] {
+                p.fragment.move_to_end(product.js());
+            }
+        }
+
+        p.keys = keys;
+    }
+}
+
+/// Indexes of the old products that aren't a source of any new item, these are
+/// unmounted and, in [`pooled`](KeyedList::pooled) lists, reused for new keys.
+fn removed(sources: &[Option<usize>], len: usize) -> Vec<usize> {
+    let mut kept = vec![false; len];
+
+    for &source in sources.iter().flatten() {
+        kept[source] = true;
+    }
+
+    (0..len).filter(|&index| !kept[index]).collect()
+}
+
+/// Index of the first item that has to be moved to the end of the list for the DOM to
+/// match the new order. Items before it kept their relative order, and any removed items
+/// in between them are unmounted, so filtering a list never moves any nodes.
+fn moved_from(sources: &[Option<usize>]) -> Option<usize> {
+    let mut last = None;
+
+    sources.iter().position(|&source| match source {
+        Some(source) if last.is_none_or(|last| source > last) => {
+            last = Some(source);
+            false
+        }
+        _ => true,
+    })
+}
+
+/// For every key in `new` find the index of the same key in `old`, if any.
+fn reconcile<K: Hash + Eq>(old: &[K], new: &[K]) -> Vec<Option<usize>> {
+    let mut index = HashMap::with_capacity(old.len());
+
+    for (idx, key) in old.iter().enumerate() {
+        index.entry(key).or_insert(idx);
+    }
+
+    new.iter().map(|key| index.remove(key)).collect()
+}
+
+impl<K, P> Anchor for KeyedProduct<K, P>
+where
+    K: 'static,
+    P: Mountable,
+{
+    type Js = Node;
+    type Target = Fragment;
+
+    fn anchor(&self) -> &Fragment {
+        &self.fragment
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reconcile_reorder() {
+        assert_eq!(
+            reconcile(&[1, 2, 3], &[3, 1, 2]),
+            [Some(2), Some(0), Some(1)]
+        );
+    }
+
+    #[test]
+    fn reconcile_insert() {
+        assert_eq!(
+            reconcile(&[1, 2, 3], &[1, 4, 2, 3]),
+            [Some(0), None, Some(1), Some(2)]
+        );
+    }
+
+    #[test]
+    fn reconcile_delete() {
+        assert_eq!(reconcile(&[1, 2, 3], &[1, 3]), [Some(0), Some(2)]);
+    }
+
+    #[test]
+    fn reconcile_filter() {
+        assert_eq!(reconcile(&[1, 2, 3, 4], &[2, 4]), [Some(1), Some(3)]);
+    }
+
+    #[test]
+    fn moves_after_reorder() {
+        // [A, B, C] -> [A, C, B], only B is moved
+        assert_eq!(moved_from(&[Some(0), Some(2), Some(1)]), Some(2));
+        // [A, B, C] -> [C, A, B]
+        assert_eq!(moved_from(&[Some(2), Some(0), Some(1)]), Some(1));
+        assert_eq!(moved_from(&[Some(0), Some(1), Some(2)]), None);
+    }
+
+    #[test]
+    fn no_moves_after_filter() {
+        assert_eq!(moved_from(&[Some(1), Some(3)]), None);
+        assert_eq!(moved_from(&[]), None);
+    }
+
+    #[test]
+    fn moves_after_insert() {
+        assert_eq!(moved_from(&[Some(0), None, Some(1)]), Some(1));
+        assert_eq!(moved_from(&[Some(0), Some(1), None]), Some(2));
+    }
+
+    #[test]
+    fn removed_products() {
+        // [1, 2, 3] -> [3, 4, 5], products of 1 and 2 go to the pool
+        let sources = reconcile(&[1, 2, 3], &[3, 4, 5]);
+
+        assert_eq!(removed(&sources, 3), [0, 1]);
+        assert_eq!(removed(&reconcile(&[1, 2], &[2, 1]), 2), []);
+        assert_eq!(removed(&reconcile(&[1, 1], &[1]), 2), [1]);
+    }
+
+    #[test]
+    fn pairs_keyed_by_first() {
+        assert_eq!((7, "seven").key(), 7);
+        assert_eq!(Keyed::key(&&(7, "seven")), 7);
+    }
+
+    #[test]
+    fn reconcile_duplicates() {
+        assert_eq!(reconcile(&[1, 1], &[1, 1]), [Some(0), None]);
+    }
+}
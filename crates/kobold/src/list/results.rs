@@ -0,0 +1,186 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Lists of items that can fail to render
+//!
+//! [`results`] renders an iterator of `Result<V, E>`, rendering every `Ok` view and
+//! handling every `Err` according to a policy, so that a single invalid item doesn't
+//! prevent the rest of the list from rendering:
+//!
+//! * [`Skip`] ignores the errors.
+//! * [`Collect`] pushes the errors to a `Vec`, to be reported separately.
+//! * [`Inline`] renders a view for each error in its place in the list.
+//!
+//! ```
+//! use kobold::prelude::*;
+//! use kobold::list::results::{results, Inline};
+//!
+//! #[component]
+//! fn rows(csv: &str) -> impl View + '_ {
+//!     let rows = csv.lines().map(|line| {
+//!         let (name, age) = line.split_once(',').ok_or(line)?;
+//!         let age: u32 = age.trim().parse().map_err(|_| line)?;
+//!
+//!         Ok(view! { <tr><td>{ name }</td><td>{ age }</td></tr> })
+//!     });
+//!
+//!     view! {
+//!         <table>
+//!         {
+//!             results(rows, Inline(invalid))
+//!         }
+//!         </table>
+//!     }
+//! }
+//!
+//! fn invalid(line: &str) -> impl View + '_ {
+//!     view! { <tr.error><td colspan="2">"Invalid row: "{ line }</td></tr> }
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! Lists are diffed positionally, same as with the [`for`](crate::keywords::for) keyword.
+//! With [`Inline`] every position keeps track of whether it rendered an item or an error,
+//! so an item turning into an error or back replaces the nodes at that position only.
+//! Skipped and collected errors take no position, which shifts the items after them.
+
+use crate::branching::Branch2;
+use crate::internal::{In, Out};
+use crate::list::unbounded::ListProduct;
+use crate::list::List;
+use crate::View;
+
+/// Render `items`, handling errors according to the `on_error` policy, see the
+/// [module documentation](self).
+pub const fn results<I, P>(items: I, on_error: P) -> Results<I, P> {
+    Results { items, on_error }
+}
+
+/// Smart [`View`] rendering a list of results, see [`results`].
+pub struct Results<I, P> {
+    items: I,
+    on_error: P,
+}
+
+/// Policy for errors in a list rendered with [`results`].
+pub trait OnError<V, E> {
+    /// View rendered for each item of the list.
+    type View: View;
+
+    /// Returns the view to render for the `item`, or `None` if it takes no position.
+    fn handle(&mut self, item: Result<V, E>) -> Option<Self::View>;
+}
+
+/// Ignore errors, only rendering `Ok` items.
+pub struct Skip;
+
+/// Render only `Ok` items, pushing the errors to a `Vec` in the order they were found.
+pub struct Collect<'a, E>(pub &'a mut Vec<E>);
+
+/// Render a view created by the closure for each error, in place of the item.
+pub struct Inline<F>(pub F);
+
+impl<V: View, E> OnError<V, E> for Skip {
+    type View = V;
+
+    fn handle(&mut self, item: Result<V, E>) -> Option<V> {
+        item.ok()
+    }
+}
+
+impl<V: View, E> OnError<V, E> for Collect<'_, E> {
+    type View = V;
+
+    fn handle(&mut self, item: Result<V, E>) -> Option<V> {
+        item.map_err(|err| self.0.push(err)).ok()
+    }
+}
+
+impl<V, E, F, W> OnError<V, E> for Inline<F>
+where
+    V: View,
+    F: FnMut(E) -> W,
+    W: View,
+{
+    type View = Branch2<V, W>;
+
+    fn handle(&mut self, item: Result<V, E>) -> Option<Branch2<V, W>> {
+        Some(match item {
+            Ok(view) => Branch2::A(view),
+            Err(err) => Branch2::B((self.0)(err)),
+        })
+    }
+}
+
+impl<I, V, E, P> View for Results<I, P>
+where
+    I: IntoIterator<Item = Result<V, E>>,
+    P: OnError<V, E>,
+{
+    type Product = ListProduct<<P::View as View>::Product>;
+
+    fn build(self, p: In<Self::Product>) -> Out<Self::Product> {
+        let mut on_error = self.on_error;
+
+        List::new(
+            self.items
+                .into_iter()
+                .filter_map(move |item| on_error.handle(item)),
+        )
+        .build(p)
+    }
+
+    fn update(self, p: &mut Self::Product) {
+        let mut on_error = self.on_error;
+
+        List::new(
+            self.items
+                .into_iter()
+                .filter_map(move |item| on_error.handle(item)),
+        )
+        .update(p)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn items() -> Vec<Result<u32, &'static str>> {
+        vec![Ok(1), Err("two"), Ok(3), Err("four")]
+    }
+
+    fn handle<P: OnError<u32, &'static str>>(mut on_error: P) -> Vec<P::View> {
+        items()
+            .into_iter()
+            .filter_map(|item| on_error.handle(item))
+            .collect()
+    }
+
+    #[test]
+    fn skip_errors() {
+        assert_eq!(handle(Skip), [1, 3]);
+    }
+
+    #[test]
+    fn collect_errors() {
+        let mut errors = Vec::new();
+
+        assert_eq!(handle(Collect(&mut errors)), [1, 3]);
+        assert_eq!(errors, ["two", "four"]);
+    }
+
+    #[test]
+    fn inline_errors() {
+        let positions: Vec<_> = handle(Inline(str::len))
+            .into_iter()
+            .map(|view| match view {
+                Branch2::A(n) => Ok(n),
+                Branch2::B(len) => Err(len),
+            })
+            .collect();
+
+        assert_eq!(positions, [Ok(1), Err(3), Ok(3), Err(4)]);
+    }
+}
@@ -0,0 +1,274 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Virtualized list that only renders rows visible in its viewport
+
+use std::cell::RefCell;
+use std::ops::Range;
+use std::rc::Rc;
+
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
+use web_sys::{Element, Node};
+
+use crate::dom::Anchor;
+use crate::internal::{self, In, Out};
+use crate::list::keyed::{keyed_by, KeyedProduct};
+use crate::{Mountable, View};
+
+/// Number of rows rendered above and below the viewport by default.
+pub const DEFAULT_BUFFER: usize = 4;
+
+/// A scrollable list of `count` rows of fixed height, where only the rows
+/// within the viewport (plus a small buffer) are built.
+///
+/// Rows are keyed by their index in a [`pooled`](crate::list::keyed::KeyedList::pooled)
+/// keyed list. As the list is scrolled, rows that stay within the viewport keep their
+/// products and DOM nodes. Products of rows leaving it are recycled to render the rows
+/// entering it, so once the viewport has been filled scrolling doesn't build any new
+/// products. The number of DOM nodes stays constant regardless of the total number of
+/// rows.
+///
+/// Since recycled products are only updated with the view of their new row, rows
+/// should be rendered entirely from their index and data, without
+/// [`stateful`](crate::stateful) components or uncontrolled inputs inside of them.
+///
+/// Because rows are rendered on scroll, independently of the parent view,
+/// the `render` closure must be `'static`. Data it needs to render rows should
+/// be owned by it, or shared through an [`Rc`].
+///
+/// ```no_run
+/// use kobold::prelude::*;
+/// use kobold::list::VirtualList;
+///
+/// #[component]
+/// fn table(rows: usize) -> impl View {
+///     view! {
+///         <div.table>
+///         {
+///             VirtualList::new(rows, 24.0, 600.0, |n| view! {
+///                 <div.row>"Row #"{ n }</div>
+///             })
+///         }
+///     }
+/// }
+/// # fn main() {}
+/// ```
+pub struct VirtualList<F> {
+    count: usize,
+    row_height: f64,
+    height: f64,
+    buffer: usize,
+    render: F,
+}
+
+impl<F> VirtualList<F> {
+    /// Create a list of `count` rows of `row_height` pixels each, rendered inside
+    /// a scrollable viewport `height` pixels tall.
+    pub const fn new(count: usize, row_height: f64, height: f64, render: F) -> Self {
+        VirtualList {
+            count,
+            row_height,
+            height,
+            buffer: DEFAULT_BUFFER,
+            render,
+        }
+    }
+
+    /// Set the number of rows rendered above and below the viewport, defaults to [`DEFAULT_BUFFER`].
+    pub const fn buffer(mut self, rows: usize) -> Self {
+        self.buffer = rows;
+        self
+    }
+}
+
+pub struct VirtualProduct<F, P: Mountable> {
+    window: Rc<RefCell<Window<F, P>>>,
+    container: Node,
+    onscroll: Closure<dyn FnMut()>,
+}
+
+struct Window<F, P: Mountable> {
+    count: usize,
+    row_height: f64,
+    height: f64,
+    buffer: usize,
+    render: F,
+    range: Range<usize>,
+    container: Element,
+    spacer: Node,
+    rows: Node,
+    list: Box<KeyedProduct<usize, P>>,
+}
+
+impl<F, V> View for VirtualList<F>
+where
+    F: Fn(usize) -> V + 'static,
+    V: View,
+{
+    type Product = VirtualProduct<F, V::Product>;
+
+    fn build(self, p: In<Self::Product>) -> Out<Self::Product> {
+        let container = internal::create_element("div");
+        let spacer = internal::create_element("div");
+        let rows = internal::create_element("div");
+
+        let mut window = Window {
+            count: self.count,
+            row_height: self.row_height,
+            height: self.height,
+            buffer: self.buffer,
+            range: 0..0,
+            container: container.clone().unchecked_into(),
+            spacer,
+            rows,
+            list: In::boxed(|p| {
+                keyed_by(0..0, |n: &usize| *n, &self.render)
+                    .pooled()
+                    .build(p)
+            }),
+            render: self.render,
+        };
+
+        window.range = window.visible(0.0);
+        window.render_rows();
+
+        internal::obj(&container).style(&format!("overflow-y:auto;height:{}px", window.height));
+        window.resize();
+        window.position();
+
+        let append = |parent: &Node, child: &JsValue| {
+            parent
+                .append_child(child.unchecked_ref())
+                .expect_throw("failed to append node");
+        };
+
+        append(&window.rows, window.list.js());
+        append(&window.spacer, &window.rows);
+        append(&container, &window.spacer);
+
+        let window = Rc::new(RefCell::new(window));
+
+        let onscroll = {
+            let window = Rc::downgrade(&window);
+
+            Closure::<dyn FnMut()>::new(move || {
+                if let Some(window) = window.upgrade() {
+                    window.borrow_mut().scroll();
+                }
+            })
+        };
+
+        container
+            .add_event_listener_with_callback("scroll", onscroll.as_ref().unchecked_ref())
+            .expect_throw("failed to add scroll listener");
+
+        p.put(VirtualProduct {
+            window,
+            container,
+            onscroll,
+        })
+    }
+
+    fn update(self, p: &mut Self::Product) {
+        let mut window = p.window.borrow_mut();
+
+        if window.height != self.height {
+            window.height = self.height;
+            internal::obj(&p.container).style(&format!("overflow-y:auto;height:{}px", self.height));
+        }
+
+        window.count = self.count;
+        window.row_height = self.row_height;
+        window.buffer = self.buffer;
+        window.render = self.render;
+
+        window.resize();
+        window.refresh();
+    }
+}
+
+impl<F, V> Window<F, V::Product>
+where
+    F: Fn(usize) -> V,
+    V: View,
+{
+    /// Range of rows to render for the current viewport, including the buffer.
+    fn visible(&self, scroll_top: f64) -> Range<usize> {
+        if self.row_height <= 0.0 {
+            return 0..self.count;
+        }
+
+        let first = (scroll_top / self.row_height) as usize;
+        let last = ((scroll_top + self.height) / self.row_height).ceil() as usize;
+
+        let start = first.saturating_sub(self.buffer).min(self.count);
+        let end = last.saturating_add(self.buffer).min(self.count);
+
+        start..end
+    }
+
+    /// Set the height of the spacer to the height of all rows.
+    fn resize(&self) {
+        let total = self.count as f64 * self.row_height;
+
+        internal::obj(&self.spacer).style(&format!("position:relative;height:{total}px"));
+    }
+
+    /// Move the rendered rows to their offset within the spacer.
+    fn position(&self) {
+        let top = self.range.start as f64 * self.row_height;
+
+        internal::obj(&self.rows).style(&format!("position:absolute;top:{top}px;left:0;right:0"));
+    }
+
+    /// Re-render rows if the visible range has changed since last render.
+    fn scroll(&mut self) {
+        let range = self.visible(self.container.scroll_top() as f64);
+
+        if range != self.range {
+            self.range = range;
+            self.render_rows();
+            self.position();
+        }
+    }
+
+    /// Re-render all rows in the visible range.
+    fn refresh(&mut self) {
+        self.range = self.visible(self.container.scroll_top() as f64);
+        self.render_rows();
+        self.position();
+    }
+
+    /// Render the rows in the current range, keyed by their index in the whole list,
+    /// recycling the products of rows that have scrolled out of it.
+    fn render_rows(&mut self) {
+        keyed_by(self.range.clone(), |n: &usize| *n, &self.render)
+            .pooled()
+            .update(&mut self.list);
+    }
+}
+
+impl<F, P> Drop for VirtualProduct<F, P>
+where
+    P: Mountable,
+{
+    fn drop(&mut self) {
+        let _ = self
+            .container
+            .remove_event_listener_with_callback("scroll", self.onscroll.as_ref().unchecked_ref());
+    }
+}
+
+impl<F, P> Anchor for VirtualProduct<F, P>
+where
+    P: Mountable,
+{
+    type Js = Node;
+    type Target = Node;
+
+    fn anchor(&self) -> &Node {
+        &self.container
+    }
+}
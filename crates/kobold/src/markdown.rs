@@ -0,0 +1,499 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Rendering markdown, enabled with the `markdown` feature
+//!
+//! [`markdown`] parses [CommonMark](https://commonmark.org/) with
+//! [`pulldown-cmark`](https://docs.rs/pulldown-cmark) and builds DOM elements for it,
+//! which makes it suitable for user-authored content such as comments or chat messages:
+//!
+//! ```
+//! use kobold::prelude::*;
+//! use kobold::markdown::markdown;
+//!
+//! #[component]
+//! fn comment(body: &str) -> impl View + '_ {
+//!     view! {
+//!         <article.comment>{ markdown(body) }</article>
+//!     }
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! Only a safe subset of markdown is rendered:
+//!
+//! * Paragraphs, headings, block quotes, lists, code, emphasis, strikethrough, links,
+//!   images, line breaks and rules.
+//! * Text, including any raw HTML in the source, is always inserted as text, never
+//!   parsed as HTML.
+//! * Links and images are only given `http:`, `https:`, `mailto:` or relative URLs.
+//!   Links with any other URL, such as `javascript:`, render as plain text, images
+//!   render their alt text instead.
+//!
+//! When the source changes it's parsed again and compared with the previous tree, only
+//! the elements that differ are replaced in the DOM.
+
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
+use web_sys::Node;
+
+use crate::dom::{Anchor, Fragment, FragmentBuilder, Property, TextContent};
+use crate::internal::{self, In, Out};
+use crate::{Mountable, View};
+
+/// Render markdown `source`, see the [module documentation](self).
+pub const fn markdown(source: &str) -> Markdown<'_> {
+    Markdown { source }
+}
+
+/// View created with [`markdown`].
+pub struct Markdown<'a> {
+    source: &'a str,
+}
+
+pub struct MarkdownProduct {
+    source: String,
+    nodes: Vec<Mounted>,
+    fragment: FragmentBuilder,
+}
+
+/// Node of the tree produced from markdown, before it's rendered.
+#[derive(Debug, PartialEq)]
+struct Tree {
+    kind: Kind,
+    children: Vec<Tree>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Kind {
+    Element(&'static str, Vec<(&'static str, String)>),
+    Text(String),
+}
+
+/// A rendered [`Tree`] node.
+struct Mounted {
+    kind: Kind,
+    node: Node,
+    children: Vec<Mounted>,
+}
+
+impl Tree {
+    fn element(tag: &'static str, attrs: Vec<(&'static str, String)>) -> Self {
+        Tree {
+            kind: Kind::Element(tag, attrs),
+            children: Vec::new(),
+        }
+    }
+
+    fn text(text: impl Into<String>) -> Self {
+        Tree {
+            kind: Kind::Text(text.into()),
+            children: Vec::new(),
+        }
+    }
+
+    fn build(self) -> Mounted {
+        let node = match &self.kind {
+            Kind::Element(tag, attrs) => {
+                let node = internal::create_element(tag);
+
+                for (name, value) in attrs {
+                    internal::obj(&node).set_attr(name, value);
+                }
+                node
+            }
+            Kind::Text(text) => internal::text_node(text),
+        };
+
+        let children = self
+            .children
+            .into_iter()
+            .map(|child| {
+                let child = child.build();
+
+                append(&node, child.node.js());
+                child
+            })
+            .collect();
+
+        Mounted {
+            kind: self.kind,
+            node,
+            children,
+        }
+    }
+}
+
+fn append(parent: &Node, child: &JsValue) {
+    parent
+        .append_child(child.unchecked_ref())
+        .expect_throw("failed to append node");
+}
+
+/// Diff the `old` rendered nodes with the `new` tree, using `append` to add nodes past
+/// the end of `old`.
+fn diff(old: &mut Vec<Mounted>, new: Vec<Tree>, append: &dyn Fn(&JsValue)) {
+    let len = new.len();
+
+    for (idx, tree) in new.into_iter().enumerate() {
+        let Some(mounted) = old.get_mut(idx) else {
+            let mounted = tree.build();
+
+            append(mounted.node.js());
+            old.push(mounted);
+            continue;
+        };
+
+        match (&mut mounted.kind, tree.kind) {
+            (Kind::Text(old), Kind::Text(new)) => {
+                if *old != new {
+                    TextContent.set(&mounted.node, new.as_str());
+                    *old = new;
+                }
+            }
+            (old_kind, new_kind) if *old_kind == new_kind => {
+                let node = &mounted.node;
+
+                diff(&mut mounted.children, tree.children, &|child| {
+                    self::append(node, child)
+                });
+            }
+            (_, kind) => {
+                let replacement = Tree {
+                    kind,
+                    children: tree.children,
+                }
+                .build();
+
+                mounted.node.replace_with(replacement.node.js());
+                *mounted = replacement;
+            }
+        }
+    }
+
+    for removed in old.drain(len.min(old.len())..) {
+        removed.node.unmount();
+    }
+}
+
+/// Returns `true` for relative URLs and the `http`, `https` and `mailto` schemes.
+fn safe_url(url: &str) -> bool {
+    let scheme = match url.find([':', '/', '?', '#']) {
+        Some(idx) if url[idx..].starts_with(':') => &url[..idx],
+        _ => return true,
+    };
+
+    ["http", "https", "mailto"]
+        .iter()
+        .any(|safe| scheme.eq_ignore_ascii_case(safe))
+}
+
+/// Collect the text of `trees` into `out`, dropping all elements.
+fn text_of(trees: Vec<Tree>, out: &mut String) {
+    for tree in trees {
+        if let Kind::Text(text) = tree.kind {
+            out.push_str(&text);
+        }
+        text_of(tree.children, out);
+    }
+}
+
+fn parse(source: &str) -> Vec<Tree> {
+    // Elements currently open, `None` for tags whose children are added to the parent
+    let mut stack: Vec<(Option<Tree>, Vec<Tree>)> = vec![(None, Vec::new())];
+
+    let push = |stack: &mut Vec<(Option<Tree>, Vec<Tree>)>, tree: Tree| {
+        if let Some((_, children)) = stack.last_mut() {
+            children.push(tree);
+        }
+    };
+
+    for event in Parser::new_ext(source, Options::ENABLE_STRIKETHROUGH) {
+        match event {
+            Event::Start(tag) => {
+                let element = match tag {
+                    Tag::Paragraph | Tag::HtmlBlock => Some(Tree::element("p", Vec::new())),
+                    Tag::Heading { level, .. } => Some(Tree::element(heading(level), Vec::new())),
+                    Tag::BlockQuote(_) => Some(Tree::element("blockquote", Vec::new())),
+                    Tag::CodeBlock(kind) => {
+                        let attrs = match kind {
+                            CodeBlockKind::Fenced(lang) => lang
+                                .split_whitespace()
+                                .next()
+                                .map(|lang| vec![("class", format!("language-{lang}"))])
+                                .unwrap_or_default(),
+                            CodeBlockKind::Indented => Vec::new(),
+                        };
+
+                        stack.push((Some(Tree::element("pre", Vec::new())), Vec::new()));
+                        Some(Tree::element("code", attrs))
+                    }
+                    Tag::List(Some(start)) if start != 1 => {
+                        Some(Tree::element("ol", vec![("start", start.to_string())]))
+                    }
+                    Tag::List(Some(_)) => Some(Tree::element("ol", Vec::new())),
+                    Tag::List(None) => Some(Tree::element("ul", Vec::new())),
+                    Tag::Item => Some(Tree::element("li", Vec::new())),
+                    Tag::Emphasis => Some(Tree::element("em", Vec::new())),
+                    Tag::Strong => Some(Tree::element("strong", Vec::new())),
+                    Tag::Strikethrough => Some(Tree::element("del", Vec::new())),
+                    Tag::Link {
+                        dest_url, title, ..
+                    } if safe_url(&dest_url) => {
+                        let mut attrs = vec![("href", dest_url.into_string())];
+
+                        if !title.is_empty() {
+                            attrs.push(("title", title.into_string()));
+                        }
+                        Some(Tree::element("a", attrs))
+                    }
+                    Tag::Image {
+                        dest_url, title, ..
+                    } if safe_url(&dest_url) => {
+                        let mut attrs = vec![("src", dest_url.into_string())];
+
+                        if !title.is_empty() {
+                            attrs.push(("title", title.into_string()));
+                        }
+                        Some(Tree::element("img", attrs))
+                    }
+                    _ => None,
+                };
+
+                stack.push((element, Vec::new()));
+            }
+            Event::End(end) => {
+                let Some((element, children)) = stack.pop() else {
+                    continue;
+                };
+
+                match element {
+                    Some(mut img) if is_img(&img) => {
+                        let mut alt = String::new();
+
+                        text_of(children, &mut alt);
+
+                        if let Kind::Element(_, attrs) = &mut img.kind {
+                            attrs.push(("alt", alt));
+                        }
+                        push(&mut stack, img);
+                    }
+                    Some(mut tree) => {
+                        tree.children = children;
+                        push(&mut stack, tree);
+                    }
+                    None => {
+                        if let Some((_, parent)) = stack.last_mut() {
+                            parent.extend(children);
+                        }
+                    }
+                }
+
+                // Code blocks open both a `<pre>` and a `<code>`
+                if let TagEnd::CodeBlock = end {
+                    if let Some((Some(mut pre), children)) = stack.pop() {
+                        pre.children = children;
+                        push(&mut stack, pre);
+                    }
+                }
+            }
+            Event::Text(text) | Event::Html(text) | Event::InlineHtml(text) => {
+                push(&mut stack, Tree::text(text.into_string()));
+            }
+            Event::Code(code) => {
+                let mut tree = Tree::element("code", Vec::new());
+
+                tree.children.push(Tree::text(code.into_string()));
+                push(&mut stack, tree);
+            }
+            Event::SoftBreak => push(&mut stack, Tree::text("\n")),
+            Event::HardBreak => push(&mut stack, Tree::element("br", Vec::new())),
+            Event::Rule => push(&mut stack, Tree::element("hr", Vec::new())),
+            _ => (),
+        }
+    }
+
+    stack.pop().map(|(_, root)| root).unwrap_or_default()
+}
+
+fn is_img(tree: &Tree) -> bool {
+    matches!(tree.kind, Kind::Element("img", _))
+}
+
+fn heading(level: HeadingLevel) -> &'static str {
+    match level {
+        HeadingLevel::H1 => "h1",
+        HeadingLevel::H2 => "h2",
+        HeadingLevel::H3 => "h3",
+        HeadingLevel::H4 => "h4",
+        HeadingLevel::H5 => "h5",
+        HeadingLevel::H6 => "h6",
+    }
+}
+
+impl View for Markdown<'_> {
+    type Product = MarkdownProduct;
+
+    fn build(self, p: In<Self::Product>) -> Out<Self::Product> {
+        let fragment = FragmentBuilder::new();
+        let mut nodes = Vec::new();
+
+        diff(&mut nodes, parse(self.source), &|node| {
+            fragment.append(node)
+        });
+
+        p.put(MarkdownProduct {
+            source: self.source.into(),
+            nodes,
+            fragment,
+        })
+    }
+
+    fn update(self, p: &mut Self::Product) {
+        if p.source == self.source {
+            return;
+        }
+
+        self.source.clone_into(&mut p.source);
+
+        let fragment = &p.fragment;
+
+        diff(&mut p.nodes, parse(self.source), &|node| {
+            fragment.append(node)
+        });
+    }
+}
+
+impl Anchor for MarkdownProduct {
+    type Js = Node;
+    type Target = Fragment;
+
+    fn anchor(&self) -> &Fragment {
+        &self.fragment
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn el(tag: &'static str, children: Vec<Tree>) -> Tree {
+        Tree {
+            kind: Kind::Element(tag, Vec::new()),
+            children,
+        }
+    }
+
+    fn with(tag: &'static str, attrs: &[(&'static str, &str)], children: Vec<Tree>) -> Tree {
+        Tree {
+            kind: Kind::Element(tag, attrs.iter().map(|&(n, v)| (n, v.into())).collect()),
+            children,
+        }
+    }
+
+    fn text(text: &str) -> Tree {
+        Tree::text(text)
+    }
+
+    #[test]
+    fn headings_and_inline() {
+        assert_eq!(
+            parse("# Hello\n\nSome *nice* **bold** `code` ~~old~~"),
+            [
+                el("h1", vec![text("Hello")]),
+                el(
+                    "p",
+                    vec![
+                        text("Some "),
+                        el("em", vec![text("nice")]),
+                        text(" "),
+                        el("strong", vec![text("bold")]),
+                        text(" "),
+                        el("code", vec![text("code")]),
+                        text(" "),
+                        el("del", vec![text("old")]),
+                    ]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn lists_and_code_blocks() {
+        assert_eq!(
+            parse("3. three\n4. four\n\n- item\n\n```rust\nfn main() {}\n```"),
+            [
+                with(
+                    "ol",
+                    &[("start", "3")],
+                    vec![el("li", vec![text("three")]), el("li", vec![text("four")])]
+                ),
+                el("ul", vec![el("li", vec![text("item")])]),
+                el(
+                    "pre",
+                    vec![with(
+                        "code",
+                        &[("class", "language-rust")],
+                        vec![text("fn main() {}\n")]
+                    )]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn links_and_images() {
+        assert_eq!(
+            parse("[home](/ \"Home\") ![cat *pic*](https://example.com/cat.png)"),
+            [el(
+                "p",
+                vec![
+                    with("a", &[("href", "/"), ("title", "Home")], vec![text("home")]),
+                    text(" "),
+                    with(
+                        "img",
+                        &[("src", "https://example.com/cat.png"), ("alt", "cat pic")],
+                        vec![]
+                    ),
+                ]
+            )]
+        );
+    }
+
+    #[test]
+    fn unsafe_content_is_text() {
+        assert_eq!(
+            parse("[click](javascript:alert(1)) ![x](data:image/png) <b onclick=\"x\">hi</b>"),
+            [el(
+                "p",
+                vec![
+                    text("click"),
+                    text(" "),
+                    text("x"),
+                    text(" "),
+                    text("<b onclick=\"x\">"),
+                    text("hi"),
+                    text("</b>"),
+                ]
+            )]
+        );
+        assert_eq!(
+            parse("<script>alert(1)</script>"),
+            [el("p", vec![text("<script>alert(1)</script>")])]
+        );
+    }
+
+    #[test]
+    fn url_schemes() {
+        assert!(safe_url("https://example.com"));
+        assert!(safe_url("HTTP://example.com"));
+        assert!(safe_url("mailto:hello@example.com"));
+        assert!(safe_url("/path:with:colons"));
+        assert!(safe_url("?q=a:b"));
+        assert!(safe_url("#anchor"));
+        assert!(!safe_url("javascript:alert(1)"));
+        assert!(!safe_url("JavaScript:alert(1)"));
+        assert!(!safe_url("data:text/html,hi"));
+    }
+}
@@ -0,0 +1,390 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Networking helpers
+//!
+//! [`WebSocket`] wraps [`web_sys::WebSocket`], turning its events into [`WsEvent`]s
+//! passed to a single handler and optionally reconnecting when the connection drops.
+//! Inside a stateful view use [`Signal::connect_ws`](crate::stateful::Signal::connect_ws)
+//! so that the events update the state, and the socket is closed when the view is
+//! dropped:
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use kobold::prelude::*;
+//! use kobold::net::{Reconnect, WebSocket, WsEvent, WsMessage};
+//!
+//! #[derive(Default)]
+//! struct Chat {
+//!     messages: Vec<String>,
+//!     socket: Option<WebSocket>,
+//! }
+//!
+//! #[component]
+//! fn chat() -> impl View {
+//!     stateful(Chat::default, |chat| {
+//!         let send = event!(|chat, e: KeyboardEvent<HtmlInputElement>| {
+//!             if e.key() == "Enter" {
+//!                 if let Some(socket) = &chat.socket {
+//!                     socket.send(&e.current_target().value());
+//!                 }
+//!             }
+//!         });
+//!
+//!         view! {
+//!             <ul>{ for chat.messages.iter().map(|message| view! { <li>{ ref message }</li> }) }</ul>
+//!             <input onkeydown={send}>
+//!         }
+//!     })
+//!     .once(|signal| {
+//!         let reconnect = Reconnect::Backoff {
+//!             initial: Duration::from_secs(1),
+//!             max: Duration::from_secs(30),
+//!         };
+//!
+//!         let socket = signal.connect_ws("wss://example.com/chat", reconnect, |chat, event| {
+//!             if let WsEvent::Message(WsMessage::Text(text)) = event {
+//!                 chat.messages.push(text);
+//!             }
+//!         });
+//!
+//!         signal.update_silent(|chat| chat.socket = Some(socket));
+//!     })
+//! }
+//! # fn main() {}
+//! ```
+
+use std::cell::{Cell, RefCell};
+use std::rc::{Rc, Weak};
+use std::time::Duration;
+
+use js_sys::Uint8Array;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use web_sys::{BinaryType, CloseEvent, MessageEvent};
+
+use crate::internal;
+
+/// What to do when a [`WebSocket`] connection is closed, other than by calling
+/// [`WebSocket::close`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reconnect {
+    /// Stay disconnected.
+    Never,
+    /// Reconnect after a fixed delay.
+    After(Duration),
+    /// Reconnect after `initial` delay, doubling it after each failed attempt up to
+    /// `max`. The delay is reset once a connection is opened.
+    Backoff { initial: Duration, max: Duration },
+}
+
+impl Reconnect {
+    /// Delay before reconnecting after `attempts` failed attempts in a row.
+    fn delay(self, attempts: u32) -> Option<Duration> {
+        match self {
+            Reconnect::Never => None,
+            Reconnect::After(delay) => Some(delay),
+            Reconnect::Backoff { initial, max } => Some(
+                initial
+                    .checked_mul(1 << attempts.min(31))
+                    .map_or(max, |delay| delay.min(max)),
+            ),
+        }
+    }
+}
+
+/// Event of a [`WebSocket`] connection.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WsEvent {
+    /// Connection has been opened, including after reconnecting.
+    Open,
+    /// Message received from the server.
+    Message(WsMessage),
+    /// Connection has been closed.
+    Close {
+        code: u16,
+        reason: String,
+        clean: bool,
+    },
+    /// Connection failed or errored, always followed by [`Close`](WsEvent::Close).
+    Error,
+}
+
+/// Message received through a [`WebSocket`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WsMessage {
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+/// Handle to a WebSocket connection, see the [module documentation](self).
+///
+/// Handles are cheap to clone, the connection is closed once it's
+/// [closed](WebSocket::close) explicitly or all handles are dropped.
+#[derive(Clone)]
+pub struct WebSocket {
+    conn: Rc<Connection>,
+}
+
+struct Connection {
+    url: String,
+    reconnect: Reconnect,
+    /// Returns `false` if the connection is no longer needed
+    handler: Box<dyn Fn(WsEvent) -> bool>,
+    socket: RefCell<Option<web_sys::WebSocket>>,
+    listeners: RefCell<Option<Listeners>>,
+    retry: Closure<dyn FnMut()>,
+    timeout: Cell<Option<i32>>,
+    attempts: Cell<u32>,
+    closed: Cell<bool>,
+}
+
+struct Listeners {
+    _onopen: Closure<dyn FnMut()>,
+    _onmessage: Closure<dyn FnMut(MessageEvent)>,
+    _onclose: Closure<dyn FnMut(CloseEvent)>,
+    _onerror: Closure<dyn FnMut()>,
+}
+
+impl WebSocket {
+    /// Connect to `url`, calling `handler` with every event of the connection.
+    pub fn connect<F>(url: &str, reconnect: Reconnect, handler: F) -> Self
+    where
+        F: Fn(WsEvent) + 'static,
+    {
+        Self::with_handler(
+            url,
+            reconnect,
+            Box::new(move |event| {
+                handler(event);
+                true
+            }),
+        )
+    }
+
+    pub(crate) fn with_handler(
+        url: &str,
+        reconnect: Reconnect,
+        handler: Box<dyn Fn(WsEvent) -> bool>,
+    ) -> Self {
+        let conn = Rc::new_cyclic(|weak: &Weak<Connection>| {
+            let weak = weak.clone();
+
+            Connection {
+                url: url.into(),
+                reconnect,
+                handler,
+                socket: RefCell::new(None),
+                listeners: RefCell::new(None),
+                retry: Closure::new(move || {
+                    if let Some(conn) = weak.upgrade() {
+                        conn.timeout.set(None);
+                        conn.open();
+                    }
+                }),
+                timeout: Cell::new(None),
+                attempts: Cell::new(0),
+                closed: Cell::new(false),
+            }
+        });
+
+        conn.open();
+
+        WebSocket { conn }
+    }
+
+    /// Send a text message, returns `false` if the connection isn't open.
+    pub fn send(&self, text: &str) -> bool {
+        self.conn
+            .open_socket()
+            .is_some_and(|socket| socket.send_with_str(text).is_ok())
+    }
+
+    /// Send a binary message, returns `false` if the connection isn't open.
+    pub fn send_bytes(&self, bytes: &[u8]) -> bool {
+        self.conn
+            .open_socket()
+            .is_some_and(|socket| socket.send_with_u8_array(bytes).is_ok())
+    }
+
+    /// Returns `true` if the connection is currently open.
+    pub fn is_open(&self) -> bool {
+        self.conn.open_socket().is_some()
+    }
+
+    /// Close the connection without reconnecting. No more events are delivered
+    /// after this, including the [`Close`](WsEvent::Close) of this connection.
+    pub fn close(&self) {
+        self.conn.close();
+    }
+}
+
+impl Connection {
+    fn open(self: &Rc<Self>) {
+        if self.closed.get() {
+            return;
+        }
+
+        self.detach();
+
+        let socket = match web_sys::WebSocket::new(&self.url) {
+            Ok(socket) => socket,
+            Err(_) => {
+                // Invalid URL, reconnecting to it is never going to work
+                self.emit(WsEvent::Error);
+                return;
+            }
+        };
+
+        socket.set_binary_type(BinaryType::Arraybuffer);
+
+        let weak = Rc::downgrade(self);
+        let with = move |f: &dyn Fn(&Rc<Connection>)| {
+            if let Some(conn) = weak.upgrade() {
+                f(&conn);
+            }
+        };
+
+        let onopen = Closure::<dyn FnMut()>::new({
+            let with = with.clone();
+
+            move || {
+                with(&|conn| {
+                    conn.attempts.set(0);
+                    conn.emit(WsEvent::Open);
+                })
+            }
+        });
+
+        let onmessage = Closure::<dyn FnMut(MessageEvent)>::new({
+            let with = with.clone();
+
+            move |event: MessageEvent| {
+                let data = event.data();
+                let message = match data.as_string() {
+                    Some(text) => WsMessage::Text(text),
+                    None => WsMessage::Binary(Uint8Array::new(&data).to_vec()),
+                };
+
+                with(&|conn| conn.emit(WsEvent::Message(message.clone())))
+            }
+        });
+
+        let onclose = Closure::<dyn FnMut(CloseEvent)>::new({
+            let with = with.clone();
+
+            move |event: CloseEvent| {
+                with(&|conn| {
+                    conn.emit(WsEvent::Close {
+                        code: event.code(),
+                        reason: event.reason(),
+                        clean: event.was_clean(),
+                    });
+                    conn.schedule();
+                })
+            }
+        });
+
+        let onerror = Closure::<dyn FnMut()>::new(move || with(&|conn| conn.emit(WsEvent::Error)));
+
+        socket.set_onopen(Some(onopen.as_ref().unchecked_ref()));
+        socket.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+        socket.set_onclose(Some(onclose.as_ref().unchecked_ref()));
+        socket.set_onerror(Some(onerror.as_ref().unchecked_ref()));
+
+        *self.socket.borrow_mut() = Some(socket);
+        *self.listeners.borrow_mut() = Some(Listeners {
+            _onopen: onopen,
+            _onmessage: onmessage,
+            _onclose: onclose,
+            _onerror: onerror,
+        });
+    }
+
+    fn emit(&self, event: WsEvent) {
+        if !self.closed.get() && !(self.handler)(event) {
+            self.close();
+        }
+    }
+
+    fn schedule(&self) {
+        if self.closed.get() {
+            return;
+        }
+
+        let attempts = self.attempts.get();
+
+        if let Some(delay) = self.reconnect.delay(attempts) {
+            let ms = delay.as_millis().min(i32::MAX as u128) as i32;
+
+            self.attempts.set(attempts.saturating_add(1));
+            self.timeout
+                .set(Some(internal::set_timeout(self.retry.as_ref(), ms)));
+        }
+    }
+
+    fn open_socket(&self) -> Option<web_sys::WebSocket> {
+        self.socket
+            .borrow()
+            .as_ref()
+            .filter(|socket| socket.ready_state() == web_sys::WebSocket::OPEN)
+            .cloned()
+    }
+
+    /// Remove all listeners from the current socket and close it. The listeners
+    /// themselves are kept until replaced, as this can run inside one of them.
+    fn detach(&self) {
+        if let Some(socket) = self.socket.borrow_mut().take() {
+            socket.set_onopen(None);
+            socket.set_onmessage(None);
+            socket.set_onclose(None);
+            socket.set_onerror(None);
+
+            let _ = socket.close();
+        }
+    }
+
+    fn close(&self) {
+        self.closed.set(true);
+
+        if let Some(id) = self.timeout.take() {
+            internal::clear_timeout(id);
+        }
+
+        self.detach();
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reconnect_delays() {
+        let secs = Duration::from_secs;
+
+        assert_eq!(Reconnect::Never.delay(0), None);
+        assert_eq!(Reconnect::After(secs(5)).delay(0), Some(secs(5)));
+        assert_eq!(Reconnect::After(secs(5)).delay(10), Some(secs(5)));
+
+        let backoff = Reconnect::Backoff {
+            initial: secs(1),
+            max: secs(30),
+        };
+
+        let delays: Vec<_> = (0..7)
+            .map(|n| backoff.delay(n).unwrap().as_secs())
+            .collect();
+
+        assert_eq!(delays, [1, 2, 4, 8, 16, 30, 30]);
+        assert_eq!(backoff.delay(u32::MAX), Some(secs(30)));
+    }
+}
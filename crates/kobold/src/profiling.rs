@@ -0,0 +1,121 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Render timings of components, enabled with the `profiling` feature
+//!
+//! With the feature enabled every [`#[component]`](crate::component) returning an
+//! `impl View` measures how long it takes to render, from calling the component function to having built or updated its DOM.
+//! Timings are inclusive, so a component that renders other components includes their
+//! render time in its own.
+//!
+//! By default each render is recorded as a
+//! [`performance.measure`](https://developer.mozilla.org/en-US/docs/Web/API/Performance/measure)
+//! entry named after the component, visible in the performance tab of browser dev tools.
+//! To collect the timings instead, set a callback with [`on_render`]:
+//!
+//! ```no_run
+//! # fn report_slow(_: &str, _: f64) {}
+//! # #[cfg(feature = "profiling")]
+//! kobold::profiling::on_render(|component, ms| {
+//!     if ms > 4.0 {
+//!         report_slow(component, ms);
+//!     }
+//! });
+//! ```
+//!
+//! With the feature enabled the body of a component runs when its view is built or updated,
+//! rather than when the component is called. Without the feature components are rendered
+//! as they are, with no overhead.
+
+#[cfg(feature = "profiling")]
+pub use enabled::{on_render, render, Profiled};
+
+/// Render the view returned by a component function, used by the
+/// [`#[component]`](crate::component) macro.
+#[cfg(not(feature = "profiling"))]
+#[doc(hidden)]
+#[inline(always)]
+pub fn render<V, F>(_: &'static str, component: F) -> V
+where
+    V: crate::View,
+    F: FnOnce() -> V,
+{
+    component()
+}
+
+#[cfg(feature = "profiling")]
+mod enabled {
+    use std::cell::RefCell;
+
+    use crate::internal::{self, In, Out};
+    use crate::View;
+
+    type Callback = Box<dyn Fn(&'static str, f64)>;
+
+    thread_local! {
+        static CALLBACK: RefCell<Option<Callback>> = const { RefCell::new(None) };
+    }
+
+    /// Report the name of the component and its render time in milliseconds to the
+    /// `callback` instead of recording `performance.measure` entries.
+    ///
+    /// The callback must not call `on_render` itself.
+    pub fn on_render<F>(callback: F)
+    where
+        F: Fn(&'static str, f64) + 'static,
+    {
+        CALLBACK.with(|cb| *cb.borrow_mut() = Some(Box::new(callback)));
+    }
+
+    /// Render the view returned by a component function, used by the
+    /// [`#[component]`](crate::component) macro.
+    ///
+    /// The component function is only called once the view is built or updated,
+    /// so that constructing a view doesn't touch the `performance` API.
+    #[doc(hidden)]
+    pub fn render<V, F>(name: &'static str, component: F) -> Profiled<F>
+    where
+        V: View,
+        F: FnOnce() -> V,
+    {
+        Profiled { name, component }
+    }
+
+    /// Smart [`View`] measuring the render time of a component.
+    pub struct Profiled<F> {
+        name: &'static str,
+        component: F,
+    }
+
+    impl<V, F> View for Profiled<F>
+    where
+        V: View,
+        F: FnOnce() -> V,
+    {
+        type Product = V::Product;
+
+        fn build(self, p: In<Self::Product>) -> Out<Self::Product> {
+            let start = internal::performance_now();
+            let out = (self.component)().build(p);
+
+            report(self.name, start);
+
+            out
+        }
+
+        fn update(self, p: &mut Self::Product) {
+            let start = internal::performance_now();
+
+            (self.component)().update(p);
+            report(self.name, start);
+        }
+    }
+
+    fn report(name: &'static str, start: f64) {
+        CALLBACK.with(|cb| match &*cb.borrow() {
+            Some(cb) => cb(name, internal::performance_now() - start),
+            None => internal::measure_render(name, start),
+        });
+    }
+}
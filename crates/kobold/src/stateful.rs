@@ -11,36 +11,134 @@
 //! could ever do is render itself once. To get around this the [`stateful`] function can
 //! be used to create views that have ownership over some arbitrary mutable state.
 //!
-use std::cell::UnsafeCell;
-use std::mem::MaybeUninit;
+use std::cell::{Cell, OnceCell, UnsafeCell};
+use std::mem::{ManuallyDrop, MaybeUninit};
 use std::rc::Rc;
 
+use wasm_bindgen::closure::Closure;
 use wasm_bindgen::JsValue;
-use web_sys::Node;
+use web_sys::{DomRect, Node};
 
 use crate::dom::Anchor;
-use crate::internal::{In, Out};
+use crate::internal::{self, In, Out};
 use crate::{init, Mountable, View};
 
 mod cell;
+mod cleanup;
+mod countdown;
+mod error;
+mod handle;
 mod hook;
+mod input;
 mod into_state;
+mod latest;
+mod media;
 mod product;
+mod reducer;
+mod serial;
 mod should_render;
+mod timer;
+mod ws;
 
 use cell::WithCell;
+use cleanup::Cleanups;
+use latest::Latest;
 use product::{Product, ProductHandler};
-
-pub use hook::{Bound, Hook, Signal};
+use serial::Tasks;
+use timer::Timers;
+
+pub use cleanup::Cleanup;
+pub use countdown::{countdown, Countdown};
+pub use error::{ErrorSlot, Retryable, RetryableProduct, WithErrorSlot};
+pub use handle::Handle;
+pub use hook::{Bound, BoundIndexed, Hook, Signal};
+pub use input::{Commit, InputListener};
 pub use into_state::IntoState;
-pub use should_render::{ShouldRender, Then};
+pub use media::MediaQuery;
+pub use reducer::Reducer;
+pub use serial::Serial;
+pub use should_render::{RenderOutcome, ShouldRender, Then};
+
+/// Derive [`ShouldRender`] for an enum, so that callbacks can return domain
+/// outcomes directly. Variants marked with `#[no_render]` update the state
+/// silently, all other variants render, optionally marked with `#[render]`.
+///
+/// ```
+/// use kobold::prelude::*;
+/// use kobold::stateful::ShouldRender;
+///
+/// #[derive(ShouldRender)]
+/// enum Edit {
+///     #[render]
+///     Changed(usize),
+///     #[no_render]
+///     Unchanged,
+///     #[no_render]
+///     Rejected { reason: &'static str },
+///     Reset,
+/// }
+///
+/// fn edit(text: &mut String, value: String) -> Edit {
+///     if value.len() > 280 {
+///         Edit::Rejected { reason: "Too long" }
+///     } else if value.is_empty() {
+///         text.clear();
+///         Edit::Reset
+///     } else if *text == value {
+///         Edit::Unchanged
+///     } else {
+///         *text = value;
+///         Edit::Changed(text.len())
+///     }
+/// }
+///
+/// let mut text = String::new();
+///
+/// assert!(edit(&mut text, "Hello".into()).should_render());
+/// assert!(!edit(&mut text, "Hello".into()).should_render());
+/// assert!(!edit(&mut text, "!".repeat(300)).should_render());
+/// assert!(edit(&mut text, String::new()).should_render());
+///
+/// // Works anywhere a `ShouldRender` is expected
+/// fn editor(signal: Signal<String>, value: String) {
+///     signal.update(move |text| edit(text, value));
+/// }
+/// ```
+pub use kobold_macros::ShouldRender;
+pub use timer::Timer;
 
 #[repr(C)]
 struct Inner<S, P: ?Sized = dyn Product<S>> {
     state: WithCell<S>,
+    extras: OnceCell<Box<Extras>>,
+    rendered: Cell<bool>,
+    forced: Cell<bool>,
+    /// A deferred render is pending in a microtask
+    deferred: Cell<bool>,
+    schedule: fn(&Inner<S>),
     prod: UnsafeCell<P>,
 }
 
+/// Bookkeeping of the less common [`Hook`] and [`Signal`] features, allocated
+/// the first time one of them is used by a stateful view.
+struct Extras {
+    tasks: Tasks,
+    latest: Latest,
+    timers: Timers,
+    cleanups: Cleanups,
+}
+
+impl Extras {
+    const fn new() -> Self {
+        Extras {
+            tasks: Tasks::new(),
+            latest: Latest::new(),
+            timers: Timers::new(),
+            cleanups: Cleanups::new(),
+        }
+    }
+}
+
 pub struct Stateful<S, F> {
     state: S,
     render: F,
@@ -92,8 +190,22 @@ impl<S, P> Inner<S, MaybeUninit<P>> {
     }
 }
 
+impl<S, P: ?Sized> Inner<S, P> {
+    fn extras(&self) -> &Extras {
+        self.extras.get_or_init(|| Box::new(Extras::new()))
+    }
+}
+
 impl<S> Inner<S> {
     fn update(&self) {
+        // Rendering now covers any pending deferred render
+        self.deferred.set(false);
+
+        // Run before `prod` is borrowed, cleanups are free to update the state
+        if let Some(extras) = self.extras.get() {
+            extras.cleanups.before_render();
+        }
+
         // ⚠️ Safety:
         // ==========
         //
@@ -109,6 +221,56 @@ impl<S> Inner<S> {
     }
 }
 
+impl<S> Inner<S> {
+    /// Render the view according to the outcome of an update.
+    fn render<O: ShouldRender>(&self, render: O) -> RenderOutcome {
+        if render.deferred() {
+            self.defer();
+            RenderOutcome::Deferred
+        } else if render.should_render() {
+            self.update();
+            RenderOutcome::Rendered
+        } else {
+            RenderOutcome::Skipped
+        }
+    }
+
+    /// Schedule a render in a microtask, unless one is already pending.
+    fn defer(&self) {
+        if !self.deferred.replace(true) {
+            (self.schedule)(self);
+        }
+    }
+
+    /// Run the pending deferred render, if it wasn't already covered by another render.
+    fn flush(&self) {
+        if self.deferred.get() {
+            self.update();
+        }
+    }
+}
+
+impl<S: 'static> Inner<S> {
+    /// Queue a microtask flushing the deferred render. This is stored as a function
+    /// pointer in `schedule` when the stateful view is built, so that deferring
+    /// a render from `Signal::update` doesn't require `S: 'static`.
+    fn queue_flush(&self) {
+        // ⚠️ Safety:
+        // ==========
+        //
+        // Same as in `Hook::bind_async`, `Inner` always lives in an `Rc` and
+        // the temporary `Rc` is only used to construct a `Weak` reference.
+        let rc = ManuallyDrop::new(unsafe { Rc::from_raw(self as *const Inner<S>) });
+        let weak = Rc::downgrade(&*rc);
+
+        internal::queue_microtask(&Closure::once_into_js(move || {
+            if let Some(inner) = weak.upgrade() {
+                inner.flush();
+            }
+        }));
+    }
+}
+
 impl<S, F, V> View for Stateful<S, F>
 where
     S: IntoState,
@@ -118,50 +280,153 @@ where
     type Product = StatefulProduct<S::State>;
 
     fn build(self, p: In<Self::Product>) -> Out<Self::Product> {
-        let inner = Rc::new(Inner {
-            state: WithCell::new(self.state.init()),
-            prod: UnsafeCell::new(MaybeUninit::uninit()),
-        });
+        let render = self.render;
 
-        // ⚠️ Safety:
-        // ==========
-        //
-        // Initial render can only access the `state` from the hook, the `prod` is
-        // not touched until an event is fired, which happens after this method
-        // completes and initializes the `prod`.
-        let view = (self.render)(Hook::new(unsafe { inner.as_init() }));
+        build_stateful(self.state.init(), p, move |hook| {
+            let view = render(hook);
 
-        // ⚠️ Safety:
-        // ==========
-        //
-        // This looks scary, but it just initializes the `prod`. We need to use the
-        // closure syntax with a raw pointer to get around lifetime restrictions.
-        unsafe {
-            In::raw((*inner.prod.get()).as_mut_ptr(), |prod| {
-                ProductHandler::build(
-                    move |hook, product: *mut V::Product| (self.render)(hook).update(&mut *product),
-                    view,
-                    prod,
-                )
-            });
-        }
+            let updater =
+                move |hook, product: *mut V::Product| unsafe { render(hook).update(&mut *product) };
 
-        // ⚠️ Safety:
-        // ==========
-        //
-        // At this point `Inner` is fully initialized.
-        p.put(StatefulProduct {
-            inner: unsafe { inner.into_init() },
+            (view, updater)
         })
     }
 
     fn update(self, p: &mut Self::Product) {
-        p.inner.state.with(|state| {
-            if self.state.update(state).should_render() {
-                p.inner.update();
-            }
+        update_stateful(self.state, p)
+    }
+}
+
+/// Same as [`stateful`], but the state is cloned after every render and compared to
+/// its current value before the next one. When the state hasn't changed the render
+/// is skipped, even if the update that mutated it asked for one.
+///
+/// This removes wasted renders from idempotent updates, at the cost of keeping an
+/// extra copy of the state around and comparing it on every update. Renders requested
+/// with [`Signal::force_render`] skip the comparison.
+///
+/// ```
+/// # use kobold::prelude::*;
+/// # use kobold::stateful::stateful_eq;
+/// let view = stateful_eq(|| String::from("Kobold"), |name: &Hook<String>| {
+///     bind! { name:
+///         // Renders only the first time the button is clicked
+///         let onclick = move |_: MouseEvent<_>| *name = "Clicked".into();
+///     }
+///
+///     view! { <button {onclick}>{ ref name }</button> }
+/// });
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn stateful_eq<'a, S, F, V>(
+    state: S,
+    render: F,
+) -> Stateful<S, SkipEqual<impl Fn(*const Hook<S::State>) -> V + 'static>>
+where
+    S: IntoState,
+    S::State: Clone + PartialEq,
+    F: Fn(&'a Hook<S::State>) -> V + 'static,
+    V: View + 'a,
+{
+    let render = move |hook: *const Hook<S::State>| render(unsafe { &*hook });
+    Stateful {
+        state,
+        render: SkipEqual(render),
+    }
+}
+
+/// Render function of a [`Stateful`] view created with [`stateful_eq`].
+pub struct SkipEqual<F>(F);
+
+impl<S, F, V> View for Stateful<S, SkipEqual<F>>
+where
+    S: IntoState,
+    S::State: Clone + PartialEq,
+    F: Fn(*const Hook<S::State>) -> V + 'static,
+    V: View,
+{
+    type Product = StatefulProduct<S::State>;
+
+    fn build(self, p: In<Self::Product>) -> Out<Self::Product> {
+        let SkipEqual(render) = self.render;
+
+        build_stateful(self.state.init(), p, move |hook| {
+            let mut rendered = S::State::clone(hook);
+            let view = render(hook);
+
+            let updater = move |hook: *const Hook<S::State>, product: *mut V::Product| unsafe {
+                let state: &S::State = &*hook;
+
+                if *state != rendered || (*hook).is_forced() {
+                    rendered.clone_from(state);
+                    render(hook).update(&mut *product);
+                }
+            };
+
+            (view, updater)
         })
     }
+
+    fn update(self, p: &mut Self::Product) {
+        update_stateful(self.state, p)
+    }
+}
+
+fn build_stateful<S, R, V, U>(
+    state: S,
+    p: In<StatefulProduct<S>>,
+    render: R,
+) -> Out<StatefulProduct<S>>
+where
+    S: 'static,
+    R: FnOnce(&Hook<S>) -> (V, U),
+    V: View,
+    U: FnMut(*const Hook<S>, *mut V::Product) + 'static,
+{
+    let inner = Rc::new(Inner {
+        state: WithCell::new(state),
+        extras: OnceCell::new(),
+        rendered: Cell::new(false),
+        forced: Cell::new(false),
+        deferred: Cell::new(false),
+        schedule: Inner::queue_flush,
+        prod: UnsafeCell::new(MaybeUninit::uninit()),
+    });
+
+    // ⚠️ Safety:
+    // ==========
+    //
+    // Initial render can only access the `state` from the hook, the `prod` is
+    // not touched until an event is fired, which happens after this method
+    // completes and initializes the `prod`.
+    let (view, updater) = render(Hook::new(unsafe { inner.as_init() }));
+
+    inner.rendered.set(true);
+
+    // ⚠️ Safety:
+    // ==========
+    //
+    // This looks scary, but it just initializes the `prod`. We need to use the
+    // closure syntax with a raw pointer to get around lifetime restrictions.
+    unsafe {
+        In::raw((*inner.prod.get()).as_mut_ptr(), |prod| {
+            ProductHandler::build(updater, view, prod)
+        });
+    }
+
+    // ⚠️ Safety:
+    // ==========
+    //
+    // At this point `Inner` is fully initialized.
+    p.put(StatefulProduct {
+        inner: unsafe { inner.into_init() },
+    })
+}
+
+fn update_stateful<S: IntoState>(state: S, p: &mut StatefulProduct<S::State>) {
+    p.inner.state.with(|current| {
+        p.inner.render(state.update(current));
+    })
 }
 
 impl<S> Mountable for StatefulProduct<S>
@@ -181,6 +446,10 @@ where
     fn replace_with(&self, new: &JsValue) {
         unsafe { (*self.inner.prod.get()).replace_with(new) }
     }
+
+    fn rect(&self) -> Option<DomRect> {
+        unsafe { (*self.inner.prod.get()).rect() }
+    }
 }
 
 impl<S, R> Stateful<S, R>
@@ -23,7 +23,7 @@ impl<T> WithCell<T> {
         O: 'static,
     {
         if self.borrowed.get() {
-            wasm_bindgen::throw_str("Cyclic state borrowing");
+            cyclic_borrow();
         }
 
         self.borrowed.set(true);
@@ -37,11 +37,35 @@ impl<T> WithCell<T> {
 
         &*self.data.get()
     }
+}
 
-    #[allow(clippy::mut_from_ref)]
-    pub unsafe fn mut_unchecked(&self) -> &mut T {
-        debug_assert!(!self.borrowed.get());
+#[cold]
+fn cyclic_borrow() -> ! {
+    if cfg!(debug_assertions) {
+        panic!(
+            "Cyclic state borrowing: state was updated (e.g. with `Signal::update`) \
+            while it was already mutably borrowed, such as from inside a callback created \
+            with `Hook::bind`. Mutate the state through the callback's `&mut` reference instead."
+        );
+    }
+
+    wasm_bindgen::throw_str("Cyclic state borrowing");
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "Cyclic state borrowing")]
+    fn reentrant_update_panics() {
+        let cell = WithCell::new(0);
+
+        cell.with(|outer| {
+            *outer += 1;
 
-        &mut *self.data.get()
+            // This is what a `Signal::update` call inside a bound callback does
+            cell.with(|inner| *inner += 1);
+        });
     }
 }
@@ -0,0 +1,99 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::cell::RefCell;
+
+/// When a cleanup registered with [`Hook::on_cleanup`](crate::stateful::Hook::on_cleanup)
+/// runs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cleanup {
+    /// Run when the stateful view is dropped.
+    Drop,
+    /// Run before the next render of the stateful view, or when it's dropped if it
+    /// never renders again.
+    Render,
+}
+
+type Callback = Box<dyn FnOnce()>;
+
+/// Cleanups registered for a single stateful view, all remaining ones run
+/// when the view is dropped.
+pub(super) struct Cleanups {
+    registered: RefCell<Vec<(Cleanup, Callback)>>,
+}
+
+impl Cleanups {
+    pub const fn new() -> Self {
+        Cleanups {
+            registered: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub fn push(&self, when: Cleanup, callback: Callback) {
+        self.registered.borrow_mut().push((when, callback));
+    }
+
+    /// Run all [`Cleanup::Render`] callbacks, last registered first.
+    pub fn before_render(&self) {
+        let due: Vec<_> = {
+            let mut registered = self.registered.borrow_mut();
+            let (due, keep) = std::mem::take(&mut *registered)
+                .into_iter()
+                .partition(|(when, _)| *when == Cleanup::Render);
+
+            *registered = keep;
+            due
+        };
+
+        // Callbacks run without `registered` borrowed
+        for (_, callback) in due.into_iter().rev() {
+            callback();
+        }
+    }
+}
+
+impl Drop for Cleanups {
+    fn drop(&mut self) {
+        for (_, callback) in self.registered.get_mut().drain(..).rev() {
+            callback();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use super::*;
+
+    fn log(log: &Rc<RefCell<Vec<&'static str>>>, entry: &'static str) -> Callback {
+        let log = log.clone();
+
+        Box::new(move || log.borrow_mut().push(entry))
+    }
+
+    #[test]
+    fn cleanups_run_in_reverse_order() {
+        let ran = Rc::new(RefCell::new(Vec::new()));
+        let cleanups = Cleanups::new();
+
+        cleanups.push(Cleanup::Drop, log(&ran, "drop 1"));
+        cleanups.push(Cleanup::Render, log(&ran, "render 1"));
+        cleanups.push(Cleanup::Drop, log(&ran, "drop 2"));
+        cleanups.push(Cleanup::Render, log(&ran, "render 2"));
+
+        cleanups.before_render();
+
+        assert_eq!(*ran.borrow(), ["render 2", "render 1"]);
+
+        cleanups.push(Cleanup::Render, log(&ran, "render 3"));
+
+        drop(cleanups);
+
+        assert_eq!(
+            *ran.borrow(),
+            ["render 2", "render 1", "render 3", "drop 2", "drop 1"]
+        );
+    }
+}
@@ -0,0 +1,128 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::time::Duration;
+
+use wasm_bindgen_futures::spawn_local;
+
+use crate::internal::{In, Out};
+use crate::stateful::{stateful, Hook, OnceProduct, Signal};
+use crate::View;
+
+/// Self-updating [`View`] rendering the time remaining until zero, created with
+/// [`countdown`].
+pub struct Countdown<F> {
+    seconds: u64,
+    on_done: F,
+}
+
+/// Render the `remaining` time as `m:ss`, or `h:mm:ss` for an hour or more, ticking
+/// down every second until it reaches zero:
+///
+/// ```
+/// # use kobold::prelude::*;
+/// # use kobold::stateful::countdown;
+/// # use std::time::Duration;
+/// # fn end_sale() {}
+/// #[component]
+/// fn sale() -> impl View {
+///     view! {
+///         <p>"Sale ends in "{ countdown(Duration::from_secs(90)).on_done(end_sale) }</p>
+///     }
+/// }
+/// # fn main() {}
+/// ```
+///
+/// The remaining time is rounded down to whole seconds. The countdown starts when it's
+/// first rendered and isn't changed by later updates from the parent view, to start
+/// a new one render it in a new place, such as a different [`keyed`](crate::list::keyed)
+/// list item. Its timer is cleared when it's dropped or reaches zero.
+pub fn countdown(remaining: Duration) -> Countdown<fn()> {
+    Countdown {
+        seconds: remaining.as_secs(),
+        on_done: || {},
+    }
+}
+
+impl<F> Countdown<F> {
+    /// Call `on_done` once the countdown reaches zero. It's called after the countdown
+    /// has rendered zero, so it's free to update the state of any view.
+    pub fn on_done<D>(self, on_done: D) -> Countdown<D>
+    where
+        D: FnOnce() + 'static,
+    {
+        Countdown {
+            seconds: self.seconds,
+            on_done,
+        }
+    }
+}
+
+impl<F> View for Countdown<F>
+where
+    F: FnOnce() + 'static,
+{
+    type Product = OnceProduct<u64, ()>;
+
+    fn build(self, p: In<Self::Product>) -> Out<Self::Product> {
+        let Countdown { seconds, on_done } = self;
+
+        stateful(
+            move || seconds,
+            |seconds: &Hook<u64>| format_remaining(**seconds),
+        )
+        .once(move |signal| {
+            if seconds == 0 {
+                spawn_local(async move { on_done() });
+            } else {
+                tick(signal, on_done);
+            }
+        })
+        .build(p)
+    }
+
+    fn update(self, _: &mut Self::Product) {}
+}
+
+fn tick<F>(signal: Signal<u64>, on_done: F)
+where
+    F: FnOnce() + 'static,
+{
+    let next = signal.clone();
+
+    signal.set_timeout(Duration::from_secs(1), move |seconds| {
+        *seconds = seconds.saturating_sub(1);
+
+        if *seconds == 0 {
+            // The state is borrowed here, `on_done` runs once this update is done
+            spawn_local(async move { on_done() });
+        } else {
+            tick(next, on_done);
+        }
+    });
+}
+
+fn format_remaining(seconds: u64) -> String {
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn remaining_time_format() {
+        assert_eq!(format_remaining(0), "0:00");
+        assert_eq!(format_remaining(59), "0:59");
+        assert_eq!(format_remaining(90), "1:30");
+        assert_eq!(format_remaining(3600), "1:00:00");
+        assert_eq!(format_remaining(36125), "10:02:05");
+    }
+}
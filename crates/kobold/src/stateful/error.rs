@@ -0,0 +1,432 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::future::Future;
+use std::rc::Rc;
+
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use wasm_bindgen_futures::spawn_local;
+use web_sys::Node;
+
+use crate::branching::{Branch2, EmptyNode};
+use crate::dom::{Anchor, Property, TextContent};
+use crate::event::{EventCast, Listener};
+use crate::internal::{self, In, Out};
+use crate::stateful::{Hook, Signal};
+use crate::View;
+
+/// Slot for the last error reported by async code, kept in the state of a stateful
+/// view so that it can render an error banner.
+///
+/// Errors are reported with [`Signal::report_error`] or returned from callbacks bound
+/// with [`Hook::bind_async_fallible`]:
+///
+/// ```
+/// # use kobold::prelude::*;
+/// use kobold::stateful::{ErrorSlot, WithErrorSlot};
+///
+/// # async fn fetch_items() -> Result<Vec<String>, String> { Ok(Vec::new()) }
+/// #[derive(Default)]
+/// struct Items {
+///     list: Vec<String>,
+///     error: ErrorSlot<String>,
+/// }
+///
+/// impl WithErrorSlot<String> for Items {
+///     fn error_slot(&mut self) -> &mut ErrorSlot<String> {
+///         &mut self.error
+///     }
+/// }
+///
+/// let items = stateful(Items::default, |items| {
+///     let onclick = items.bind_async_fallible(|items, _: MouseEvent<_>| async move {
+///         let list = fetch_items().await?;
+///
+///         items.update(|items| {
+///             items.error.clear();
+///             items.list = list;
+///         });
+///         Ok(())
+///     });
+///
+///     let dismiss = items.bind(|items, _: MouseEvent<_>| {
+///         items.error.clear();
+///     });
+///
+///     view! {
+///         { items.error.view(|error| view! {
+///             <p.error>{ ref error }<button onclick={dismiss}>"×"</button></p>
+///         }) }
+///         <button {onclick}>"Refresh"</button>
+///         <ul>{ for items.list.iter().map(|item| view! { <li>{ ref item }</li> }) }</ul>
+///     }
+/// });
+/// ```
+///
+/// Only the last error is kept, reporting a new one replaces it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ErrorSlot<E> {
+    error: Option<E>,
+}
+
+impl<E> ErrorSlot<E> {
+    /// Create an empty slot.
+    pub const fn new() -> Self {
+        ErrorSlot { error: None }
+    }
+
+    /// Store an `error`, replacing any previous one.
+    pub fn set(&mut self, error: E) {
+        self.error = Some(error);
+    }
+
+    /// Remove the error, returning it if there was one.
+    pub fn clear(&mut self) -> Option<E> {
+        self.error.take()
+    }
+
+    /// Returns the current error, if any.
+    pub fn get(&self) -> Option<&E> {
+        self.error.as_ref()
+    }
+
+    /// Returns `true` if there is an error in the slot.
+    pub fn is_set(&self) -> bool {
+        self.error.is_some()
+    }
+
+    /// Render the error with `render`, or nothing if the slot is empty.
+    pub fn view<'a, F, V>(&'a self, render: F) -> Option<V>
+    where
+        F: FnOnce(&'a E) -> V,
+        V: View,
+    {
+        self.error.as_ref().map(render)
+    }
+}
+
+impl<E: Display> ErrorSlot<E> {
+    /// Render the error, if any, followed by a button calling `retry` to run the failed
+    /// operation again. The error is cleared when the retry starts, and the error
+    /// returned by the future, if any, is [reported](Signal::report_error) back to this
+    /// slot:
+    ///
+    /// ```
+    /// # use kobold::prelude::*;
+    /// # use kobold::stateful::{ErrorSlot, WithErrorSlot};
+    /// # use wasm_bindgen_futures::spawn_local;
+    /// # async fn fetch_items() -> Result<Vec<String>, String> { Ok(Vec::new()) }
+    /// # #[derive(Default)]
+    /// # struct Items { list: Vec<String>, error: ErrorSlot<String> }
+    /// # impl WithErrorSlot<String> for Items {
+    /// #     fn error_slot(&mut self) -> &mut ErrorSlot<String> { &mut self.error }
+    /// # }
+    /// async fn load(items: Signal<Items>) -> Result<(), String> {
+    ///     let list = fetch_items().await?;
+    ///
+    ///     items.update(|items| items.list = list);
+    ///     Ok(())
+    /// }
+    ///
+    /// let items = stateful(Items::default, |items| {
+    ///     view! {
+    ///         { items.error.retry(items, load).message("Failed to load items") }
+    ///         <ul>{ for items.list.iter().map(|item| view! { <li>{ ref item }</li> }) }</ul>
+    ///     }
+    /// })
+    /// .once(|items| spawn_local(async move {
+    ///     if let Err(error) = load(items.clone()).await {
+    ///         items.report_error(error);
+    ///     }
+    /// }));
+    /// ```
+    ///
+    /// This renders as `<div role="alert">Failed to load items: {error}<button>Retry</button></div>`,
+    /// the label of the button can be changed with [`label`](Retryable::label).
+    pub fn retry<'a, S, F, T>(&'a self, hook: &Hook<S>, retry: F) -> Retryable<'a, S, E, F>
+    where
+        S: WithErrorSlot<E> + 'static,
+        E: 'static,
+        F: Fn(Signal<S>) -> T + 'static,
+        T: Future<Output = Result<(), E>> + 'static,
+    {
+        Retryable {
+            error: self.error.as_ref(),
+            signal: hook.weak_signal(),
+            retry,
+            message: "",
+            label: "Retry",
+        }
+    }
+}
+
+impl<E> Default for ErrorSlot<E> {
+    fn default() -> Self {
+        ErrorSlot::new()
+    }
+}
+
+/// State holding an [`ErrorSlot`], allowing errors to be reported to it with
+/// [`Signal::report_error`].
+pub trait WithErrorSlot<E> {
+    /// Returns the slot errors are reported to.
+    fn error_slot(&mut self) -> &mut ErrorSlot<E>;
+}
+
+impl<E> WithErrorSlot<E> for ErrorSlot<E> {
+    fn error_slot(&mut self) -> &mut ErrorSlot<E> {
+        self
+    }
+}
+
+impl<S> Signal<S> {
+    /// Store the `error` in the [`ErrorSlot`] of the state and render the view.
+    /// Does nothing if the view has been dropped.
+    pub fn report_error<E>(&self, error: E)
+    where
+        S: WithErrorSlot<E>,
+    {
+        self.update(move |state| state.error_slot().set(error));
+    }
+}
+
+impl<S> Hook<S> {
+    /// Same as [`bind_async`](Hook::bind_async), but the future returns a `Result`, and
+    /// any error it returns is [reported](Signal::report_error) to the
+    /// [`ErrorSlot`] of the state.
+    pub fn bind_async_fallible<E, F, T, Err>(&self, callback: F) -> impl Listener<E>
+    where
+        S: WithErrorSlot<Err> + 'static,
+        E: EventCast,
+        F: Fn(Signal<S>, E) -> T + 'static,
+        T: Future<Output = Result<(), Err>> + 'static,
+        Err: 'static,
+    {
+        self.bind_async(move |signal: Signal<S>, e| {
+            let task = callback(signal.clone(), e);
+
+            async move {
+                if let Err(error) = task.await {
+                    signal.report_error(error);
+                }
+            }
+        })
+    }
+}
+
+/// View rendering an error with a retry button, created with [`ErrorSlot::retry`].
+pub struct Retryable<'a, S, E, F> {
+    error: Option<&'a E>,
+    signal: Signal<S>,
+    retry: F,
+    message: &'a str,
+    label: &'a str,
+}
+
+impl<'a, S, E, F> Retryable<'a, S, E, F> {
+    /// Text rendered before the error, separated from it by a colon.
+    pub fn message(self, message: &'a str) -> Self {
+        Retryable { message, ..self }
+    }
+
+    /// Label of the retry button, `"Retry"` by default.
+    pub fn label(self, label: &'a str) -> Self {
+        Retryable { label, ..self }
+    }
+}
+
+struct Retry<S, F> {
+    signal: Signal<S>,
+    retry: F,
+}
+
+pub struct RetryableProduct<S, F> {
+    text: String,
+    label: String,
+    el: Node,
+    text_node: Node,
+    button: Node,
+    shared: Rc<RefCell<Retry<S, F>>>,
+    _onclick: Closure<dyn FnMut()>,
+}
+
+fn error_text(message: &str, error: &impl Display) -> String {
+    if message.is_empty() {
+        error.to_string()
+    } else {
+        format!("{message}: {error}")
+    }
+}
+
+/// Non-optional part of [`Retryable`], rendered only when there is an error.
+struct RetryView<'a, S, E, F> {
+    error: &'a E,
+    signal: Signal<S>,
+    retry: F,
+    message: &'a str,
+    label: &'a str,
+}
+
+impl<'a, S, E, F> Retryable<'a, S, E, F> {
+    fn into_view(self) -> Option<RetryView<'a, S, E, F>> {
+        Some(RetryView {
+            error: self.error?,
+            signal: self.signal,
+            retry: self.retry,
+            message: self.message,
+            label: self.label,
+        })
+    }
+}
+
+impl<S, E, F, T> View for Retryable<'_, S, E, F>
+where
+    S: WithErrorSlot<E> + 'static,
+    E: Display + 'static,
+    F: Fn(Signal<S>) -> T + 'static,
+    T: Future<Output = Result<(), E>> + 'static,
+{
+    type Product = Branch2<RetryableProduct<S, F>, EmptyNode>;
+
+    fn build(self, p: In<Self::Product>) -> Out<Self::Product> {
+        self.into_view().build(p)
+    }
+
+    fn update(self, p: &mut Self::Product) {
+        self.into_view().update(p)
+    }
+}
+
+impl<S, E, F, T> View for RetryView<'_, S, E, F>
+where
+    S: WithErrorSlot<E> + 'static,
+    E: Display + 'static,
+    F: Fn(Signal<S>) -> T + 'static,
+    T: Future<Output = Result<(), E>> + 'static,
+{
+    type Product = RetryableProduct<S, F>;
+
+    fn build(self, p: In<Self::Product>) -> Out<Self::Product> {
+        let text = error_text(self.message, self.error);
+
+        let el = internal::create_element("div");
+        let text_node = internal::text_node(&text);
+        let button = internal::create_element("button");
+
+        internal::obj(&el).set_attr("role", "alert");
+        TextContent.set(&button, self.label);
+
+        let append = |parent: &Node, child: &Node| {
+            parent
+                .append_child(child)
+                .expect_throw("failed to append node");
+        };
+
+        append(&el, &text_node);
+        append(&el, &button);
+
+        let shared = Rc::new(RefCell::new(Retry {
+            signal: self.signal,
+            retry: self.retry,
+        }));
+
+        let onclick = {
+            let shared = shared.clone();
+
+            Closure::<dyn FnMut()>::new(move || {
+                let shared = shared.clone();
+
+                // Clearing the error drops this listener, so it has to happen
+                // after the event handler has returned
+                spawn_local(async move {
+                    let (signal, task) = {
+                        let shared = shared.borrow();
+
+                        (shared.signal.clone(), (shared.retry)(shared.signal.clone()))
+                    };
+
+                    signal.update(|state| {
+                        state.error_slot().clear();
+                    });
+
+                    if let Err(error) = task.await {
+                        signal.report_error(error);
+                    }
+                });
+            })
+        };
+
+        button
+            .add_event_listener_with_callback("click", onclick.as_ref().unchecked_ref())
+            .expect_throw("failed to add click listener");
+
+        p.put(RetryableProduct {
+            text,
+            label: self.label.into(),
+            el,
+            text_node,
+            button,
+            shared,
+            _onclick: onclick,
+        })
+    }
+
+    fn update(self, p: &mut Self::Product) {
+        let text = error_text(self.message, self.error);
+
+        if p.text != text {
+            TextContent.set(&p.text_node, text.as_str());
+            p.text = text;
+        }
+
+        if p.label != self.label {
+            self.label.clone_into(&mut p.label);
+            TextContent.set(&p.button, self.label);
+        }
+
+        *p.shared.borrow_mut() = Retry {
+            signal: self.signal,
+            retry: self.retry,
+        };
+    }
+}
+
+impl<S: 'static, F: 'static> Anchor for RetryableProduct<S, F> {
+    type Js = Node;
+    type Target = Node;
+
+    fn anchor(&self) -> &Node {
+        &self.el
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn retry_text() {
+        assert_eq!(error_text("", &"timed out"), "timed out");
+        assert_eq!(error_text("Failed to load", &404), "Failed to load: 404");
+    }
+
+    #[test]
+    fn set_and_clear() {
+        let mut slot = ErrorSlot::new();
+
+        assert!(!slot.is_set());
+        assert!(slot.view(|error: &&str| *error).is_none());
+
+        slot.set("first");
+        slot.error_slot().set("second");
+
+        assert_eq!(slot.get(), Some(&"second"));
+        assert_eq!(slot.view(|error| *error), Some("second"));
+        assert_eq!(slot.clear(), Some("second"));
+        assert_eq!(slot.clear(), None);
+    }
+}
@@ -0,0 +1,104 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Shared slot through which a child component can expose an imperative handle
+/// to its parent, for things like focusing, scrolling or resetting that are awkward
+/// to model with props alone.
+///
+/// The parent creates the `Handle` and passes a clone of it to the child as a prop,
+/// the child then [`register`](Handle::register)s a type with methods the parent can
+/// call, typically wrapping a [`Signal`](crate::stateful::Signal) to its own state:
+///
+/// ```no_run
+/// use kobold::prelude::*;
+/// use kobold::stateful::Handle;
+///
+/// pub struct Counter {
+///     count: Signal<u32>,
+/// }
+///
+/// impl Counter {
+///     pub fn reset(&self) {
+///         self.count.set(0);
+///     }
+/// }
+///
+/// #[component]
+/// fn counter(handle: Handle<Counter>) -> impl View {
+///     stateful(0_u32, |count| {
+///         bind! { count:
+///             let onclick = move |_| *count += 1;
+///         }
+///
+///         view! { <button {onclick}>"Clicked "{ count }" times"</button> }
+///     })
+///     .once(move |count| handle.register(Counter { count }))
+/// }
+///
+/// fn main() {
+///     let handle = Handle::new();
+///     let reset = {
+///         let handle = handle.clone();
+///
+///         move |_| {
+///             handle.with(Counter::reset);
+///         }
+///     };
+///
+///     kobold::start(view! {
+///         <!counter handle={handle}>
+///         <button onclick={reset}>"Reset"</button>
+///     });
+/// }
+/// ```
+///
+/// The handle is not cleared when the child is unmounted. Methods going through a
+/// `Signal` simply do nothing once the state they point to has been dropped, other
+/// handles can be removed with [`clear`](Handle::clear).
+pub struct Handle<T>(Rc<RefCell<Option<T>>>);
+
+impl<T> Handle<T> {
+    /// Create an empty handle.
+    pub fn new() -> Self {
+        Handle(Rc::new(RefCell::new(None)))
+    }
+
+    /// Register the handle, replacing any previously registered one.
+    pub fn register(&self, handle: T) {
+        *self.0.borrow_mut() = Some(handle);
+    }
+
+    /// Remove the registered handle.
+    pub fn clear(&self) {
+        self.0.borrow_mut().take();
+    }
+
+    /// Returns `true` if a handle has been registered.
+    pub fn is_registered(&self) -> bool {
+        self.0.borrow().is_some()
+    }
+
+    /// Call `f` with the registered handle, returns `None` if there isn't one.
+    pub fn with<F, R>(&self, f: F) -> Option<R>
+    where
+        F: FnOnce(&T) -> R,
+    {
+        self.0.borrow().as_ref().map(f)
+    }
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Handle(self.0.clone())
+    }
+}
+
+impl<T> Default for Handle<T> {
+    fn default() -> Self {
+        Handle::new()
+    }
+}
@@ -7,12 +7,16 @@ use std::marker::PhantomData;
 use std::mem::ManuallyDrop;
 use std::ops::Deref;
 use std::rc::{Rc, Weak};
+use std::time::Duration;
 
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsValue;
 use wasm_bindgen_futures::spawn_local;
 
-use crate::event::{EventCast, Listener};
-use crate::internal::{In, Out};
-use crate::stateful::{Inner, ShouldRender};
+use crate::event::{EventCast, Listener, ListenerHandle};
+use crate::internal::{self, In, Out};
+use crate::stateful::timer::{self, Timer};
+use crate::stateful::{Cleanup, Inner, RenderOutcome, Serial, ShouldRender};
 use crate::View;
 
 /// A hook into some state `S`. A reference to `Hook` is obtained by using the [`stateful`](crate::stateful::stateful)
@@ -25,6 +29,49 @@ pub struct Hook<S> {
     inner: Inner<S>,
 }
 
+/// A weak handle to some state `S` that can be moved into closures and async code to
+/// update the state, obtained with [`Hook::weak_signal`] or passed to callbacks by
+/// [`Hook::bind_async`] and [`once`](crate::stateful::Stateful::once).
+///
+/// A `Signal` is `'static` and cheap to clone, so it can also be passed to child
+/// components as a prop, letting them update state they don't own without borrowing
+/// the parent [`Hook`]:
+///
+/// ```
+/// # use kobold::prelude::*;
+/// # async fn fetch_count() -> u32 { 42 }
+/// #[component]
+/// fn reload(count: Signal<u32>) -> impl View {
+///     stateful(false, move |loading| {
+///         let count = count.clone();
+///         let onclick = loading.bind_async(move |loading, _: MouseEvent<_>| {
+///             let count = count.clone();
+///
+///             async move {
+///                 loading.set(true);
+///                 count.set(fetch_count().await);
+///                 loading.set(false);
+///             }
+///         });
+///
+///         view! { <button {onclick} disabled={**loading}>"Reload"</button> }
+///     })
+/// }
+///
+/// #[component]
+/// fn counter() -> impl View {
+///     stateful(0_u32, |count| {
+///         view! {
+///             <p>"Count: "{ count.get() }</p>
+///             <!reload count={count.weak_signal()}>
+///         }
+///     })
+/// }
+/// # fn main() {}
+/// ```
+///
+/// Once the stateful view owning the state is dropped, all updates through its
+/// signals are ignored.
 #[repr(transparent)]
 pub struct Signal<S> {
     pub(super) weak: Weak<Inner<S>>,
@@ -55,11 +102,42 @@ impl<S> Signal<S> {
         F: FnOnce(&mut S) -> O,
         O: ShouldRender,
     {
-        if let Some(inner) = self.weak.upgrade() {
-            if inner.state.with(mutator).should_render() {
-                inner.update()
-            }
-        }
+        self.try_update(mutator);
+    }
+
+    /// Same as [`update`](Signal::update), but returns whether the update was applied and
+    /// rendered. Useful for async code that should stop once the view is gone:
+    ///
+    /// ```
+    /// # use kobold::prelude::*;
+    /// # use kobold::stateful::RenderOutcome;
+    /// # async fn next_message() -> String { String::new() }
+    /// async fn poll(log: Signal<Vec<String>>) {
+    ///     loop {
+    ///         let message = next_message().await;
+    ///
+    ///         if log.try_update(|log| log.push(message)) == RenderOutcome::Dead {
+    ///             break;
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Views created with [`stateful_eq`](crate::stateful::stateful_eq) report
+    /// [`Rendered`](RenderOutcome::Rendered) for every update that asked for a render,
+    /// even when the render is then skipped because the state hasn't changed. Updates
+    /// returning [`Then::Deferred`](crate::stateful::Then::Deferred) report
+    /// [`Deferred`](RenderOutcome::Deferred), as the render hasn't happened yet.
+    pub fn try_update<F, O>(&self, mutator: F) -> RenderOutcome
+    where
+        F: FnOnce(&mut S) -> O,
+        O: ShouldRender,
+    {
+        let Some(inner) = self.weak.upgrade() else {
+            return RenderOutcome::Dead;
+        };
+
+        inner.render(inner.state.with(mutator))
     }
 
     /// Same as [`update`](Signal::update), but it never renders updates.
@@ -72,10 +150,156 @@ impl<S> Signal<S> {
         }
     }
 
+    /// Render the view without changing the state. This is an escape hatch for state
+    /// that lives partly outside of the stateful view, such as in an `Rc<RefCell<_>>`,
+    /// and changes without going through [`update`](Signal::update).
+    ///
+    /// The view is always rendered, even if it was created with
+    /// [`stateful_eq`](crate::stateful::stateful_eq) and the state hasn't changed.
+    pub fn force_render(&self) {
+        if let Some(inner) = self.weak.upgrade() {
+            // Same borrow check as `update`, the state must not be borrowed during render
+            inner.state.with(|_| ());
+
+            inner.forced.set(true);
+            inner.update();
+            inner.forced.set(false);
+        }
+    }
+
     /// Replace the entire state with a new value and trigger an update.
     pub fn set(&self, val: S) {
         self.update(move |s| *s = val);
     }
+
+    /// Update the state once after `delay`, same as [`update`](Signal::update) would. The
+    /// timeout is cleared if the stateful view is dropped before it fires, or by calling
+    /// [`clear`](Timer::clear) on the returned handle.
+    ///
+    /// ```
+    /// # use kobold::prelude::*;
+    /// # use std::time::Duration;
+    /// fn show_toast(toast: Signal<Option<String>>, message: String) {
+    ///     toast.set(Some(message));
+    ///
+    ///     // auto-dismiss the toast after 3 seconds
+    ///     toast.set_timeout(Duration::from_secs(3), |toast| *toast = None);
+    /// }
+    /// ```
+    pub fn set_timeout<F, O>(&self, delay: Duration, callback: F) -> Timer<S>
+    where
+        S: 'static,
+        F: FnOnce(&mut S) -> O + 'static,
+        O: ShouldRender,
+    {
+        let Some(inner) = self.weak.upgrade() else {
+            // The view is gone, the handle will do nothing
+            return Timer {
+                key: 0,
+                weak: self.weak.clone(),
+            };
+        };
+
+        let key = inner.extras().timers.next_key();
+        let signal = self.clone();
+
+        let callback = Closure::once(move || {
+            signal.update(callback);
+
+            if let Some(inner) = signal.weak.upgrade() {
+                inner.extras().timers.remove(key);
+            }
+        });
+
+        let id = internal::set_timeout(callback.as_ref(), timer::millis(delay));
+
+        inner.extras().timers.insert(key, id, callback);
+
+        Timer {
+            key,
+            weak: self.weak.clone(),
+        }
+    }
+
+    /// Update the state repeatedly every `period`, same as [`update`](Signal::update) would.
+    /// The interval is cleared when the stateful view is dropped, or by calling
+    /// [`clear`](Timer::clear) on the returned handle.
+    ///
+    /// ```
+    /// # use kobold::prelude::*;
+    /// # use std::time::Duration;
+    /// let view = stateful(0_u32, |seconds| {
+    ///     view! { <p>"Elapsed seconds: "{ seconds }</p> }
+    /// })
+    /// .once(|signal| {
+    ///     signal.set_interval(Duration::from_secs(1), |seconds| *seconds += 1);
+    /// });
+    /// ```
+    pub fn set_interval<F, O>(&self, period: Duration, mut callback: F) -> Timer<S>
+    where
+        S: 'static,
+        F: FnMut(&mut S) -> O + 'static,
+        O: ShouldRender,
+    {
+        let Some(inner) = self.weak.upgrade() else {
+            return Timer {
+                key: 0,
+                weak: self.weak.clone(),
+            };
+        };
+
+        let key = inner.extras().timers.next_key();
+        let signal = self.clone();
+
+        let callback = Closure::<dyn FnMut()>::new(move || signal.update(&mut callback));
+        let id = internal::set_interval(callback.as_ref(), timer::millis(period));
+
+        inner.extras().timers.insert(key, id, callback);
+
+        Timer {
+            key,
+            weak: self.weak.clone(),
+        }
+    }
+
+    /// Update the state with `first`, then update it again with `then` on the next tick,
+    /// after the browser had a chance to paint the first render. Useful for multi-step
+    /// flows, such as showing a spinner before starting some blocking work:
+    ///
+    /// ```
+    /// # use kobold::prelude::*;
+    /// # fn expensive_search(_: &str) -> Vec<String> { Vec::new() }
+    /// struct Search {
+    ///     query: String,
+    ///     loading: bool,
+    ///     results: Vec<String>,
+    /// }
+    ///
+    /// fn search(search: Signal<Search>) {
+    ///     search.and_then(
+    ///         |search| search.loading = true,
+    ///         |search| {
+    ///             search.results = expensive_search(&search.query);
+    ///             search.loading = false;
+    ///         },
+    ///     );
+    /// }
+    /// ```
+    ///
+    /// The follow-up is scheduled as a zero delay [`set_timeout`](Signal::set_timeout),
+    /// so it can be cleared with the returned handle and never runs if the stateful
+    /// view is dropped in the meantime.
+    pub fn and_then<F, O, T, P>(&self, first: F, then: T) -> Timer<S>
+    where
+        S: 'static,
+        F: FnOnce(&mut S) -> O,
+        O: ShouldRender,
+        T: FnOnce(&mut S) -> P + 'static,
+        P: ShouldRender,
+    {
+        self.update(first);
+        self.set_timeout(Duration::ZERO, then)
+    }
 }
 
 impl<S> Clone for Signal<S> {
@@ -91,9 +315,76 @@ impl<S> Hook<S> {
         unsafe { &*(inner as *const _ as *const Hook<S>) }
     }
 
+    pub(super) fn is_forced(&self) -> bool {
+        self.inner.forced.get()
+    }
+
+    /// Returns `true` while the view is being built for the first time, and `false` for
+    /// every render after that. Useful for one-time setup that needs data only available
+    /// inside the render closure, such as props of the component:
+    ///
+    /// ```
+    /// # use kobold::prelude::*;
+    /// # use std::time::Duration;
+    /// #[component]
+    /// fn toast(message: &'static str, seconds: u64) -> impl View {
+    ///     stateful(true, move |visible| {
+    ///         if visible.is_first_render() {
+    ///             visible.set_timeout(Duration::from_secs(seconds), |visible| *visible = false);
+    ///         }
+    ///
+    ///         view! {
+    ///             <div.toast class={if **visible { "" } else { "hidden" }}>{ static message }</div>
+    ///         }
+    ///     })
+    /// }
+    /// # fn main() {}
+    /// ```
+    ///
+    /// The first render happens before the handler passed to
+    /// [`once`](crate::stateful::Stateful::once) is called, so the [`Signal`] it receives is
+    /// never available while this returns `true`. Renders triggered by the parent view
+    /// updating its props, rather than by changes to the state, also return `false`.
+    pub fn is_first_render(&self) -> bool {
+        !self.inner.rendered.get()
+    }
+
+    /// Register a `cleanup` to run either when the view is dropped or before its next
+    /// render, such as unsubscribing from an external source or revoking object URLs.
+    /// Cleanups run last registered first.
+    ///
+    /// ```
+    /// # use kobold::prelude::*;
+    /// # use kobold::stateful::Cleanup;
+    /// # fn subscribe(_: Signal<String>) -> u32 { 0 }
+    /// # fn unsubscribe(_: u32) {}
+    /// let feed = stateful(String::new, |message| {
+    ///     if message.is_first_render() {
+    ///         let id = subscribe(message.weak_signal());
+    ///
+    ///         message.on_cleanup(Cleanup::Drop, move || unsubscribe(id));
+    ///     }
+    ///
+    ///     view! { <p>{ ref message }</p> }
+    /// });
+    /// ```
+    ///
+    /// Every render registers its cleanups anew, so [`Cleanup::Drop`] cleanups should
+    /// only be registered once, such as on the first render like above.
+    ///
+    /// [`Cleanup::Render`] cleanups run whenever the view is about to update, even if
+    /// the render is then skipped, as with [`stateful_eq`](crate::stateful::stateful_eq)
+    /// when the state hasn't changed.
+    pub fn on_cleanup<F>(&self, when: Cleanup, cleanup: F)
+    where
+        F: FnOnce() + 'static,
+    {
+        self.inner.extras().cleanups.push(when, Box::new(cleanup));
+    }
+
     /// Binds a closure to a mutable reference of the state. While this method is public
     /// it's recommended to use the [`bind!`](crate::bind) macro instead.
-    pub fn bind<E, F, O>(&self, callback: F) -> Bound<S, F>
+    pub fn bind<E, F, O>(&self, callback: F) -> Bound<'_, S, F>
     where
         S: 'static,
         E: EventCast,
@@ -105,6 +396,45 @@ impl<S> Hook<S> {
         Bound { inner, callback }
     }
 
+    /// Same as [`bind`](Hook::bind), but the callback also receives an `index`, typically
+    /// the position of an item in a list, without having to capture it in the closure.
+    ///
+    /// The index is stored separately from the callback, so updating it is cheap, and
+    /// callbacks that don't capture anything never need to be replaced.
+    ///
+    /// ```
+    /// # use kobold::prelude::*;
+    /// let list = stateful(|| vec![0_u32; 10], |counts: &Hook<Vec<u32>>| {
+    ///     view! {
+    ///         <ul>
+    ///         {
+    ///             for counts.iter().enumerate().map(|(idx, count)| {
+    ///                 let onclick = counts.bind_indexed(idx, |counts, idx, _: MouseEvent<_>| {
+    ///                     counts[idx] += 1;
+    ///                 });
+    ///
+    ///                 view! { <li {onclick}>{ count }</li> }
+    ///             })
+    ///         }
+    ///     }
+    /// });
+    /// ```
+    pub fn bind_indexed<E, F, O>(&self, index: usize, callback: F) -> BoundIndexed<'_, S, F>
+    where
+        S: 'static,
+        E: EventCast,
+        F: Fn(&mut S, usize, E) -> O + 'static,
+        O: ShouldRender,
+    {
+        let inner = &self.inner;
+
+        BoundIndexed {
+            inner,
+            index,
+            callback,
+        }
+    }
+
     pub fn bind_async<E, F, T>(&self, callback: F) -> impl Listener<E>
     where
         S: 'static,
@@ -134,6 +464,142 @@ impl<S> Hook<S> {
         }
     }
 
+    /// Same as [`bind_async`](Hook::bind_async), but only one future spawned by callbacks
+    /// bound with this method runs at a time for this state. What happens to events fired
+    /// while a future is still running is decided by the `policy`:
+    ///
+    /// * [`Serial::Drop`] ignores the event without calling the callback.
+    /// * [`Serial::Queue`] calls the callback and runs the returned future after all
+    ///   futures before it have completed. Note that any code in the callback before
+    ///   it returns the future (such as outside of an `async move` block) runs immediately.
+    ///
+    /// ```
+    /// # use kobold::prelude::*;
+    /// # use kobold::stateful::Serial;
+    /// # async fn submit(_: &str) {}
+    /// let form = stateful(String::new, |text| {
+    ///     let onclick = text.bind_async_serial(Serial::Drop, |text, _: MouseEvent<_>| async move {
+    ///         let mut copy = String::new();
+    ///
+    ///         text.update_silent(|text| copy.clone_from(text));
+    ///
+    ///         // Clicking again while this is pending does nothing
+    ///         submit(&copy).await;
+    ///     });
+    ///
+    ///     view! { <button {onclick}>"Submit"</button> }
+    /// });
+    /// ```
+    pub fn bind_async_serial<E, F, T>(&self, policy: Serial, callback: F) -> impl Listener<E>
+    where
+        S: 'static,
+        E: EventCast,
+        F: Fn(Signal<S>, E) -> T + 'static,
+        T: Future<Output = ()> + 'static,
+    {
+        let inner = &self.inner as *const Inner<S>;
+
+        move |e| {
+            // ⚠️ Safety:
+            // ==========
+            //
+            // Same as in `bind_async`, the temporary `Rc` is only used to
+            // construct a `Weak` reference to `Inner`.
+            let rc = ManuallyDrop::new(unsafe { Rc::from_raw(inner) });
+
+            if rc.extras().tasks.is_running() {
+                if policy == Serial::Queue {
+                    let signal = Signal {
+                        weak: Rc::downgrade(&*rc),
+                    };
+
+                    rc.extras().tasks.push(callback(signal, e));
+                }
+                return;
+            }
+
+            let weak = Rc::downgrade(&*rc);
+            let signal = Signal { weak: weak.clone() };
+            let task = callback(signal, e);
+
+            rc.extras().tasks.start();
+
+            spawn_local(async move {
+                task.await;
+
+                while let Some(task) = weak.upgrade().and_then(|inner| inner.extras().tasks.next())
+                {
+                    task.await;
+                }
+            });
+        }
+    }
+
+    /// Same as [`Signal::set_timeout`], for use from within the render closure.
+    pub fn set_timeout<F, O>(&self, delay: Duration, callback: F) -> Timer<S>
+    where
+        S: 'static,
+        F: FnOnce(&mut S) -> O + 'static,
+        O: ShouldRender,
+    {
+        self.weak_signal().set_timeout(delay, callback)
+    }
+
+    /// Same as [`Signal::set_interval`], for use from within the render closure.
+    pub fn set_interval<F, O>(&self, period: Duration, callback: F) -> Timer<S>
+    where
+        S: 'static,
+        F: FnMut(&mut S) -> O + 'static,
+        O: ShouldRender,
+    {
+        self.weak_signal().set_interval(period, callback)
+    }
+
+    /// Create a [`Signal`] for this state that can be stored anywhere, such as in an
+    /// external event bus, and used to update the state later on. The signal holds only
+    /// a weak reference to the state, once the stateful view is dropped all updates
+    /// through it are no-ops.
+    ///
+    /// ```
+    /// # use kobold::prelude::*;
+    /// # use std::cell::RefCell;
+    /// thread_local! {
+    ///     static LISTENERS: RefCell<Vec<Signal<String>>> = RefCell::new(Vec::new());
+    /// }
+    ///
+    /// fn broadcast(message: &str) {
+    ///     LISTENERS.with(|listeners| {
+    ///         for signal in listeners.borrow().iter() {
+    ///             signal.set(message.into());
+    ///         }
+    ///     });
+    /// }
+    ///
+    /// let view = stateful(String::new, |message| {
+    ///     let signal = message.weak_signal();
+    ///     let subscribe = move |_: MouseEvent<_>| {
+    ///         LISTENERS.with(|listeners| listeners.borrow_mut().push(signal.clone()));
+    ///     };
+    ///
+    ///     view! {
+    ///         <p>{ ref message }</p>
+    ///         <button onclick={subscribe}>"Subscribe"</button>
+    ///     }
+    /// });
+    /// ```
+    pub fn weak_signal(&self) -> Signal<S> {
+        // ⚠️ Safety:
+        // ==========
+        //
+        // Same as in `bind_async`, the temporary `Rc` is only used to
+        // construct a `Weak` reference to `Inner`.
+        let rc = ManuallyDrop::new(unsafe { Rc::from_raw(&self.inner as *const Inner<S>) });
+
+        Signal {
+            weak: Rc::downgrade(&*rc),
+        }
+    }
+
     /// Get the value of state if state implements `Copy`. This is equivalent to writing
     /// `**hook` but conveys intent better.
     pub fn get(&self) -> S
@@ -164,15 +630,15 @@ impl<S, F> Bound<'_, S, F> {
             // ⚠️ Safety:
             // ==========
             //
-            // This is fired only as event listener from the DOM, which guarantees that
-            // state is not currently borrowed, as events cannot interrupt normal
-            // control flow, and `Signal`s cannot borrow state across .await points.
+            // This is fired only as event listener from the DOM, which happens after
+            // the product holding the listener is built, and the listener is removed
+            // along with it. Events dispatched synchronously from within a callback,
+            // such as with `HtmlElement::click`, would borrow the state twice, which
+            // `with` guards against in every build.
             let inner = unsafe { &*inner };
-            let state = unsafe { inner.state.mut_unchecked() };
+            let render = inner.state.with(|state| callback(state, e));
 
-            if callback(state, e).should_render() {
-                inner.update();
-            }
+            inner.render(render);
         };
 
         BoundListener {
@@ -223,6 +689,95 @@ where
     }
 }
 
+pub struct BoundIndexed<'b, S, F> {
+    inner: &'b Inner<S>,
+    index: usize,
+    callback: F,
+}
+
+impl<S, F> BoundIndexed<'_, S, F> {
+    pub fn into_listener<E, O>(self) -> impl Listener<E>
+    where
+        S: 'static,
+        E: EventCast + 'static,
+        F: Fn(&mut S, usize, E) -> O + 'static,
+        O: ShouldRender,
+    {
+        let BoundIndexed {
+            inner,
+            index,
+            callback,
+        } = self;
+
+        IndexedListener {
+            inner: inner as *const Inner<S>,
+            index,
+            callback,
+            _event: PhantomData,
+        }
+    }
+}
+
+pub struct IndexedListener<S, F, E> {
+    inner: *const Inner<S>,
+    index: usize,
+    callback: F,
+    _event: PhantomData<E>,
+}
+
+impl<S, F, E, O> Listener<E> for IndexedListener<S, F, E>
+where
+    S: 'static,
+    E: EventCast + 'static,
+    F: Fn(&mut S, usize, E) -> O + 'static,
+    O: ShouldRender,
+{
+    type Product = Self;
+
+    fn build(self, p: In<Self>) -> Out<Self> {
+        p.put(self)
+    }
+
+    fn update(self, p: &mut Self) {
+        p.index = self.index;
+
+        // No need to update zero-sized closures.
+        //
+        // This is a const branch that should be optimized away.
+        if std::mem::size_of::<F>() != 0 {
+            p.callback = self.callback;
+        }
+    }
+}
+
+impl<S, F, E, O> ListenerHandle for IndexedListener<S, F, E>
+where
+    S: 'static,
+    E: EventCast + 'static,
+    F: Fn(&mut S, usize, E) -> O + 'static,
+    O: ShouldRender,
+{
+    fn js_value(&mut self) -> JsValue {
+        let vcall: fn(E, *mut ()) = |e, ptr| {
+            // ⚠️ Safety:
+            // ==========
+            //
+            // Same as with `Bound`, this is fired only as event listener from the DOM,
+            // and re-entrant borrows of the state are guarded by `with`. The product
+            // itself is never moved after it's built, so the pointer to it remains valid.
+            let this = unsafe { &*(ptr as *const IndexedListener<S, F, E>) };
+            let inner = unsafe { &*this.inner };
+            let render = inner
+                .state
+                .with(|state| (this.callback)(state, this.index, e));
+
+            inner.render(render);
+        };
+
+        internal::make_event_handler(self as *mut Self as *mut (), vcall as usize)
+    }
+}
+
 impl<S> Deref for Hook<S> {
     type Target = S;
 
@@ -253,27 +808,40 @@ where
 
 #[cfg(test)]
 mod test {
-    use std::cell::UnsafeCell;
+    use std::cell::{Cell, OnceCell, UnsafeCell};
     use wasm_bindgen::JsCast;
 
     use crate::stateful::cell::WithCell;
     use crate::stateful::product::ProductHandler;
+    use crate::stateful::Then;
     use crate::value::TextProduct;
 
     use super::*;
 
-    #[test]
-    fn bound_callback_is_copy() {
-        let inner = Inner {
+    /// Mock of a stateful view with `0` as state, counting its renders in `renders`.
+    fn mock_inner(renders: &Rc<Cell<u32>>) -> Rc<Inner<i32>> {
+        let counter = renders.clone();
+
+        Rc::new(Inner {
             state: WithCell::new(0_i32),
+            extras: OnceCell::new(),
+            rendered: Cell::new(true),
+            forced: Cell::new(false),
+            deferred: Cell::new(false),
+            schedule: Inner::queue_flush,
             prod: UnsafeCell::new(ProductHandler::mock(
-                |_, _| {},
+                move |_, _| counter.set(counter.get() + 1),
                 TextProduct {
                     memo: 0,
                     node: wasm_bindgen::JsValue::UNDEFINED.unchecked_into(),
                 },
             )),
-        };
+        })
+    }
+
+    #[test]
+    fn bound_callback_is_copy() {
+        let inner = mock_inner(&Rc::default());
 
         let mock = Bound {
             inner: &inner,
@@ -285,4 +853,86 @@ mod test {
         // Make sure we can copy the mock twice
         let _ = [mock, mock];
     }
+
+    #[test]
+    fn extras_are_allocated_on_first_use() {
+        let renders = Rc::default();
+        let inner = mock_inner(&renders);
+
+        let signal = Signal {
+            weak: Rc::downgrade(&inner),
+        };
+
+        signal.update(|n| *n += 1);
+
+        assert_eq!(renders.get(), 1);
+        assert!(inner.extras.get().is_none());
+
+        let cleaned = Rc::new(Cell::new(false));
+        let flag = cleaned.clone();
+
+        Hook::new(&inner).on_cleanup(Cleanup::Render, move || flag.set(true));
+
+        assert!(inner.extras.get().is_some());
+
+        signal.update(|n| *n += 1);
+
+        assert!(cleaned.get());
+    }
+
+    #[test]
+    fn try_update_outcome() {
+        let renders = Rc::default();
+        let inner = mock_inner(&renders);
+
+        let signal = Signal {
+            weak: Rc::downgrade(&inner),
+        };
+
+        assert_eq!(signal.try_update(|n| *n += 1), RenderOutcome::Rendered);
+        assert_eq!(signal.try_update(|_| Then::Stop), RenderOutcome::Skipped);
+        assert_eq!(renders.get(), 1);
+
+        drop(inner);
+
+        assert_eq!(signal.try_update(|n| *n += 1), RenderOutcome::Dead);
+    }
+
+    #[test]
+    fn deferred_updates_render_once() {
+        let renders = Rc::default();
+        let inner = mock_inner(&renders);
+
+        // Pretend the microtask is already queued, it can't run outside of the browser
+        inner.deferred.set(true);
+
+        let signal = Signal {
+            weak: Rc::downgrade(&inner),
+        };
+
+        for _ in 0..3 {
+            let outcome = signal.try_update(|n| {
+                *n += 1;
+                Then::Deferred
+            });
+
+            assert_eq!(outcome, RenderOutcome::Deferred);
+        }
+
+        assert_eq!(renders.get(), 0);
+
+        inner.flush();
+        inner.flush();
+
+        assert_eq!(renders.get(), 1);
+        assert_eq!(inner.state.with(|n| *n), 3);
+
+        // An immediate render covers the pending deferred one
+        inner.deferred.set(true);
+
+        signal.update(|n| *n += 1);
+        inner.flush();
+
+        assert_eq!(renders.get(), 2);
+    }
 }
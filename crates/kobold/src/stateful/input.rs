@@ -0,0 +1,220 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use wasm_bindgen::JsValue;
+
+use crate::event::{Event, Listener, ListenerHandle};
+use crate::internal::{self, In, Out};
+use crate::stateful::{Hook, ShouldRender, Signal, Timer};
+
+/// Policy deciding when the value of an input bound with [`Hook::bind_input`] is
+/// committed to the state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Commit {
+    /// Commit once the length of the value, in bytes, differs by at least this much
+    /// from the last committed one.
+    Length(usize),
+    /// Commit once no input has happened for this long.
+    Debounce(Duration),
+}
+
+mod sealed {
+    pub trait TextInput: wasm_bindgen::JsCast + Clone + 'static {
+        fn text_value(&self) -> String;
+    }
+
+    impl TextInput for web_sys::HtmlInputElement {
+        fn text_value(&self) -> String {
+            self.value()
+        }
+    }
+
+    impl TextInput for web_sys::HtmlTextAreaElement {
+        fn text_value(&self) -> String {
+            self.value()
+        }
+    }
+}
+
+pub(crate) use sealed::TextInput;
+
+impl<S> Hook<S> {
+    /// Bind an `oninput` listener that only commits the value to the state when the
+    /// `commit` policy says so, instead of on every keystroke. Between commits the
+    /// element is left alone, the DOM holds the current value, making this useful for
+    /// large `<textarea>`s where rendering on every input would be costly.
+    ///
+    /// ```
+    /// # use kobold::prelude::*;
+    /// # use kobold::stateful::Commit;
+    /// # use std::time::Duration;
+    /// let editor = stateful(String::new, |text| {
+    ///     let oninput = text.bind_input(Commit::Debounce(Duration::from_millis(300)), |text, value| {
+    ///         *text = value;
+    ///     });
+    ///
+    ///     view! {
+    ///         <textarea {oninput}></textarea>
+    ///         <p>{ text.len() }" bytes"</p>
+    ///     }
+    /// });
+    /// ```
+    ///
+    /// With [`Commit::Length`] the first input always commits. Neither policy catches
+    /// the last few keystrokes before the user stops typing, if the final value needs
+    /// to be committed right away also bind an `onchange` listener.
+    pub fn bind_input<T, F, O>(&self, commit: Commit, callback: F) -> InputListener<S, F, T>
+    where
+        S: 'static,
+        T: TextInput,
+        F: Fn(&mut S, String) -> O + Clone + 'static,
+        O: ShouldRender,
+    {
+        InputListener {
+            signal: self.weak_signal(),
+            commit,
+            callback,
+            committed: None,
+            pending: Pending(None),
+            _target: PhantomData,
+        }
+    }
+}
+
+/// Listener created by [`Hook::bind_input`]. Its product keeps track of the last
+/// committed length and the pending debounce timer across renders.
+pub struct InputListener<S, F, T> {
+    signal: Signal<S>,
+    commit: Commit,
+    callback: F,
+    committed: Option<usize>,
+    pending: Pending<S>,
+    _target: PhantomData<T>,
+}
+
+/// Debounce timer that is cleared when the listener is dropped.
+struct Pending<S>(Option<Timer<S>>);
+
+impl<S> Drop for Pending<S> {
+    fn drop(&mut self) {
+        if let Some(timer) = self.0.take() {
+            timer.clear();
+        }
+    }
+}
+
+/// Returns whether a value of `len` should be committed right away, keeping track
+/// of the `committed` length.
+fn commit_now(commit: Commit, committed: &mut Option<usize>, len: usize) -> bool {
+    match commit {
+        Commit::Length(threshold) => {
+            let commit = committed.is_none_or(|committed| committed.abs_diff(len) >= threshold);
+
+            if commit {
+                *committed = Some(len);
+            }
+            commit
+        }
+        Commit::Debounce(_) => false,
+    }
+}
+
+impl<S, F, T, O> Listener<Event<T>> for InputListener<S, F, T>
+where
+    S: 'static,
+    T: TextInput,
+    F: Fn(&mut S, String) -> O + Clone + 'static,
+    O: ShouldRender,
+{
+    type Product = Self;
+
+    fn build(self, p: In<Self>) -> Out<Self> {
+        p.put(self)
+    }
+
+    fn update(self, p: &mut Self) {
+        p.signal = self.signal;
+        p.commit = self.commit;
+
+        // No need to update zero-sized closures.
+        //
+        // This is a const branch that should be optimized away.
+        if std::mem::size_of::<F>() != 0 {
+            p.callback = self.callback;
+        }
+    }
+}
+
+impl<S, F, T, O> ListenerHandle for InputListener<S, F, T>
+where
+    S: 'static,
+    T: TextInput,
+    F: Fn(&mut S, String) -> O + Clone + 'static,
+    O: ShouldRender,
+{
+    fn js_value(&mut self) -> JsValue {
+        let vcall: fn(Event<T>, *mut ()) = |e, ptr| {
+            // ⚠️ Safety:
+            // ==========
+            //
+            // The product is never moved after it's built, so the pointer to it
+            // remains valid. It's only borrowed until the update is prepared, as
+            // committing renders the view, which updates this very product.
+            let this = unsafe { &mut *(ptr as *mut InputListener<S, F, T>) };
+            let target = e.current_target();
+
+            if let Some(timer) = this.pending.0.take() {
+                timer.clear();
+            }
+
+            let signal = this.signal.clone();
+            let callback = this.callback.clone();
+
+            if let Commit::Debounce(delay) = this.commit {
+                let target = (*target).clone();
+                let timer =
+                    signal.set_timeout(delay, move |state| callback(state, target.text_value()));
+
+                this.pending.0 = Some(timer);
+            } else {
+                let value = target.text_value();
+
+                if commit_now(this.commit, &mut this.committed, value.len()) {
+                    signal.update(move |state| callback(state, value));
+                }
+            }
+        };
+
+        internal::make_event_handler(self as *mut Self as *mut (), vcall as usize)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn length_threshold() {
+        let mut committed = None;
+        let mut input = |len| commit_now(Commit::Length(10), &mut committed, len);
+
+        assert!(input(100));
+        assert!(!input(105));
+        assert!(!input(91));
+        assert!(input(110));
+        assert!(input(0));
+    }
+
+    #[test]
+    fn debounce_never_commits_immediately() {
+        let mut committed = None;
+        let commit = Commit::Debounce(Duration::from_millis(300));
+
+        assert!(!commit_now(commit, &mut committed, 0));
+        assert!(!commit_now(commit, &mut committed, 100));
+    }
+}
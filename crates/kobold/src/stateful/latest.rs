@@ -0,0 +1,270 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::cell::{Cell, RefCell};
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+
+use wasm_bindgen::UnwrapThrowExt;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{AbortController, AbortSignal};
+
+use crate::event::{EventCast, Listener};
+use crate::stateful::{Hook, Signal};
+
+type Task = Pin<Box<dyn Future<Output = ()>>>;
+
+/// Future started with [`Signal::latest`], shared between the spawned [`Run`]
+/// and the [`Latest`] slot so that it can be dropped before it completes.
+struct Slot {
+    task: RefCell<Option<Task>>,
+    cancelled: Cell<bool>,
+}
+
+impl Slot {
+    fn new(task: Task) -> Rc<Self> {
+        Rc::new(Slot {
+            task: RefCell::new(Some(task)),
+            cancelled: Cell::new(false),
+        })
+    }
+
+    fn cancel(&self) {
+        self.cancelled.set(true);
+
+        // Don't drop the future while `task` is borrowed
+        let task = self.task.borrow_mut().take();
+
+        drop(task);
+    }
+}
+
+/// Drives the future in a [`Slot`], completing early once it's cancelled.
+struct Run {
+    slot: Rc<Slot>,
+}
+
+impl Future for Run {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+        // The future is taken out of the slot while polling, as it can start a new
+        // latest future and cancel itself in the process.
+        let Some(mut task) = self.slot.task.borrow_mut().take() else {
+            return Poll::Ready(());
+        };
+
+        let poll = task.as_mut().poll(cx);
+
+        if poll.is_pending() && !self.slot.cancelled.get() {
+            *self.slot.task.borrow_mut() = Some(task);
+        }
+
+        poll
+    }
+}
+
+struct Current {
+    slot: Rc<Slot>,
+    abort: AbortController,
+}
+
+/// Most recent future started with [`Signal::latest`] for a single stateful view,
+/// it's cancelled when the view is dropped.
+pub(super) struct Latest {
+    current: RefCell<Option<Current>>,
+}
+
+impl Latest {
+    pub const fn new() -> Self {
+        Latest {
+            current: RefCell::new(None),
+        }
+    }
+
+    /// Cancel the current future and replace it with `task`, returns the future
+    /// that needs to be spawned to run it.
+    fn replace(&self, task: Task, abort: AbortController) -> Run {
+        self.cancel();
+
+        let slot = Slot::new(task);
+
+        *self.current.borrow_mut() = Some(Current {
+            slot: slot.clone(),
+            abort,
+        });
+
+        Run { slot }
+    }
+
+    fn cancel(&self) {
+        let current = self.current.borrow_mut().take();
+
+        if let Some(Current { slot, abort }) = current {
+            slot.cancel();
+            abort.abort();
+        }
+    }
+}
+
+impl Drop for Latest {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+impl<S> Signal<S> {
+    /// Spawn the future returned by `producer`, cancelling the future previously started
+    /// with this method for the same state if it's still running. Only the most recent
+    /// future can update the state, so results of stale requests never overwrite fresh ones.
+    ///
+    /// Cancelled futures are dropped, and the [`AbortSignal`] passed to `producer` is
+    /// aborted. Passing it on to a `fetch` with `RequestInit::set_signal` aborts the
+    /// request itself. The latest future is also cancelled when the stateful view is dropped.
+    ///
+    /// ```
+    /// # use kobold::prelude::*;
+    /// # use web_sys::AbortSignal;
+    /// # async fn search(_: &str, _: &AbortSignal) -> Vec<String> { Vec::new() }
+    /// fn query(results: &Signal<Vec<String>>, query: String) {
+    ///     let signal = results.clone();
+    ///
+    ///     results.latest(|abort| async move {
+    ///         let found = search(&query, &abort).await;
+    ///
+    ///         signal.update(|results| *results = found);
+    ///     });
+    /// }
+    /// ```
+    pub fn latest<F, T>(&self, producer: F)
+    where
+        F: FnOnce(AbortSignal) -> T,
+        T: Future<Output = ()> + 'static,
+    {
+        let Some(inner) = self.weak.upgrade() else {
+            return;
+        };
+
+        let abort = AbortController::new().unwrap_throw();
+        let task = Box::pin(producer(abort.signal()));
+
+        spawn_local(inner.extras().latest.replace(task, abort));
+    }
+}
+
+impl<S> Hook<S> {
+    /// Same as [`bind_async`](Hook::bind_async), but starting the future with
+    /// [`Signal::latest`], so that every event cancels the future of the previous one.
+    ///
+    /// ```
+    /// # use kobold::prelude::*;
+    /// # use web_sys::AbortSignal;
+    /// # async fn search(_: &str, _: &AbortSignal) -> Vec<String> { Vec::new() }
+    /// let search_box = stateful(Vec::<String>::new, |results| {
+    ///     let oninput = results.bind_latest(|results, e: Event<HtmlInputElement>, abort| {
+    ///         let query = e.current_target().value();
+    ///
+    ///         async move {
+    ///             // Typing again while this is pending aborts the search
+    ///             let found = search(&query, &abort).await;
+    ///
+    ///             results.update(|results| *results = found);
+    ///         }
+    ///     });
+    ///
+    ///     view! {
+    ///         <input {oninput}>
+    ///         <ul>{ for results.iter().map(|result| view! { <li>{ ref result }</li> }) }</ul>
+    ///     }
+    /// });
+    /// ```
+    pub fn bind_latest<E, F, T>(&self, callback: F) -> impl Listener<E>
+    where
+        S: 'static,
+        E: EventCast,
+        F: Fn(Signal<S>, E, AbortSignal) -> T + 'static,
+        T: Future<Output = ()> + 'static,
+    {
+        let signal = self.weak_signal();
+
+        move |e| {
+            let target = signal.clone();
+
+            signal.latest(|abort| callback(target, e, abort));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+    use std::task::{Wake, Waker};
+
+    use super::*;
+
+    struct Noop;
+
+    impl Wake for Noop {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    /// Future that is pending once, counting how many times it was completed or dropped.
+    fn task(done: &Rc<Cell<u32>>, dropped: &Rc<Cell<u32>>) -> Task {
+        struct Guard(Rc<Cell<u32>>);
+
+        impl Drop for Guard {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let done = done.clone();
+        let guard = Guard(dropped.clone());
+        let mut pending = true;
+
+        Box::pin(async move {
+            let _guard = guard;
+
+            std::future::poll_fn(|_| {
+                if std::mem::take(&mut pending) {
+                    Poll::Pending
+                } else {
+                    Poll::Ready(())
+                }
+            })
+            .await;
+
+            done.set(done.get() + 1);
+        })
+    }
+
+    #[test]
+    fn cancelled_run_completes_without_polling() {
+        let waker = Waker::from(Arc::new(Noop));
+        let mut cx = Context::from_waker(&waker);
+
+        let done = Rc::new(Cell::new(0));
+        let dropped = Rc::new(Cell::new(0));
+
+        let mut first = Run {
+            slot: Slot::new(task(&done, &dropped)),
+        };
+        let mut second = Run {
+            slot: Slot::new(task(&done, &dropped)),
+        };
+
+        assert!(Pin::new(&mut first).poll(&mut cx).is_pending());
+        assert!(Pin::new(&mut second).poll(&mut cx).is_pending());
+
+        first.slot.cancel();
+
+        assert_eq!(dropped.get(), 1);
+        assert!(Pin::new(&mut first).poll(&mut cx).is_ready());
+        assert!(Pin::new(&mut second).poll(&mut cx).is_ready());
+        assert_eq!(done.get(), 1);
+        assert_eq!(dropped.get(), 2);
+    }
+}
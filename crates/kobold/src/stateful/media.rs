@@ -0,0 +1,139 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use web_sys::{MediaQueryList, MediaQueryListEvent};
+
+use crate::stateful::{Cleanup, Hook, ShouldRender, Signal};
+
+/// Handle to a media query subscribed to with [`Signal::media_query`].
+///
+/// Dropping the handle does not unsubscribe, that happens automatically when the
+/// stateful view is dropped, or manually with [`unsubscribe`](MediaQuery::unsubscribe).
+#[derive(Clone)]
+pub struct MediaQuery {
+    sub: Rc<Subscription>,
+}
+
+struct Subscription {
+    list: MediaQueryList,
+    listener: Closure<dyn FnMut(MediaQueryListEvent)>,
+    active: Cell<bool>,
+}
+
+impl Subscription {
+    fn unsubscribe(&self) {
+        if self.active.replace(false) {
+            let _ = self.list.remove_event_listener_with_callback(
+                "change",
+                self.listener.as_ref().unchecked_ref(),
+            );
+        }
+    }
+}
+
+impl MediaQuery {
+    /// Returns `true` if the document currently matches the query.
+    pub fn matches(&self) -> bool {
+        self.sub.list.matches()
+    }
+
+    /// Stop listening to changes of the query, the state is no longer updated afterwards.
+    pub fn unsubscribe(&self) {
+        self.sub.unsubscribe();
+    }
+}
+
+impl<S> Signal<S> {
+    /// Evaluate a CSS media `query`, such as `"(prefers-color-scheme: dark)"` or
+    /// `"(min-width: 800px)"`, via
+    /// [`matchMedia`](https://developer.mozilla.org/en-US/docs/Web/API/Window/matchMedia),
+    /// and update the state with `callback` whenever the document starts or stops
+    /// matching it, same as [`update`](Signal::update) would.
+    ///
+    /// The `callback` is not called for the current status, use [`MediaQuery::matches`]
+    /// on the returned handle to initialize the state. The listener is removed when the
+    /// stateful view is dropped.
+    ///
+    /// ```no_run
+    /// use kobold::prelude::*;
+    ///
+    /// #[component]
+    /// fn layout() -> impl View {
+    ///     stateful(false, |wide| {
+    ///         let layout = if **wide { "columns" } else { "stacked" };
+    ///
+    ///         view! {
+    ///             <div class={layout}>
+    ///                 <nav>"Sidebar"</nav>
+    ///                 <main>"Content"</main>
+    ///             </div>
+    ///         }
+    ///     })
+    ///     .once(|wide| {
+    ///         let query = wide.media_query("(min-width: 800px)", |wide, matches| *wide = matches);
+    ///
+    ///         wide.update(|wide| *wide = query.matches());
+    ///     })
+    /// }
+    /// # fn main() {}
+    /// ```
+    pub fn media_query<F, O>(&self, query: &str, callback: F) -> MediaQuery
+    where
+        S: 'static,
+        F: Fn(&mut S, bool) -> O + 'static,
+        O: ShouldRender,
+    {
+        let list = web_sys::window()
+            .and_then(|window| window.match_media(query).ok().flatten())
+            .expect_throw("Couldn't evaluate media query");
+
+        let signal = self.clone();
+        let listener =
+            Closure::<dyn FnMut(MediaQueryListEvent)>::new(move |e: MediaQueryListEvent| {
+                signal.update(|state| callback(state, e.matches()));
+            });
+
+        let sub = Rc::new(Subscription {
+            list,
+            listener,
+            active: Cell::new(false),
+        });
+
+        if let Some(inner) = self.weak.upgrade() {
+            let _ = sub
+                .list
+                .add_event_listener_with_callback("change", sub.listener.as_ref().unchecked_ref());
+
+            sub.active.set(true);
+
+            let sub = sub.clone();
+
+            inner
+                .extras()
+                .cleanups
+                .push(Cleanup::Drop, Box::new(move || sub.unsubscribe()));
+        }
+
+        MediaQuery { sub }
+    }
+}
+
+impl<S> Hook<S> {
+    /// Same as [`Signal::media_query`], for use from within the render closure. Every
+    /// call adds a new listener, so it should only be called once, such as on the
+    /// [first render](Hook::is_first_render).
+    pub fn media_query<F, O>(&self, query: &str, callback: F) -> MediaQuery
+    where
+        S: 'static,
+        F: Fn(&mut S, bool) -> O + 'static,
+        O: ShouldRender,
+    {
+        self.weak_signal().media_query(query, callback)
+    }
+}
@@ -5,6 +5,7 @@
 use std::marker::PhantomData;
 
 use wasm_bindgen::JsValue;
+use web_sys::DomRect;
 
 use crate::internal::{In, Out};
 use crate::stateful::Hook;
@@ -18,6 +19,8 @@ pub trait Product<S> {
     fn unmount(&self);
 
     fn replace_with(&self, new: &JsValue);
+
+    fn rect(&self) -> Option<DomRect>;
 }
 
 pub struct ProductHandler<S, P, F> {
@@ -72,4 +75,8 @@ where
     fn replace_with(&self, new: &JsValue) {
         self.product.replace_with(new)
     }
+
+    fn rect(&self) -> Option<DomRect> {
+        self.product.rect()
+    }
 }
@@ -0,0 +1,78 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::event::EventCast;
+use crate::stateful::{Bound, Hook, Signal, Then};
+
+/// State that is only ever changed by actions, funneling all state transitions
+/// through a single [`reduce`](Reducer::reduce) method. Actions are sent to the
+/// state with [`Hook::dispatch`] from event listeners, or [`Signal::dispatch`]
+/// from anywhere else.
+///
+/// ```
+/// use kobold::prelude::*;
+/// use kobold::stateful::Reducer;
+///
+/// struct Counter {
+///     count: i32,
+/// }
+///
+/// enum Action {
+///     Increment,
+///     Decrement,
+///     Reset,
+/// }
+///
+/// impl Reducer for Counter {
+///     type Action = Action;
+///
+///     fn reduce(&mut self, action: Action) -> Then {
+///         match action {
+///             Action::Increment => self.count += 1,
+///             Action::Decrement => self.count -= 1,
+///             Action::Reset if self.count == 0 => return Then::Stop,
+///             Action::Reset => self.count = 0,
+///         }
+///         Then::Render
+///     }
+/// }
+///
+/// #[component]
+/// fn counter() -> impl View {
+///     stateful(|| Counter { count: 0 }, |counter| {
+///         view! {
+///             <p>{ counter.count }</p>
+///             <button onclick={counter.dispatch(|_| Action::Decrement)}>"-"</button>
+///             <button onclick={counter.dispatch(|_| Action::Increment)}>"+"</button>
+///             <button onclick={counter.dispatch(|_| Action::Reset)}>"Reset"</button>
+///         }
+///     })
+/// }
+/// # fn main() {}
+/// ```
+pub trait Reducer: 'static {
+    type Action;
+
+    /// Apply the `action` to the state, returning whether the view should render.
+    fn reduce(&mut self, action: Self::Action) -> Then;
+}
+
+impl<S: Reducer> Hook<S> {
+    /// Bind a listener that dispatches the action returned by `action` for each event,
+    /// see [`Reducer`].
+    pub fn dispatch<E, F>(&self, action: F) -> Bound<'_, S, impl Fn(&mut S, E) -> Then + 'static>
+    where
+        E: EventCast,
+        F: Fn(E) -> S::Action + 'static,
+    {
+        self.bind(move |state: &mut S, e| state.reduce(action(e)))
+    }
+}
+
+impl<S: Reducer> Signal<S> {
+    /// Dispatch the `action` to the state, same as [`Hook::dispatch`] does for events.
+    pub fn dispatch(&self, action: S::Action) {
+        self.update(move |state| state.reduce(action));
+    }
+}
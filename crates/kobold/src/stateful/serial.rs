@@ -0,0 +1,84 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+
+/// Policy for events fired while a future spawned by
+/// [`bind_async_serial`](crate::stateful::Hook::bind_async_serial) is still running.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Serial {
+    /// Ignore the event, no future is created for it. Useful for preventing
+    /// duplicate form submissions.
+    Drop,
+    /// Queue the future created for the event, and run it once all futures
+    /// before it have completed. Useful for keeping updates in order.
+    Queue,
+}
+
+type Task = Pin<Box<dyn Future<Output = ()>>>;
+
+/// In-flight state of serial async callbacks for a single stateful view.
+pub(super) struct Tasks {
+    running: Cell<bool>,
+    queue: RefCell<VecDeque<Task>>,
+}
+
+impl Tasks {
+    pub const fn new() -> Self {
+        Tasks {
+            running: Cell::new(false),
+            queue: RefCell::new(VecDeque::new()),
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.get()
+    }
+
+    /// Mark tasks as running, returns `false` if they were already running.
+    pub fn start(&self) -> bool {
+        !self.running.replace(true)
+    }
+
+    pub fn push(&self, task: impl Future<Output = ()> + 'static) {
+        self.queue.borrow_mut().push_back(Box::pin(task));
+    }
+
+    /// Get the next task in the queue, or mark tasks as no longer running if the queue is empty.
+    pub fn next(&self) -> Option<Task> {
+        let next = self.queue.borrow_mut().pop_front();
+
+        if next.is_none() {
+            self.running.set(false);
+        }
+
+        next
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tasks_run_in_order() {
+        let tasks = Tasks::new();
+
+        assert!(tasks.start());
+        assert!(!tasks.start());
+
+        tasks.push(async {});
+        tasks.push(async {});
+
+        assert!(tasks.next().is_some());
+        assert!(tasks.next().is_some());
+        assert!(tasks.is_running());
+        assert!(tasks.next().is_none());
+        assert!(!tasks.is_running());
+        assert!(tasks.start());
+    }
+}
@@ -3,12 +3,43 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 /// Describes whether or not a component should be rendered after state changes.
+///
+/// * `()`, the return type of closures without an explicit one, always renders.
+/// * `bool` renders on `true` and stops on `false`.
+/// * [`Then`] renders on [`Then::Render`], stops on [`Then::Stop`] and defers the render
+///   on [`Then::Deferred`].
+/// * Your own enums, with [`#[derive(ShouldRender)]`](macro@crate::stateful::ShouldRender).
+///
+/// ```
+/// # use kobold::prelude::*;
+/// fn example(count: Signal<i32>) {
+///     // always renders
+///     count.update(|count| *count += 1);
+///
+///     // only renders if the count was changed
+///     count.update(|count| {
+///         let changed = *count < 10;
+///         if changed {
+///             *count += 1;
+///         }
+///         changed
+///     });
+/// }
+/// ```
+///
 /// For uses see:
 ///
 /// * [`Hook::bind`](crate::stateful::Hook::bind)
 /// * [`IntoState::update`](crate::stateful::IntoState::update)
 pub trait ShouldRender: 'static {
     fn should_render(self) -> bool;
+
+    /// Returns `true` if the render should be deferred to a microtask at the end of
+    /// the current task, so that multiple deferred updates render the view only once.
+    /// Checked before [`should_render`](ShouldRender::should_render), defaults to `false`.
+    fn deferred(&self) -> bool {
+        false
+    }
 }
 
 /// Closures without return type always update their view.
@@ -18,6 +49,13 @@ impl ShouldRender for () {
     }
 }
 
+/// `true` renders the view, `false` is a silent update.
+impl ShouldRender for bool {
+    fn should_render(self) -> bool {
+        self
+    }
+}
+
 /// An enum that implements the [`ShouldRender`](ShouldRender) trait.
 /// See:
 ///
@@ -28,13 +66,76 @@ pub enum Then {
     Stop,
     /// Render the view after this update
     Render,
+    /// Render the view at the end of the current task, coalescing with other
+    /// deferred updates of the same state.
+    ///
+    /// ```
+    /// # use kobold::prelude::*;
+    /// fn log_all(log: Signal<Vec<String>>, messages: Vec<String>) {
+    ///     for message in messages {
+    ///         // Renders once after the loop, not on every message
+    ///         log.update(|log| {
+    ///             log.push(message);
+    ///             Then::Deferred
+    ///         });
+    ///     }
+    /// }
+    /// ```
+    Deferred,
 }
 
 impl ShouldRender for Then {
     fn should_render(self) -> bool {
         match self {
             Then::Stop => false,
-            Then::Render => true,
+            Then::Render | Then::Deferred => true,
+        }
+    }
+
+    fn deferred(&self) -> bool {
+        matches!(self, Then::Deferred)
+    }
+}
+
+impl From<bool> for Then {
+    fn from(render: bool) -> Then {
+        if render {
+            Then::Render
+        } else {
+            Then::Stop
         }
     }
 }
+
+/// Outcome of an update made with [`Signal::try_update`](crate::stateful::Signal::try_update).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RenderOutcome {
+    /// The state was updated and the view rendered
+    Rendered,
+    /// The state was updated silently, as the update asked not to render
+    Skipped,
+    /// The state was updated and the render deferred to the end of the current task
+    Deferred,
+    /// The stateful view has been dropped, the update never ran
+    Dead,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_render_contract() {
+        assert!(().should_render());
+        assert!(true.should_render());
+        assert!(!false.should_render());
+        assert!(Then::Render.should_render());
+        assert!(!Then::Stop.should_render());
+        assert!(Then::Deferred.should_render());
+        assert!(Then::Deferred.deferred());
+        assert!(!Then::Render.deferred());
+        assert!(!().deferred());
+        assert!(Then::from(true).should_render());
+        assert!(!Then::from(false).should_render());
+    }
+}
@@ -0,0 +1,122 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::cell::{Cell, RefCell};
+use std::rc::Weak;
+use std::time::Duration;
+
+use wasm_bindgen::closure::Closure;
+
+use crate::internal;
+use crate::stateful::Inner;
+
+struct Entry {
+    key: u32,
+    id: i32,
+    _callback: Closure<dyn FnMut()>,
+}
+
+/// Timers scheduled for a single stateful view, all of them are cleared
+/// when the view is dropped.
+pub(super) struct Timers {
+    next: Cell<u32>,
+    active: RefCell<Vec<Entry>>,
+}
+
+impl Timers {
+    pub const fn new() -> Self {
+        Timers {
+            next: Cell::new(0),
+            active: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub fn next_key(&self) -> u32 {
+        let key = self.next.get();
+
+        self.next.set(key.wrapping_add(1));
+
+        key
+    }
+
+    pub fn insert(&self, key: u32, id: i32, callback: Closure<dyn FnMut()>) {
+        self.active.borrow_mut().push(Entry {
+            key,
+            id,
+            _callback: callback,
+        });
+    }
+
+    /// Forget the timer without clearing it, used once a timeout has fired.
+    pub fn remove(&self, key: u32) {
+        let mut active = self.active.borrow_mut();
+
+        if let Some(idx) = active.iter().position(|entry| entry.key == key) {
+            let entry = active.swap_remove(idx);
+
+            // Don't drop the closure while `active` is borrowed
+            drop(active);
+            drop(entry);
+        }
+    }
+
+    pub fn clear(&self, key: u32) {
+        let mut active = self.active.borrow_mut();
+
+        if let Some(idx) = active.iter().position(|entry| entry.key == key) {
+            let entry = active.swap_remove(idx);
+
+            drop(active);
+
+            // Timeouts and intervals share the same pool of ids,
+            // `clearTimeout` works for both
+            internal::clear_timeout(entry.id);
+        }
+    }
+}
+
+impl Drop for Timers {
+    fn drop(&mut self) {
+        for entry in self.active.get_mut().iter() {
+            internal::clear_timeout(entry.id);
+        }
+    }
+}
+
+/// Handle to a timer scheduled with [`Signal::set_timeout`](crate::stateful::Signal::set_timeout)
+/// or [`Signal::set_interval`](crate::stateful::Signal::set_interval).
+///
+/// Dropping the handle does not clear the timer, it's cleared automatically when
+/// the stateful view is dropped, or manually with [`clear`](Timer::clear).
+pub struct Timer<S> {
+    pub(super) key: u32,
+    pub(super) weak: Weak<Inner<S>>,
+}
+
+impl<S> Timer<S> {
+    /// Clear the timer, nothing happens if it's a timeout that has already fired.
+    pub fn clear(self) {
+        if let Some(inner) = self.weak.upgrade() {
+            if let Some(extras) = inner.extras.get() {
+                extras.timers.clear(self.key);
+            }
+        }
+    }
+}
+
+pub(super) fn millis(duration: Duration) -> i32 {
+    duration.as_millis().min(i32::MAX as u128) as i32
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn duration_to_millis() {
+        assert_eq!(millis(Duration::from_millis(1500)), 1500);
+        assert_eq!(millis(Duration::from_micros(999)), 0);
+        assert_eq!(millis(Duration::MAX), i32::MAX);
+    }
+}
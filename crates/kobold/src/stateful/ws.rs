@@ -0,0 +1,53 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::net::{Reconnect, WebSocket, WsEvent};
+use crate::stateful::{Cleanup, Hook, RenderOutcome, ShouldRender, Signal};
+
+impl<S> Signal<S> {
+    /// Connect a [`WebSocket`] to `url`, updating the state with `callback` for every
+    /// event of the connection, same as [`update`](Signal::update) would. The socket is
+    /// closed when the stateful view is dropped, see the [`net`](crate::net) module for
+    /// an example.
+    pub fn connect_ws<F, O>(&self, url: &str, reconnect: Reconnect, callback: F) -> WebSocket
+    where
+        S: 'static,
+        F: Fn(&mut S, WsEvent) -> O + 'static,
+        O: ShouldRender,
+    {
+        let signal = self.clone();
+        let socket = WebSocket::with_handler(
+            url,
+            reconnect,
+            Box::new(move |event| {
+                signal.try_update(|state| callback(state, event)) != RenderOutcome::Dead
+            }),
+        );
+
+        if let Some(inner) = self.weak.upgrade() {
+            let socket = socket.clone();
+
+            inner
+                .extras()
+                .cleanups
+                .push(Cleanup::Drop, Box::new(move || socket.close()));
+        }
+
+        socket
+    }
+}
+
+impl<S> Hook<S> {
+    /// Same as [`Signal::connect_ws`], for use from within the render closure. Every
+    /// call opens a new connection, so it should only be called once, such as on the
+    /// [first render](Hook::is_first_render).
+    pub fn connect_ws<F, O>(&self, url: &str, reconnect: Reconnect, callback: F) -> WebSocket
+    where
+        S: 'static,
+        F: Fn(&mut S, WsEvent) -> O + 'static,
+        O: ShouldRender,
+    {
+        self.weak_signal().connect_ws(url, reconnect, callback)
+    }
+}
@@ -0,0 +1,148 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Utilities for building URLs
+//!
+//! [`Query`] appends percent-encoded parameters to a URL, so that user data can't
+//! produce malformed links. It can be used directly as an `href`:
+//!
+//! ```
+//! use kobold::prelude::*;
+//! use kobold::url::Query;
+//!
+//! #[component]
+//! fn search_link(term: &str, page: u32) -> impl View + '_ {
+//!     let href = Query::new("/search").param("q", term).param("page", page);
+//!
+//!     view! { <a {href}>{ term }</a> }
+//! }
+//! # fn main() {}
+//! ```
+
+use std::fmt::{self, Display, Write};
+
+use web_sys::Node;
+
+use crate::attribute::Attribute;
+use crate::dom::Property;
+
+/// URL with query parameters, see the [module documentation](self).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Query {
+    url: String,
+    params: bool,
+}
+
+impl Query {
+    /// Start with a `url` to append parameters to. The `url` itself isn't encoded,
+    /// and can already contain a query string.
+    pub fn new(url: &str) -> Self {
+        Query {
+            url: url.into(),
+            params: url.contains('?'),
+        }
+    }
+
+    /// Append a `key=value` pair, with both the key and the formatted value encoded.
+    pub fn param(mut self, key: &str, value: impl Display) -> Self {
+        self.url.push(if self.params { '&' } else { '?' });
+        self.params = true;
+
+        let mut encoder = Encoder(&mut self.url);
+
+        let _ = encoder.write_str(key);
+        encoder.0.push('=');
+        let _ = write!(encoder, "{value}");
+
+        self
+    }
+
+    /// Returns the URL as a string.
+    pub fn as_str(&self) -> &str {
+        &self.url
+    }
+}
+
+impl AsRef<str> for Query {
+    fn as_ref(&self) -> &str {
+        &self.url
+    }
+}
+
+impl Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.url)
+    }
+}
+
+impl From<Query> for String {
+    fn from(query: Query) -> String {
+        query.url
+    }
+}
+
+impl<P> Attribute<P> for Query
+where
+    P: for<'a> Property<&'a str>,
+{
+    type Product = String;
+
+    fn build(self) -> Self::Product {
+        self.url
+    }
+
+    fn build_in(self, prop: P, node: &Node) -> Self::Product {
+        self.url.build_in(prop, node)
+    }
+
+    fn update_in(self, prop: P, node: &Node, prod: &mut Self::Product) {
+        self.url.update_in(prop, node, prod)
+    }
+}
+
+/// Writer percent-encoding everything but the unreserved characters of
+/// [RFC 3986](https://www.rfc-editor.org/rfc/rfc3986#section-2.3).
+struct Encoder<'a>(&'a mut String);
+
+impl Write for Encoder<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for byte in s.bytes() {
+            match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                    self.0.push(byte as char)
+                }
+                _ => write!(self.0, "%{byte:02X}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn encodes_params() {
+        let query = Query::new("/search")
+            .param("q", "cats & dogs")
+            .param("page", 2);
+
+        assert_eq!(query.as_str(), "/search?q=cats%20%26%20dogs&page=2");
+    }
+
+    #[test]
+    fn encodes_keys_and_unicode() {
+        let query = Query::new("/").param("a=b", "żółw?#");
+
+        assert_eq!(query.as_str(), "/?a%3Db=%C5%BC%C3%B3%C5%82w%3F%23");
+    }
+
+    #[test]
+    fn extends_existing_query() {
+        let query = Query::new("/list?sort=asc").param("filter", "done");
+
+        assert_eq!(query.as_str(), "/list?sort=asc&filter=done");
+    }
+}
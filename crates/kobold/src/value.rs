@@ -2,12 +2,19 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use std::cell::{Cell, RefCell};
+use std::ffi::{OsStr, OsString};
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+
 use web_sys::Node;
 
+use crate::branching::{Branch2, EmptyNode};
 use crate::diff::{Diff, Ref, VString};
 use crate::dom::{Anchor, Property, TextContent};
 use crate::internal::{self, In, Out};
-use crate::View;
+use crate::{Mountable, View};
 
 /// Value that can be set as a property on DOM node
 pub trait Value<P>: IntoText {
@@ -174,6 +181,21 @@ impl<'a> View for &&'a str {
     }
 }
 
+/// Renders the current contents of the buffer, same as `&String`. This is read-only,
+/// to update state from an input bind an `oninput` or `onchange` listener with
+/// [`Hook::bind`](crate::stateful::Hook::bind).
+impl<'a> View for &'a mut String {
+    type Product = <&'a String as View>::Product;
+
+    fn build(self, p: In<Self::Product>) -> Out<Self::Product> {
+        (&*self).build(p)
+    }
+
+    fn update(self, p: &mut Self::Product) {
+        (&*self).update(p)
+    }
+}
+
 macro_rules! impl_ref_view {
     ($($ty:ty),*) => {
         $(
@@ -193,3 +215,216 @@ macro_rules! impl_ref_view {
 }
 
 impl_ref_view!(bool, u8, u16, u32, u64, u128, usize, isize, i8, i16, i32, i64, i128, f32, f64);
+
+macro_rules! impl_lossy_view {
+    ($($ty:ty),*) => {
+        $(
+            /// Paths and OS strings are rendered with `to_string_lossy`, any invalid
+            /// UTF-8 sequences are replaced with `U+FFFD REPLACEMENT CHARACTER`.
+            impl View for &$ty {
+                type Product = TextProduct<String>;
+
+                fn build(self, p: In<Self::Product>) -> Out<Self::Product> {
+                    let text = self.to_string_lossy();
+
+                    p.put(TextProduct {
+                        node: text.as_ref().into_text(),
+                        memo: text.into_owned(),
+                    })
+                }
+
+                fn update(self, p: &mut Self::Product) {
+                    let text = self.to_string_lossy();
+
+                    if p.memo != text {
+                        text.as_ref().clone_into(&mut p.memo);
+                        p.memo.as_str().set_prop(TextContent, &p.node);
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_lossy_view!(Path, PathBuf, OsStr, OsString);
+
+/// Renders the result of [`format_args!`], avoiding a `format!` at the call site:
+///
+/// ```no_run
+/// # use kobold::prelude::*;
+/// let (done, total) = (3, 10);
+///
+/// kobold::start(view! {
+///     <p>{ format_args!("{done}/{total}") }</p>
+/// });
+/// ```
+///
+/// Note that `format_args!` borrows temporaries that only live until the end of the
+/// statement, so views using it can't be returned from components or `stateful`
+/// closures. Use `format!` there instead.
+///
+/// The arguments can't be diffed before they are formatted, so they are formatted
+/// into a new `String` on every update, and the DOM is only updated if it differs
+/// from the previously rendered text.
+impl View for fmt::Arguments<'_> {
+    type Product = TextProduct<String>;
+
+    fn build(self, p: In<Self::Product>) -> Out<Self::Product> {
+        let text = fmt::format(self);
+
+        p.put(TextProduct {
+            node: text.as_str().into_text(),
+            memo: text,
+        })
+    }
+
+    fn update(self, p: &mut Self::Product) {
+        let mut text = String::with_capacity(p.memo.len());
+        let _ = fmt::Write::write_fmt(&mut text, self);
+
+        if p.memo != text {
+            p.memo = text;
+            p.memo.as_str().set_prop(TextContent, &p.node);
+        }
+    }
+}
+
+/// Mounts an existing DOM node, such as one created by a third party widget, in place
+/// of the expression:
+///
+/// ```no_run
+/// # use kobold::prelude::*;
+/// use kobold::reexport::web_sys::{self, Node};
+///
+/// // A canvas some charting library draws into
+/// let chart: Node = web_sys::window()
+///     .and_then(|window| window.document())
+///     .and_then(|document| document.create_element("canvas").ok())
+///     .expect("a browser window")
+///     .into();
+///
+/// kobold::start(view! {
+///     <h1>"Sales"</h1>
+///     { chart }
+/// });
+/// ```
+///
+/// The node is compared by identity on updates, it's left as is if it's the same node,
+/// and replaced in the DOM otherwise. Kobold never modifies the node, removing it from
+/// the view only detaches it from the document, so the widget owning it can keep its
+/// state and mount it again later.
+impl View for Node {
+    type Product = Node;
+
+    fn build(self, p: In<Node>) -> Out<Node> {
+        p.put(self)
+    }
+
+    fn update(self, p: &mut Node) {
+        if *p != self {
+            p.replace_with(&self);
+            *p = self;
+        }
+    }
+}
+
+/// Same as [`Node`](#impl-View-for-Node), cloning the reference to the node.
+impl View for &Node {
+    type Product = Node;
+
+    fn build(self, p: In<Node>) -> Out<Node> {
+        self.clone().build(p)
+    }
+
+    fn update(self, p: &mut Node) {
+        if p != self {
+            self.clone().update(p);
+        }
+    }
+}
+
+/// Renders the value in the cell, same as the value returned by [`Cell::get`] would.
+impl<T> View for &Cell<T>
+where
+    T: View + Copy,
+{
+    type Product = T::Product;
+
+    fn build(self, p: In<Self::Product>) -> Out<Self::Product> {
+        self.get().build(p)
+    }
+
+    fn update(self, p: &mut Self::Product) {
+        self.get().update(p)
+    }
+}
+
+/// Renders a reference to the value in the cell, diffed the same way the reference
+/// would be.
+///
+/// ```
+/// # use kobold::prelude::*;
+/// # use std::cell::{Cell, RefCell};
+/// struct Player {
+///     name: RefCell<String>,
+///     score: Cell<u32>,
+/// }
+///
+/// #[component]
+/// fn player(player: &Player) -> impl View + '_ {
+///     view! {
+///         <p>{ &player.name }": "{ &player.score }</p>
+///     }
+/// }
+/// # fn main() {}
+/// ```
+///
+/// Rendering never panics if the cell is mutably borrowed: if that happens on the
+/// first render an empty placeholder is rendered instead of the value, on later
+/// updates the previously rendered value is kept until the next update.
+impl<T, P> View for &RefCell<T>
+where
+    for<'b> &'b T: View<Product = P>,
+    P: Mountable,
+{
+    type Product = Branch2<P, EmptyNode>;
+
+    fn build(self, p: In<Self::Product>) -> Out<Self::Product> {
+        match self.try_borrow() {
+            Ok(value) => Some(&*value).build(p),
+            Err(_) => None::<&T>.build(p),
+        }
+    }
+
+    fn update(self, p: &mut Self::Product) {
+        if let Ok(value) = self.try_borrow() {
+            Some(&*value).update(p)
+        }
+    }
+}
+
+macro_rules! impl_display_view {
+    ($($ty:ty),*) => {
+        $(
+            impl View for $ty {
+                type Product = TextProduct<$ty>;
+
+                fn build(self, p: In<Self::Product>) -> Out<Self::Product> {
+                    p.put(TextProduct {
+                        node: self.to_string().as_str().into_text(),
+                        memo: self,
+                    })
+                }
+
+                fn update(self, p: &mut Self::Product) {
+                    if p.memo != self {
+                        p.memo = self;
+                        self.to_string().as_str().set_prop(TextContent, &p.node);
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_display_view!(IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr);
@@ -0,0 +1,337 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::fmt::Write;
+
+use crate::parse::prelude::*;
+use crate::tokenize::prelude::*;
+use crate::TokenStreamExt;
+use tokens::{Span, TokenStream, TokenTree};
+
+pub fn parse(stream: TokenStream) -> Result<TokenStream, ParseError> {
+    let mut stream = stream.parse_stream();
+
+    let lit = match stream.expect(Lit)? {
+        TokenTree::Literal(lit) => lit,
+        _ => unreachable!(),
+    };
+
+    stream.parse::<()>()?;
+
+    let source = unquote(&lit.to_string())
+        .ok_or_else(|| ParseError::new("Expected a string literal with CSS", lit.span()))?;
+
+    let class = format!("kobold-{:08x}", fnv1a(&source));
+    let css = scope(&source, &class).map_err(|msg| ParseError::new(msg, lit.span()))?;
+
+    let mut class_lit = string(&class);
+    let mut css_lit = string(&css);
+
+    class_lit.set_span(lit.span());
+    css_lit.set_span(Span::call_site());
+
+    Ok(call(
+        "::kobold::attribute::ScopedStyle::new",
+        (class_lit, ',', css_lit),
+    ))
+}
+
+/// Extract the contents of a string literal, handling raw strings and common escapes.
+fn unquote(lit: &str) -> Option<String> {
+    if let Some(raw) = lit.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let inner = raw.get(hashes + 1..raw.len() - hashes - 1)?;
+
+        return Some(inner.to_owned());
+    }
+
+    let inner = lit.strip_prefix('"')?.strip_suffix('"')?;
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+
+        match chars.next()? {
+            'n' => out.push('\n'),
+            't' => out.push('\t'),
+            'r' => out.push('\r'),
+            '0' => out.push('\0'),
+            'x' => {
+                let hex = chars.as_str().get(..2)?;
+
+                out.push(char::from(u8::from_str_radix(hex, 16).ok()?));
+                chars = chars.as_str()[2..].chars();
+            }
+            'u' => {
+                let rest = chars.as_str().strip_prefix('{')?;
+                let end = rest.find('}')?;
+                let code = u32::from_str_radix(&rest[..end].replace('_', ""), 16).ok()?;
+
+                out.push(char::from_u32(code)?);
+                chars = rest[end + 1..].chars();
+            }
+            // Line continuation, skip leading whitespace on the next line
+            '\n' => {
+                let rest = chars.as_str().trim_start();
+                chars = rest.chars();
+            }
+            c => out.push(c),
+        }
+    }
+
+    Some(out)
+}
+
+fn fnv1a(source: &str) -> u32 {
+    source.bytes().fold(0x811c9dc5, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x01000193)
+    })
+}
+
+/// Rewrite all selectors in `css` to be scoped to elements with the `class` class.
+///
+/// * Regular selectors `foo` become descendant selectors `.class foo`.
+/// * `&` refers to the element with the class itself, so `&:hover` becomes `.class:hover`.
+/// * Rules inside `@media`, `@supports`, `@container`, and `@layer` blocks are scoped,
+///   other at-rules such as `@keyframes` or `@font-face` are left as they are.
+fn scope(css: &str, class: &str) -> Result<String, &'static str> {
+    let css = strip_comments(css);
+    let mut out = String::with_capacity(css.len() * 2);
+
+    scope_rules(&css, class, &mut out)?;
+
+    Ok(out)
+}
+
+fn scope_rules(mut css: &str, class: &str, out: &mut String) -> Result<(), &'static str> {
+    loop {
+        css = css.trim_start();
+
+        if css.is_empty() {
+            return Ok(());
+        }
+
+        let open = match find_outside_quotes(css, |c| c == '{' || c == ';') {
+            Some((i, ';')) => {
+                // Statement at-rule such as `@import url(...);`
+                out.push_str(css[..=i].trim());
+                css = &css[i + 1..];
+                continue;
+            }
+            Some((i, _)) => i,
+            None => return Err("Unexpected end of CSS, expected a `{ ... }` block"),
+        };
+
+        let prelude = css[..open].trim();
+        let close = open + matching_brace(&css[open..]).ok_or("Unclosed `{` in CSS")?;
+        let body = &css[open + 1..close];
+
+        css = &css[close + 1..];
+
+        if let Some(at_rule) = prelude.strip_prefix('@') {
+            let name = at_rule
+                .split(|c: char| c.is_whitespace() || c == '(')
+                .next()
+                .unwrap_or("");
+
+            out.push_str(prelude);
+            out.push('{');
+
+            match name {
+                "media" | "supports" | "container" | "layer" => scope_rules(body, class, out)?,
+                _ => out.push_str(body.trim()),
+            }
+
+            out.push('}');
+            continue;
+        }
+
+        if prelude.is_empty() {
+            return Err("Missing selector for CSS rule");
+        }
+
+        for (i, selector) in split_selectors(prelude).enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+
+            if selector.contains('&') {
+                out.push_str(&selector.replace('&', &format!(".{class}")));
+            } else {
+                let _ = write!(out, ".{class} {selector}");
+            }
+        }
+
+        out.push('{');
+        out.push_str(body.trim());
+        out.push('}');
+    }
+}
+
+/// Split a selector list at top-level commas, leaving commas inside of parentheses,
+/// brackets, or quotes such as in `:is(h1, h2)` alone.
+fn split_selectors(prelude: &str) -> impl Iterator<Item = &str> {
+    let mut depth = 0_usize;
+    let mut start = 0;
+    let mut rest = prelude;
+    let mut selectors = Vec::new();
+
+    while let Some((i, c)) = find_outside_quotes(rest, |c| matches!(c, '(' | '[' | ')' | ']' | ','))
+    {
+        let i = prelude.len() - rest.len() + i;
+
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            _ if depth == 0 => {
+                selectors.push(prelude[start..i].trim());
+                start = i + 1;
+            }
+            _ => (),
+        }
+
+        rest = &prelude[i + 1..];
+    }
+
+    selectors.push(prelude[start..].trim());
+    selectors.into_iter()
+}
+
+fn strip_comments(css: &str) -> String {
+    let mut out = String::with_capacity(css.len());
+    let mut rest = css;
+
+    while let Some(start) = rest.find("/*") {
+        out.push_str(&rest[..start]);
+
+        rest = match rest[start + 2..].find("*/") {
+            Some(end) => &rest[start + 2 + end + 2..],
+            None => "",
+        };
+    }
+
+    out.push_str(rest);
+    out
+}
+
+fn find_outside_quotes(css: &str, pattern: impl Fn(char) -> bool) -> Option<(usize, char)> {
+    let mut quote = None;
+
+    for (i, c) in css.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => (),
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if pattern(c) => return Some((i, c)),
+            None => (),
+        }
+    }
+
+    None
+}
+
+/// Find the index of the `}` closing the `{` that `css` starts with.
+fn matching_brace(css: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut offset = 0;
+
+    while let Some((i, c)) = find_outside_quotes(&css[offset..], |c| c == '{' || c == '}') {
+        let i = offset + i;
+
+        match c {
+            '{' => depth += 1,
+            _ => {
+                depth -= 1;
+
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+        }
+
+        offset = i + 1;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn scopes_selectors() {
+        let css = scope("h1, .title > span { color: red; } p{margin:0}", "k").unwrap();
+
+        assert_eq!(css, ".k h1,.k .title > span{color: red;}.k p{margin:0}");
+    }
+
+    #[test]
+    fn ampersand_is_root() {
+        let css = scope(
+            "& { padding: 1em } &:hover, &.active a { color: blue }",
+            "k",
+        )
+        .unwrap();
+
+        assert_eq!(css, ".k{padding: 1em}.k:hover,.k.active a{color: blue}");
+    }
+
+    #[test]
+    fn at_rules() {
+        let css = scope(
+            "@media (max-width: 600px) { p { display: none } } \
+             @keyframes fade { from { opacity: 0 } to { opacity: 1 } }",
+            "k",
+        )
+        .unwrap();
+
+        assert_eq!(
+            css,
+            "@media (max-width: 600px){.k p{display: none}}\
+             @keyframes fade{from { opacity: 0 } to { opacity: 1 }}"
+        );
+    }
+
+    #[test]
+    fn comments_and_quotes() {
+        let css = scope("/* title */ a[title=\"{x}\"] { content: '}' }", "k").unwrap();
+
+        assert_eq!(css, ".k a[title=\"{x}\"]{content: '}'}");
+    }
+
+    #[test]
+    fn nested_selector_lists() {
+        let css = scope(
+            ":is(h1, h2) > a, p:not(.x, .y), [data-list=\"a,b\"] { color: red }",
+            "k",
+        )
+        .unwrap();
+
+        assert_eq!(
+            css,
+            ".k :is(h1, h2) > a,.k p:not(.x, .y),.k [data-list=\"a,b\"]{color: red}"
+        );
+    }
+
+    #[test]
+    fn unclosed_block() {
+        assert!(scope("p { color: red", "k").is_err());
+    }
+
+    #[test]
+    fn unquote_literals() {
+        assert_eq!(unquote(r#""a \"b\"""#).unwrap(), "a \"b\"");
+        assert_eq!(unquote(r##"r#"a "b""#"##).unwrap(), "a \"b\"");
+        assert_eq!(
+            unquote(r#""content: '\u{2192}\x41'""#).unwrap(),
+            "content: '\u{2192}A'"
+        );
+        assert!(unquote(r#""\u{110000}""#).is_none());
+    }
+}
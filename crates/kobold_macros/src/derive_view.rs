@@ -0,0 +1,220 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! `#[derive(View)]` for field-less enums
+
+use std::fmt::Write;
+
+use tokens::{Ident, Literal, TokenStream, TokenTree};
+
+use crate::parse::prelude::*;
+use crate::tokenize::prelude::*;
+
+struct Variant {
+    name: Ident,
+    label: Literal,
+}
+
+pub fn derive(stream: TokenStream) -> Result<TokenStream, ParseError> {
+    let mut stream = stream.parse_stream();
+
+    while stream.allow_consume('#').is_some() {
+        stream.expect('[')?;
+    }
+
+    if stream.allow_consume("pub").is_some() {
+        stream.allow_consume('(');
+    }
+
+    if stream.allow_consume("enum").is_none() {
+        return Err(ParseError::new(
+            "View can only be derived for enums",
+            stream.next(),
+        ));
+    }
+
+    let name: Ident = stream.parse()?;
+
+    if stream.allow('<') {
+        return Err(ParseError::new(
+            "View can't be derived for generic enums",
+            stream.next(),
+        ));
+    }
+
+    let mut body = match stream.expect('{')? {
+        TokenTree::Group(body) => body.stream().parse_stream(),
+        _ => unreachable!(),
+    };
+
+    let mut variants = Vec::new();
+
+    while !body.end() {
+        variants.push(variant(&mut body)?);
+    }
+
+    let mut arms = String::new();
+
+    for Variant {
+        name: variant,
+        label,
+    } in variants.iter()
+    {
+        let _ = write!(arms, "{name}::{variant} => {label},");
+    }
+
+    let view = |ty: &str| {
+        (
+            format_args!("impl ::kobold::View for {ty}"),
+            block((
+                "type Product = <&'static str as ::kobold::View>::Product;",
+                "fn build(self, p: ::kobold::internal::In<Self::Product>) \
+                    -> ::kobold::internal::Out<Self::Product>",
+                block((
+                    "let label: &'static str = match self",
+                    block(arms.as_str()),
+                    "; ::kobold::View::build(label, p)",
+                )),
+                "fn update(self, p: &mut Self::Product)",
+                block((
+                    "let label: &'static str = match self",
+                    block(arms.as_str()),
+                    "; ::kobold::View::update(label, p)",
+                )),
+            )),
+        )
+            .tokenize()
+    };
+
+    Ok((view(&name.to_string()), view(&format!("&{name}"))).tokenize())
+}
+
+fn variant(stream: &mut ParseStream) -> Result<Variant, ParseError> {
+    let mut label = None;
+
+    while let Some(hash) = stream.allow_consume('#') {
+        let TokenTree::Group(attr) = stream.expect('[')? else {
+            unreachable!();
+        };
+
+        let mut attr = attr.stream().parse_stream();
+
+        if attr.allow_consume("view").is_none() {
+            continue;
+        }
+
+        let TokenTree::Group(args) = attr.expect('(')? else {
+            unreachable!();
+        };
+
+        let mut args = args.stream().parse_stream();
+
+        args.expect("label")?;
+        args.expect('=')?;
+
+        let lit = args.expect(Lit)?;
+
+        if !lit.to_string().starts_with('"') {
+            return Err(ParseError::new("Expected a label string", lit));
+        }
+
+        args.parse::<()>()?;
+
+        let TokenTree::Literal(lit) = lit else {
+            unreachable!();
+        };
+
+        if label.replace(lit).is_some() {
+            return Err(ParseError::new(
+                "Variant can only have one #[view(label)] attribute",
+                hash,
+            ));
+        }
+    }
+
+    let name: Ident = stream.parse()?;
+
+    match stream.next() {
+        None => (),
+        Some(tt) if tt.is(',') => (),
+        Some(tt) if tt.is('=') => {
+            // Explicit discriminants don't matter for rendering
+            for tt in stream.by_ref() {
+                if tt.is(',') {
+                    break;
+                }
+            }
+        }
+        Some(tt) => {
+            return Err(ParseError::new(
+                "View can only be derived for enums without fields",
+                tt,
+            ))
+        }
+    }
+
+    let label = label.unwrap_or_else(|| {
+        let name = name.to_string();
+
+        Literal::string(name.strip_prefix("r#").unwrap_or(&name))
+    });
+
+    Ok(Variant { name, label })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn derive_str(input: &str) -> Result<String, String> {
+        derive(input.parse().unwrap())
+            .map(|out| out.to_string())
+            .map_err(|err| err.msg.to_string())
+    }
+
+    #[test]
+    fn status_enum() {
+        let out = derive_str(
+            r#"
+            /// Status of an account
+            #[derive(Clone, Copy)]
+            pub enum Status {
+                Active,
+                #[view(label = "On hold")]
+                Paused = 4,
+                Closed,
+            }
+            "#,
+        )
+        .unwrap();
+
+        assert!(out.starts_with("impl :: kobold :: View for Status"));
+        assert!(out.contains("impl :: kobold :: View for & Status"));
+        assert!(out.contains(r#"Status :: Active => "Active" ,"#));
+        assert!(out.contains(r#"Status :: Paused => "On hold" ,"#));
+        assert!(out.contains(r#"Status :: Closed => "Closed" ,"#));
+    }
+
+    #[test]
+    fn invalid_input() {
+        let err = derive_str("struct Status;").unwrap_err();
+        assert!(err.contains("only be derived for enums"));
+
+        let err = derive_str("enum Status<T> { A(T) }").unwrap_err();
+        assert!(err.contains("generic"));
+
+        let err = derive_str("enum Status { Active(u8) }").unwrap_err();
+        assert!(err.contains("without fields"));
+
+        let err = derive_str("enum Status { #[view(label = 1)] Active }").unwrap_err();
+        assert!(err.contains("label string"));
+
+        let err = derive_str(r#"enum Status { #[view(label = "a")] #[view(label = "b")] Active }"#)
+            .unwrap_err();
+        assert!(err.contains("only have one"));
+
+        let err = derive_str(r#"enum Status { #[view("On hold")] Paused }"#).unwrap_err();
+        assert!(err.contains("label"));
+    }
+}
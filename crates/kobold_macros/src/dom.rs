@@ -2,7 +2,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use tokens::{Delimiter, Ident, Literal, Span, TokenStream, TokenTree};
+use tokens::{Delimiter, Ident, Literal, Spacing, Span, TokenStream, TokenTree};
 
 use crate::parse::prelude::*;
 use crate::syntax::CssLabel;
@@ -40,10 +40,14 @@ pub enum Node {
 
 #[derive(Debug)]
 pub struct Component {
+    #[allow(dead_code)]
     pub name: String,
     pub span: Span,
     pub path: TokenStream,
     pub generics: Option<TokenStream>,
+    pub render: Option<Ident>,
+    /// Arguments passed to the render function after the props, as in `<!chart.view(ctx)>`
+    pub render_args: Option<TokenStream>,
     pub props: Vec<Property>,
     pub children: Option<Vec<Node>>,
 }
@@ -51,6 +55,7 @@ pub struct Component {
 #[derive(Debug)]
 pub struct HtmlElement {
     pub name: ElementTag,
+    #[allow(dead_code)]
     pub span: Span,
     pub classes: Vec<CssValue>,
     pub attributes: Vec<Attribute>,
@@ -80,6 +85,8 @@ pub enum AttributeValue {
     Literal(Literal),
     Boolean(Ident),
     Expression(Expression),
+    /// `name=?{expr}`, attribute is only present for `true` or `Some(_)`
+    Optional(Expression),
 }
 
 impl From<Expression> for AttributeValue {
@@ -97,7 +104,7 @@ impl Node {
                 return Ok(1);
             }
             Some(Ok(ShallowNode::Expression(expr))) => {
-                parent.push(Expression::try_from(expr)?.into());
+                parent.push(Expression::child(expr)?.into());
                 return Ok(1);
             }
             Some(Err(error)) => return Err(error),
@@ -108,19 +115,42 @@ impl Node {
             TagNesting::SelfClosing => Children::None,
             TagNesting::Opening => Node::parse_children(&tag.name, stream)?,
             TagNesting::Closing => {
-                return Err(ParseError::new(
-                    format!("Unexpected closing tag {}", tag.name),
-                    tag.name,
-                ));
+                let msg = match tag.name {
+                    TagName::HtmlElement { name, .. } if name.forbids_children() => {
+                        format!(
+                            "<{}> is a void element and can't have a closing tag",
+                            tag.name
+                        )
+                    }
+                    TagName::Fragment { .. } => "Unexpected closing tag </>".into(),
+                    _ => format!("Unexpected closing tag {}", tag.name),
+                };
+
+                return Err(ParseError::new(msg, tag.name));
             }
         };
 
         match tag.name {
+            TagName::Fragment { span } => match children {
+                Children::Explicit(children) => {
+                    let count = children.len();
+
+                    // Fragments don't produce any nodes of their own, their children
+                    // are appended to the parent directly
+                    parent.extend(children);
+
+                    Ok(count)
+                }
+                Children::None => Err(ParseError::new("Empty fragment <></>", span)),
+                Children::Implicit(_) => Err(ParseError::new("Missing closing tag </>", span)),
+            },
             TagName::Component {
                 name,
                 span,
                 path,
                 generics,
+                render,
+                render_args,
             } => {
                 let mut content = tag.content.parse_stream();
                 let mut props = Vec::new();
@@ -142,6 +172,8 @@ impl Node {
                     span,
                     path,
                     generics,
+                    render,
+                    render_args,
                     props,
                     children,
                 }));
@@ -163,12 +195,14 @@ impl Node {
                             label: "id".into(),
                             ident: Ident::new("id", hash.span()),
                         };
-                        let value: CssValue = content.parse()?;
 
-                        attributes.push(Attribute {
-                            name,
-                            value: value.into(),
-                        })
+                        // Unlike classes, `#{expr}` takes any expression as is
+                        let value = match content.allow_consume('{') {
+                            Some(expr) => AttributeValue::Expression(Expression::try_from(expr)?),
+                            None => content.parse::<CssValue>()?.into(),
+                        };
+
+                        attributes.push(Attribute { name, value })
                     } else {
                         break;
                     }
@@ -189,6 +223,10 @@ impl Node {
                     Children::Explicit(children) | Children::Implicit(children) => Some(children),
                 };
 
+                if let (ElementTag::Template, Some(children)) = (name, &children) {
+                    check_inert(children)?;
+                }
+
                 parent.push(Node::HtmlElement(HtmlElement {
                     name,
                     span,
@@ -236,6 +274,42 @@ impl Node {
     }
 }
 
+/// Contents of a `<template>` element are never activated, so they can only be
+/// static markup.
+fn check_inert(nodes: &[Node]) -> Result<(), ParseError> {
+    const MSG: &str = "Contents of <template> are inert and can't contain";
+
+    for node in nodes {
+        match node {
+            Node::Text(_) => (),
+            Node::Expression(expr) => {
+                return Err(ParseError::new(format!("{MSG} expressions"), expr.span));
+            }
+            Node::Component(comp) => {
+                return Err(ParseError::new(format!("{MSG} components"), comp.span));
+            }
+            Node::HtmlElement(el) => {
+                for class in &el.classes {
+                    if let CssValue::Expression(expr) = class {
+                        return Err(ParseError::new(format!("{MSG} expressions"), expr.span));
+                    }
+                }
+                for attr in &el.attributes {
+                    if let AttributeValue::Expression(expr) | AttributeValue::Optional(expr) =
+                        &attr.value
+                    {
+                        return Err(ParseError::new(format!("{MSG} expressions"), expr.span));
+                    }
+                }
+                if let Some(children) = &el.children {
+                    check_inert(children)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
 enum Children {
     None,
     Explicit(Vec<Node>),
@@ -307,6 +381,12 @@ impl CssValue {
 impl Parse for CssValue {
     fn parse(stream: &mut ParseStream) -> Result<Self, ParseError> {
         if let Some(expr) = stream.allow_consume('{') {
+            if let Some(toggle) = class_toggle(&expr) {
+                return Ok(CssValue::Expression(toggle));
+            }
+            if let Some(list) = class_list(&expr) {
+                return Ok(CssValue::Expression(list));
+            }
             return Ok(CssValue::Expression(Expression::try_from(expr)?));
         }
 
@@ -318,6 +398,61 @@ impl Parse for CssValue {
     }
 }
 
+/// Turn the `{condition => "class"}` shorthand into a `class!("class" if condition)` call.
+fn class_toggle(tt: &TokenTree) -> Option<Expression> {
+    let TokenTree::Group(group) = tt else {
+        return None;
+    };
+
+    let tokens: Vec<TokenTree> = group.stream().into_iter().collect();
+
+    let [condition @ .., TokenTree::Punct(eq), TokenTree::Punct(gt), TokenTree::Literal(class)] =
+        &tokens[..]
+    else {
+        return None;
+    };
+
+    if condition.is_empty()
+        || eq.as_char() != '='
+        || eq.spacing() != Spacing::Joint
+        || gt.as_char() != '>'
+        || !class.to_string().starts_with('"')
+    {
+        return None;
+    }
+
+    let condition: TokenStream = condition.iter().cloned().collect();
+
+    Some(Expression {
+        stream: call("::kobold::class!", (class.clone(), "if", condition)),
+        span: group.span(),
+        is_static: false,
+    })
+}
+
+/// Turn the `{[first, second, ..]}` shorthand into a `classes([first, second, ..])` call.
+fn class_list(tt: &TokenTree) -> Option<Expression> {
+    let TokenTree::Group(group) = tt else {
+        return None;
+    };
+
+    let mut tokens = group.stream().into_iter();
+
+    let (Some(TokenTree::Group(list)), None) = (tokens.next(), tokens.next()) else {
+        return None;
+    };
+
+    if list.delimiter() != Delimiter::Bracket {
+        return None;
+    }
+
+    Some(Expression {
+        stream: call("::kobold::attribute::classes", TokenTree::Group(list)),
+        span: group.span(),
+        is_static: false,
+    })
+}
+
 impl TryFrom<AttributeValue> for CssValue {
     type Error = ParseError;
 
@@ -329,6 +464,10 @@ impl TryFrom<AttributeValue> for CssValue {
                 "Cannot assign bool to this attribute",
                 b.span(),
             )),
+            AttributeValue::Optional(expr) => Err(ParseError::new(
+                "This attribute can't be conditional, use {expression} instead of ?{expression}",
+                expr.span,
+            )),
         }
     }
 }
@@ -369,10 +508,52 @@ impl Parse for Attribute {
             ));
         }
 
-        let name = stream.parse()?;
+        let mut name: CssLabel = stream.parse()?;
+
+        // Namespaced attributes, such as `xlink:href`
+        if stream.allow_consume(':').is_some() {
+            let local: CssLabel = stream.parse()?;
+
+            name.label = format!("{}:{}", name.label, local.label);
+            name.ident = local.ident;
+        }
 
         stream.expect('=')?;
 
+        // The keyboard handling of `interactive` is generated at compile time
+        if name.label == "interactive" {
+            return match stream.next() {
+                Some(TokenTree::Ident(b)) if b.one_of(["true", "false"]) => Ok(Attribute {
+                    name,
+                    value: AttributeValue::Boolean(b),
+                }),
+                _ => Err(ParseError::new(
+                    "The interactive attribute can only be set to `true` or `false`",
+                    name.ident.span(),
+                )),
+            };
+        }
+
+        if let Some(question) = stream.allow_consume('?') {
+            if name.label.starts_with("on") {
+                return Err(ParseError::new(
+                    "Event listeners can't be conditional",
+                    question.span(),
+                ));
+            }
+
+            return match stream.next() {
+                Some(tt) if tt.is('{') => Ok(Attribute {
+                    name,
+                    value: AttributeValue::Optional(Expression::try_from(tt)?),
+                }),
+                _ => Err(ParseError::new(
+                    "Conditional attributes must contain an ?{expression}",
+                    question.span(),
+                )),
+            };
+        }
+
         match stream.next() {
             Some(TokenTree::Literal(lit)) => Ok(Attribute {
                 name,
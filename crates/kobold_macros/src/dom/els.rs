@@ -263,4 +263,5 @@ build_tags! {
 	SvgPolygon  "polygon" : "http://www.w3.org/2000/svg" ForbidsChildren;
 	SvgEllipse  "ellipse" : "http://www.w3.org/2000/svg" ForbidsChildren;
 	SvgText     "text"    : "http://www.w3.org/2000/svg" __;
+	SvgUse      "use"     : "http://www.w3.org/2000/svg" ForbidsChildren;
 }
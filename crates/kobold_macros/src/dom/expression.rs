@@ -4,14 +4,15 @@
 
 use std::fmt::{self, Debug};
 
-use tokens::{Group, Ident, Span, TokenStream, TokenTree};
+use tokens::{Delimiter, Group, Ident, Span, TokenStream, TokenTree};
 
 use crate::dom::{IteratorExt, Lit, Node, ParseError};
-use crate::parse::IdentExt;
+use crate::parse::{IdentExt, TokenTreeExt};
 use crate::tokenize::prelude::*;
 
 pub struct Expression {
     pub stream: TokenStream,
+    #[allow(dead_code)]
     pub span: Span,
     pub is_static: bool,
 }
@@ -33,7 +34,7 @@ impl TryFrom<TokenTree> for Expression {
 
     fn try_from(tt: TokenTree) -> Result<Self, ParseError> {
         if let TokenTree::Group(group) = tt {
-            return Ok(Expression::try_from(group)?);
+            return Expression::try_from(group);
         }
 
         let span = tt.span();
@@ -83,14 +84,12 @@ impl TryFrom<Group> for Expression {
             if let Some(mut keyword) = keyword {
                 stream.next();
 
-                if keyword == "for" {
-                    if let Some(_) = stream.allow_consume('<') {
-                        let n = stream.expect(Lit)?;
-                        let close = stream.expect('>')?;
+                if keyword == "for" && stream.allow_consume('<').is_some() {
+                    let n = stream.expect(Lit)?;
+                    let close = stream.expect('>')?;
 
-                        keyword = "for_bounded";
-                        invoke = Some(("::<_, ", n, close).tokenize())
-                    }
+                    keyword = "for_bounded";
+                    invoke = Some(("::<_, ", n, close).tokenize())
                 }
                 let keyword = Ident::new_raw(keyword, span);
 
@@ -113,6 +112,37 @@ impl TryFrom<Group> for Expression {
     }
 }
 
+impl Expression {
+    /// Parse an expression in the child position, where a leading `?` such as
+    /// `{ ?entry }` renders an `Option` only if it's `Some`. No Rust expression starts
+    /// with a `?`, so unlike a trailing one it can't be confused with the `?` operator.
+    pub fn child(group: Group) -> Result<Self, ParseError> {
+        let mut tokens = group.stream().into_iter().peekable();
+
+        if tokens.next_if(|tt| tt.is('?')).is_none() {
+            return Expression::try_from(group);
+        }
+
+        if tokens.peek().is_none() {
+            return Err(ParseError::new(
+                "Expected an `Option` to render after `?`",
+                group.span(),
+            ));
+        }
+
+        let mut inner = Group::new(Delimiter::Brace, tokens.collect());
+
+        inner.set_span(group.span());
+
+        let expr = Expression::try_from(inner)?;
+
+        Ok(Expression {
+            stream: call("::kobold::keywords::optional", expr.stream),
+            ..expr
+        })
+    }
+}
+
 impl From<&str> for Expression {
     fn from(code: &str) -> Self {
         Expression {
@@ -73,6 +73,14 @@ pub enum TagName {
         span: Span,
         path: TokenStream,
         generics: Option<TokenStream>,
+        /// Render function other than `render`, as in `<!chart.view>`
+        render: Option<Ident>,
+        /// Extra arguments of the render function, as in `<!chart.view(ctx)>`
+        render_args: Option<TokenStream>,
+    },
+    /// Explicit fragment `<>...</>`
+    Fragment {
+        span: Span,
     },
 }
 
@@ -81,6 +89,7 @@ impl PartialEq for TagName {
         match (self, other) {
             (TagName::HtmlElement { name: l, .. }, TagName::HtmlElement { name: r, .. }) => l == r,
             (TagName::Component { name: l, .. }, TagName::Component { name: r, .. }) => l == r,
+            (TagName::Fragment { .. }, TagName::Fragment { .. }) => true,
             _ => false,
         }
     }
@@ -91,13 +100,14 @@ impl TagName {
         match self {
             TagName::HtmlElement { span, .. } => *span,
             TagName::Component { span, .. } => *span,
+            TagName::Fragment { span } => *span,
         }
     }
 
     pub fn forbids_children(&self) -> bool {
         match self {
             TagName::HtmlElement { name, .. } => name.forbids_children(),
-            TagName::Component { .. } => false,
+            TagName::Component { .. } | TagName::Fragment { .. } => false,
         }
     }
 }
@@ -113,6 +123,7 @@ impl Display for TagName {
         let name = match self {
             TagName::HtmlElement { name, .. } => name,
             TagName::Component { name, .. } => name.as_str(),
+            TagName::Fragment { .. } => "",
         };
 
         f.write_str(name)
@@ -156,11 +167,24 @@ impl Parse for TagName {
             generics = Some(Generics::parse(stream)?.tokens);
         }
 
+        let mut render = None;
+        let mut render_args = None;
+
+        if stream.allow_consume('.').is_some() {
+            render = Some(stream.parse()?);
+
+            if let Some(TokenTree::Group(args)) = stream.allow_consume('(') {
+                render_args = Some(args.stream());
+            }
+        }
+
         Ok(TagName::Component {
             name,
             span,
             path,
             generics,
+            render,
+            render_args,
         })
     }
 }
@@ -214,13 +238,27 @@ impl Tag {
 
 impl Parse for Tag {
     fn parse(stream: &mut ParseStream) -> Result<Self, ParseError> {
-        stream.expect('<')?;
+        let open = stream.expect('<')?;
+
+        if stream.allow_consume('>').is_some() {
+            return Ok(Tag {
+                name: TagName::Fragment { span: open.span() },
+                nesting: TagNesting::Opening,
+                content: TokenStream::new(),
+            });
+        }
 
         let mut nesting = match stream.allow_consume('/') {
             Some(_) => {
-                let name = stream.parse()?;
-
-                stream.expect('>')?;
+                let name = match stream.allow_consume('>') {
+                    Some(_) => TagName::Fragment { span: open.span() },
+                    None => {
+                        let name = stream.parse()?;
+
+                        stream.expect('>')?;
+                        name
+                    }
+                };
 
                 return Ok(Tag {
                     name,
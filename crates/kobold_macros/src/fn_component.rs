@@ -20,6 +20,9 @@ use generic_finder::GenericFinder;
 pub struct ComponentArgs {
     branching: Option<Ident>,
     children: Option<Ident>,
+    memo: Option<Ident>,
+    render: Option<Ident>,
+    render_args: Vec<Ident>,
     defaults: Vec<(Ident, Value)>,
 }
 
@@ -56,6 +59,8 @@ pub fn args(stream: TokenStream) -> Result<ComponentArgs, ParseError> {
     enum Token {
         Children,
         AutoBranch,
+        Memo,
+        Render,
         Default,
     }
 
@@ -68,8 +73,10 @@ pub fn args(stream: TokenStream) -> Result<ComponentArgs, ParseError> {
             ident.with_str(|s| match s {
                 "children" => Ok(Token::Children),
                 "auto_branch" => Ok(Token::AutoBranch),
+                "memo" => Ok(Token::Memo),
+                "render" => Ok(Token::Render),
                 _ => Err(ParseError::new(
-                    "Unknown attribute, allowed: `auto_branch`, `children`, or `<parameter>?`",
+                    "Unknown attribute, allowed: `auto_branch`, `children`, `memo`, `render`, or `<parameter>?`",
                     ident.span(),
                 )),
             })?
@@ -77,6 +84,41 @@ pub fn args(stream: TokenStream) -> Result<ComponentArgs, ParseError> {
 
         match token {
             Token::AutoBranch => args.branching = Some(ident),
+            Token::Memo => args.memo = Some(ident),
+            Token::Render => {
+                stream.expect('=')?;
+
+                // Accept both `render = view` and `render = "view"`
+                let name = match stream.next() {
+                    Some(TokenTree::Ident(name)) => name,
+                    Some(TokenTree::Literal(lit)) => {
+                        let name = lit.to_string();
+
+                        match name.strip_prefix('"').and_then(|n| n.strip_suffix('"')) {
+                            Some(name) if !name.is_empty() => Ident::new(name, lit.span()),
+                            _ => {
+                                return Err(ParseError::new("Expected a function name", lit.span()))
+                            }
+                        }
+                    }
+                    tt => return Err(ParseError::new("Expected a function name", tt)),
+                };
+
+                args.render = Some(name);
+
+                // Arguments passed to the render function instead of as props
+                if let Some(TokenTree::Group(group)) = stream.allow_consume('(') {
+                    let mut stream = group.stream().parse_stream();
+
+                    while !stream.end() {
+                        args.render_args.push(stream.parse()?);
+
+                        if !stream.end() {
+                            stream.expect(',')?;
+                        }
+                    }
+                }
+            }
             Token::Children => {
                 args.children = Some(ident);
 
@@ -138,8 +180,15 @@ struct FnComponent {
     generics: Option<Generics>,
     raw_args: Option<Group>,
     arguments: Vec<Argument>,
+    /// Arguments of the render function following the props
+    extra: Vec<Argument>,
+    /// Names of all arguments in the order of the component function
+    call_args: Vec<Ident>,
     ret: TokenStream,
     render: TokenStream,
+    /// Name of the generated render function, `render` unless renamed
+    render_fn: Ident,
+    memo: bool,
 }
 
 impl FnComponent {
@@ -185,12 +234,47 @@ impl FnComponent {
             ));
         }
 
+        let call_args = fun.arguments.iter().map(|arg| arg.name.clone()).collect();
+        let mut extra = Vec::new();
+
+        for name in args.render_args.drain(..) {
+            temp_var.clear();
+
+            let _ = write!(temp_var, "{name}");
+
+            let Some(idx) = fun
+                .arguments
+                .iter()
+                .position(|arg| arg.name.eq_str(&temp_var))
+            else {
+                return Err(ParseError::new(
+                    format!("Parameter `{name}` missing in the component `{}`", fun.name),
+                    name.span(),
+                ));
+            };
+
+            let arg = fun.arguments.remove(idx);
+
+            if arg.default.is_some() || temp_var == "children" {
+                return Err(ParseError::new(
+                    format!("Parameter `{name}` can't be both a prop and a render argument"),
+                    name.span(),
+                ));
+            }
+
+            extra.push(arg);
+        }
+
         let render = match fun.body {
             TokenTree::Group(group) => group.stream(),
             tt => tt.into(),
         };
 
         let r#mod = Ident::new("mod", fun.r#fn.span());
+        let render_fn = args
+            .render
+            .take()
+            .unwrap_or_else(|| Ident::new("render", fun.name.span()));
 
         Ok(FnComponent {
             r#fn: fun.r#fn,
@@ -200,8 +284,12 @@ impl FnComponent {
             generics: fun.generics,
             raw_args: fun.raw_args,
             arguments: fun.arguments,
+            extra,
+            call_args,
             ret: fun.r#return,
             render,
+            render_fn,
+            memo: args.memo.is_some(),
         })
     }
 }
@@ -298,7 +386,7 @@ impl Tokenize for FnComponent {
         };
 
         let args = if self.arguments.is_empty() {
-            "_: Props".tokenize()
+            "_: Props,".tokenize()
         } else {
             let destruct = (
                 "Props",
@@ -306,7 +394,7 @@ impl Tokenize for FnComponent {
             );
             let props_ty = ("Props<", each(self.arguments.iter().map(Argument::ty)), '>');
 
-            (destruct, ':', props_ty).tokenize()
+            (destruct, ':', props_ty, ',').tokenize()
         };
 
         mo.write("#[allow(non_camel_case_types)] pub struct Props");
@@ -322,17 +410,48 @@ impl Tokenize for FnComponent {
             ));
         };
 
+        let args = (args, each(self.extra.iter().map(Argument::param)));
+
+        let render_call = call(
+            ("super::", name),
+            each(self.call_args.iter().map(|arg| (arg, ','))),
+        );
+
+        // Components can return types other than views, such as closures
+        // used as children, those can't be profiled
+        let render_call = if returns_impl_view(self.ret.clone()) {
+            call(
+                "::kobold::profiling::render",
+                (string(&name.to_string()), ", move ||", render_call),
+            )
+        } else {
+            render_call
+        };
+
+        let render_call = if self.memo {
+            // Skip updates unless any of the arguments has changed
+            call(
+                "::kobold::diff::fence",
+                (
+                    group('(', each(self.call_args.iter().map(|arg| (arg, ',')))),
+                    ", move ||",
+                    render_call,
+                ),
+            )
+            .tokenize()
+        } else {
+            render_call
+        };
+
         let fn_render = (
-            "pub fn render",
+            "pub fn",
+            self.render_fn,
             self.generics.clone(),
             group('(', args),
             self.ret.clone(),
             block((
                 each(self.arguments.iter().map(Argument::maybe)),
-                call(
-                    ("super::", name),
-                    each(self.arguments.iter().map(Argument::name)),
-                ),
+                render_call,
             )),
         );
 
@@ -398,6 +517,10 @@ impl Argument {
         (&self.name, ',')
     }
 
+    fn param(&self) -> impl Tokenize + '_ {
+        (&self.name, ':', &self.ty, ',')
+    }
+
     fn generic(&self) -> impl Tokenize + '_ {
         (&self.name, "= ::kobold::maybe::Undefined,")
     }
@@ -481,8 +604,97 @@ impl Argument {
     }
 }
 
+/// Check whether the return type is `impl View`, allowing for a path to the trait
+/// and extra bounds such as lifetimes.
+fn returns_impl_view(ret: TokenStream) -> bool {
+    let mut tokens = ret.into_iter().skip_while(|tt| !tt.is("impl")).skip(1);
+    let mut last = None;
+
+    for tt in tokens.by_ref() {
+        match tt {
+            TokenTree::Ident(_) => last = Some(tt),
+            TokenTree::Punct(ref p) if p.as_char() == ':' => (),
+            _ => break,
+        }
+    }
+
+    last.is("View")
+}
+
 impl Tokenize for Argument {
     fn tokenize_in(self, stream: &mut TokenStream) {
         stream.write((self.name, ':', self.ty, ','))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn expand(args: &str, input: &str) -> Result<String, String> {
+        let args = self::args(args.parse().unwrap()).map_err(|err| err.msg.to_string())?;
+
+        component(args, input.parse().unwrap())
+            .map(|out| out.to_string())
+            .map_err(|err| err.msg.to_string())
+    }
+
+    #[test]
+    fn renamed_render_function() {
+        let input = "fn chart(points: &[f64]) -> impl View + '_ { view! { <p>\"Chart\"</p> } }";
+
+        let out = expand("", input).unwrap();
+        assert!(out.contains("pub fn render"));
+
+        for args in ["render = view", "render = \"view\"", "memo, render = view"] {
+            let out = expand(args, input).unwrap();
+
+            assert!(out.contains("pub fn view"), "{args}");
+            assert!(!out.contains("pub fn render"), "{args}");
+        }
+
+        assert!(expand("render", input).is_err());
+        assert!(expand("render = \"\"", input).is_err());
+    }
+
+    #[test]
+    fn profile_impl_view_only() {
+        let returns = |ret: &str| returns_impl_view(ret.parse().unwrap());
+
+        assert!(returns("-> impl View"));
+        assert!(returns("-> impl View + '_"));
+        assert!(returns("-> impl kobold::View + 'a"));
+        assert!(!returns("-> impl Fn(u32) -> View"));
+        assert!(!returns("-> impl Fn(PreviewView) -> u32"));
+        assert!(!returns("-> ViewState"));
+        assert!(!returns(""));
+    }
+
+    #[test]
+    fn render_function_arguments() {
+        let input = "fn chart<'a>(title: &'a str, theme: &'a Theme) -> impl View + 'a { title }";
+
+        let out = expand("render = view(theme)", input).unwrap();
+
+        assert!(out.contains("pub struct Props < title = :: kobold :: maybe :: Undefined , >"));
+        assert!(out.contains("theme : &'a Theme ,"));
+        assert!(out.contains("super :: chart (title , theme ,)"));
+
+        assert!(expand("render = view(missing)", input).is_err());
+        assert!(expand("title?, render = view(title)", input).is_err());
+    }
+
+    #[test]
+    fn call_renamed_render_function() {
+        let nodes = crate::dom::parse("<!chart.view points={data}>".parse().unwrap()).unwrap();
+        let out = crate::gen::generate(nodes).tokenize().to_string();
+
+        assert!(out.contains("chart :: view (chart :: props () . points (data))"));
+
+        let nodes =
+            crate::dom::parse("<!chart.view(&theme) points={data}>".parse().unwrap()).unwrap();
+        let out = crate::gen::generate(nodes).tokenize().to_string();
+
+        assert!(out.contains("chart :: view (chart :: props () . points (data) , & theme)"));
+    }
+}
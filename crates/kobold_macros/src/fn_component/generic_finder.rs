@@ -25,12 +25,14 @@ impl Parse for Generic {
     fn parse(stream: &mut ParseStream) -> Result<Self, ParseError> {
         let lifetime = stream.allow_consume('\'').is_some();
 
+        if !lifetime {
+            stream.allow_consume("const");
+        }
+
         let ident: Ident = stream.parse()?;
 
         if stream.allow_consume(':').is_some() {
-            while !stream.allow(',') {
-                stream.next();
-            }
+            skip_bounds(stream);
         }
 
         stream.allow_consume(',');
@@ -45,6 +47,30 @@ impl Parse for Generic {
     }
 }
 
+/// Skip tokens up to the `,` or `>` ending the bounds of a generic, keeping track
+/// of nested generics and arrows such as `Fn(&T) -> V`.
+fn skip_bounds(stream: &mut ParseStream) {
+    let mut depth = 0_usize;
+    let mut arrow = false;
+
+    while let Some(tt) = stream.peek() {
+        let close = tt.is('>') && !arrow;
+
+        if depth == 0 && (close || tt.is(',')) {
+            break;
+        }
+
+        if tt.is('<') {
+            depth += 1;
+        } else if close {
+            depth -= 1;
+        }
+
+        arrow = tt.is('-');
+        stream.next();
+    }
+}
+
 pub struct GenericFinder {
     generics: Vec<(bool, Generic)>,
 }
@@ -56,14 +82,10 @@ impl Parse for GenericFinder {
         // skip opening <
         stream.next();
 
-        loop {
+        while stream.allow_consume('>').is_none() && !stream.end() {
             let gen = stream.parse()?;
 
             out.push((false, gen));
-
-            if stream.allow_consume('>').is_some() {
-                break;
-            }
         }
 
         Ok(GenericFinder::new(out))
@@ -120,3 +142,30 @@ impl GenericFinder {
         GenericFinder { generics }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(generics: &str, ty: &str) -> Vec<String> {
+        let stream: TokenStream = generics.parse().unwrap();
+        let mut finder: GenericFinder = stream.parse_stream().parse().unwrap();
+
+        finder
+            .in_type(&ty.parse().unwrap())
+            .map(|gen| match gen {
+                Generic::Lifetime(lt) => format!("'{lt}"),
+                Generic::Type(ty) => ty.to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn inline_bounds() {
+        let generics = "<'a, T: View, F: Fn(&T) -> V + 'a, V: Into<Vec<T>>, const N: usize>";
+
+        assert_eq!(find(generics, "&'a [T; N]"), ["'a", "T", "N"]);
+        assert_eq!(find(generics, "F"), ["F"]);
+        assert_eq!(find(generics, "Option<V>"), ["V"]);
+    }
+}
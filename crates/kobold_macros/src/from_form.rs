@@ -0,0 +1,266 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! `#[derive(FromForm)]` for structs
+
+use std::fmt::Write;
+
+use tokens::{Ident, Literal, TokenStream, TokenTree};
+
+use crate::parse::prelude::*;
+use crate::tokenize::prelude::*;
+
+struct Field {
+    ident: Ident,
+    /// Name of the form field
+    name: String,
+    reader: &'static str,
+}
+
+pub fn derive(stream: TokenStream) -> Result<TokenStream, ParseError> {
+    let mut stream = stream.parse_stream();
+
+    while stream.allow_consume('#').is_some() {
+        stream.expect('[')?;
+    }
+
+    if stream.allow_consume("pub").is_some() {
+        stream.allow_consume('(');
+    }
+
+    if stream.allow_consume("struct").is_none() {
+        return Err(ParseError::new(
+            "FromForm can only be derived for structs",
+            stream.next(),
+        ));
+    }
+
+    let name: Ident = stream.parse()?;
+
+    if stream.allow('<') {
+        return Err(ParseError::new(
+            "FromForm can't be derived for generic structs",
+            stream.next(),
+        ));
+    }
+
+    let mut body = match stream.next() {
+        Some(TokenTree::Group(body)) if body.delimiter() == tokens::Delimiter::Brace => {
+            body.stream().parse_stream()
+        }
+        tt => {
+            return Err(ParseError::new(
+                "FromForm can only be derived for structs with named fields",
+                tt,
+            ))
+        }
+    };
+
+    let mut fields = Vec::new();
+
+    while !body.end() {
+        fields.push(field(&mut body)?);
+    }
+
+    let mut code = String::new();
+    let mut vars = String::new();
+    let mut init = String::new();
+
+    for (
+        n,
+        Field {
+            ident,
+            name,
+            reader,
+        },
+    ) in fields.iter().enumerate()
+    {
+        let name = Literal::string(name);
+
+        let _ = write!(
+            code,
+            "let __f{n} = ::kobold::form::{reader}(__fields, {name})\
+                .map_err(|err| __errors.push(err))\
+                .ok();"
+        );
+        let _ = write!(vars, "__f{n},");
+        let _ = write!(init, "{ident}: __f{n},");
+    }
+
+    let result = if fields.is_empty() {
+        format!("let _ = (__fields, __errors); ::std::result::Result::Ok({name} {{}})")
+    } else {
+        let some = fields
+            .iter()
+            .enumerate()
+            .fold(String::new(), |mut some, (n, _)| {
+                let _ = write!(some, "::std::option::Option::Some(__f{n}),");
+                some
+            });
+
+        format!(
+            "match ({vars}) {{\
+                ({some}) => ::std::result::Result::Ok({name} {{ {init} }}),\
+                _ => ::std::result::Result::Err(::kobold::form::FormError::new(__errors)),\
+            }}"
+        )
+    };
+
+    Ok((
+        format_args!("impl ::kobold::form::FromForm for {name}"),
+        block((
+            "fn from_fields(\
+                __fields: &[(::std::string::String, ::std::string::String)]\
+            ) -> ::std::result::Result<Self, ::kobold::form::FormError>",
+            block((
+                "let mut __errors = ::std::vec::Vec::new();",
+                code.as_str(),
+                result.as_str(),
+            )),
+        )),
+    )
+        .tokenize())
+}
+
+fn field(stream: &mut ParseStream) -> Result<Field, ParseError> {
+    let mut rename = None;
+
+    while stream.allow_consume('#').is_some() {
+        let TokenTree::Group(attr) = stream.expect('[')? else {
+            unreachable!();
+        };
+
+        let mut attr = attr.stream().parse_stream();
+
+        if attr.allow_consume("form").is_none() {
+            continue;
+        }
+
+        let TokenTree::Group(args) = attr.expect('(')? else {
+            unreachable!();
+        };
+
+        let mut args = args.stream().parse_stream();
+
+        args.expect("name")?;
+        args.expect('=')?;
+
+        let lit = args.expect(Lit)?;
+        let name = lit.to_string();
+
+        match name.strip_prefix('"').and_then(|n| n.strip_suffix('"')) {
+            Some(name) if !name.is_empty() => rename = Some(name.to_owned()),
+            _ => return Err(ParseError::new("Expected a field name string", lit)),
+        }
+
+        args.parse::<()>()?;
+    }
+
+    if stream.allow_consume("pub").is_some() {
+        stream.allow_consume('(');
+    }
+
+    let ident: Ident = stream.parse()?;
+
+    stream.expect(':')?;
+
+    // The type only decides how the field is read, it's inferred in the generated code.
+    // Keep the last segment of its path, such as `Option` in `std::option::Option<T>`.
+    let mut depth = 0_usize;
+    let mut outer = None;
+
+    for tt in stream.by_ref() {
+        match &tt {
+            TokenTree::Punct(p) if p.as_char() == ',' && depth == 0 => break,
+            TokenTree::Punct(p) if p.as_char() == '<' => depth += 1,
+            TokenTree::Punct(p) if p.as_char() == '>' => depth = depth.saturating_sub(1),
+            TokenTree::Ident(ty) if depth == 0 => outer = Some(ty.to_string()),
+            _ => (),
+        }
+    }
+
+    let reader = match outer.as_deref() {
+        Some("Option") => "optional",
+        Some("Vec") => "all",
+        Some("bool") => "checked",
+        _ => "required",
+    };
+
+    let name = rename.unwrap_or_else(|| {
+        let name = ident.to_string();
+
+        name.strip_prefix("r#").map(str::to_owned).unwrap_or(name)
+    });
+
+    Ok(Field {
+        ident,
+        name,
+        reader,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn derive_str(input: &str) -> Result<String, String> {
+        derive(input.parse().unwrap())
+            .map(|out| out.to_string())
+            .map_err(|err| err.msg.to_string())
+    }
+
+    #[test]
+    fn readers_by_type() {
+        let out = derive_str(
+            r#"
+            /// Sign up form
+            #[derive(Debug)]
+            pub struct SignUp {
+                pub user: String,
+                age: std::primitive::u8,
+                referral: Option<String>,
+                tags: Vec<String>,
+                newsletter: bool,
+                #[form(name = "favorite-color")]
+                color: String,
+                r#type: String,
+                scores: std::collections::HashMap<String, u32>,
+            }
+            "#,
+        )
+        .unwrap();
+
+        assert!(out.starts_with("impl :: kobold :: form :: FromForm for SignUp"));
+
+        for expected in [
+            r#"required (__fields , "user")"#,
+            r#"required (__fields , "age")"#,
+            r#"optional (__fields , "referral")"#,
+            r#"all (__fields , "tags")"#,
+            r#"checked (__fields , "newsletter")"#,
+            r#"required (__fields , "favorite-color")"#,
+            r#"required (__fields , "type")"#,
+            r#"required (__fields , "scores")"#,
+            "color : __f5",
+            "r#type : __f6",
+        ] {
+            assert!(out.contains(expected), "{expected} in {out}");
+        }
+    }
+
+    #[test]
+    fn invalid_input() {
+        let err = derive_str("enum Form { A }").unwrap_err();
+        assert!(err.contains("only be derived for structs"));
+
+        let err = derive_str("struct Form(String);").unwrap_err();
+        assert!(err.contains("named fields"));
+
+        let err = derive_str("struct Form<T> { a: T }").unwrap_err();
+        assert!(err.contains("generic"));
+
+        let err = derive_str(r#"struct Form { #[form(name = "")] a: String }"#).unwrap_err();
+        assert!(err.contains("field name"));
+    }
+}
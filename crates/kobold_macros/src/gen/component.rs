@@ -12,7 +12,10 @@ impl Component {
     fn into_expression(self) -> TokenStream {
         let mut render = self.path.clone();
 
-        render.write("::render");
+        match self.render {
+            Some(name) => render.write(("::", name)),
+            None => render.write("::render"),
+        }
 
         if let Some(generics) = self.generics {
             render.write(("::", generics));
@@ -32,7 +35,10 @@ impl Component {
             params.write(('.', call("children", children)));
         }
 
-        call(render, params)
+        match self.render_args {
+            Some(args) => call(render, (params, ',', args)),
+            None => call(render, params),
+        }
     }
 }
 
@@ -9,7 +9,7 @@ use tokens::{Literal, TokenStream};
 use crate::dom::{Attribute, AttributeValue, CssValue, ElementTag, HtmlElement};
 use crate::gen::{append, DomNode, Generator, IntoGenerator, JsArgument, Short};
 use crate::itertools::IteratorExt as _;
-use crate::parse::IteratorExt as _;
+use crate::parse::{IdentExt as _, IteratorExt as _};
 use crate::tokenize::prelude::*;
 
 pub struct JsElement {
@@ -67,7 +67,7 @@ impl IntoGenerator for HtmlElement {
                 }
             },
             _ => {
-                let lit_count = self.classes.iter().map(CssValue::is_literal).count();
+                let lit_count = self.classes.iter().filter(|c| c.is_literal()).count();
 
                 if lit_count > 0 {
                     let classes = self
@@ -93,17 +93,62 @@ impl IntoGenerator for HtmlElement {
             }
         }
 
+        let interactive = take_interactive(&mut self.attributes);
+
+        if interactive {
+            let has = |attr: &str| self.attributes.iter().any(|a| a.name.label == attr);
+
+            if !has("role") {
+                writeln!(el, "{var}.setAttribute(\"role\",\"button\");");
+            }
+            if !has("tabindex") {
+                writeln!(el, "{var}.tabIndex=0;");
+            }
+            // Only keys pressed on the element itself, not on focusable children
+            // such as inputs, and no repeated clicks while Space is held down
+            writeln!(
+                el,
+                "{var}.addEventListener(\"keydown\",(e)=>{{\
+                    if(e.target!==e.currentTarget)return;\
+                    if(e.key===\"Enter\"){{e.preventDefault();{var}.click();}}\
+                    else if(e.key===\" \"){{e.preventDefault();if(!e.repeat){var}.click();}}\
+                }});"
+            );
+        }
+
         for Attribute { name, value } in self.attributes {
             let attr_type = attribute_type(&name.label);
 
             match value {
                 AttributeValue::Literal(value) => {
-                    let name = attribute_name(&name.label);
-                    writeln!(el, "{var}.setAttribute(\"{name}\",{value});");
-                }
-                AttributeValue::Boolean(value) => {
-                    writeln!(el, "{var}.{name}={value};");
+                    let js_name = attribute_name(&name.label);
+                    let value = js_literal(&value);
+
+                    // These map to a property that has no attribute counterpart
+                    if matches!(
+                        name.label.as_str(),
+                        "view-transition-name" | "default-value"
+                    ) {
+                        writeln!(el, "{var}.{js_name}={value};");
+                    } else if let AttributeType::Namespaced(ns) = attr_type {
+                        writeln!(el, "{var}.setAttributeNS(\"{ns}\",\"{js_name}\",{value});");
+                    } else {
+                        writeln!(el, "{var}.setAttribute(\"{js_name}\",{value});");
+                    }
                 }
+                AttributeValue::Boolean(value) => match boolean_property(&name.label) {
+                    Some(prop) => writeln!(el, "{var}.{prop}={value};"),
+                    // Enumerated attributes such as `aria-hidden` take `"true"` or `"false"`
+                    None if name.label.starts_with("aria-") || name.label.starts_with("data-") => {
+                        writeln!(el, "{var}.setAttribute(\"{}\",\"{value}\");", name.label)
+                    }
+                    // Any other boolean attribute is true when present, and the
+                    // element was just created so `false` leaves it out
+                    None if value.one_of(["true"]) => {
+                        writeln!(el, "{var}.setAttribute(\"{}\",\"\");", name.label)
+                    }
+                    None => (),
+                },
                 AttributeValue::Expression(mut expr) => match &attr_type {
                     AttributeType::Event(event) => {
                         let target = el.typ;
@@ -138,16 +183,30 @@ impl IntoGenerator for HtmlElement {
                         let name = attribute_name(&name.label);
                         el.hoisted = true;
 
-                        let value = gen
-                            .add_field(expr.stream)
-                            .attr(var, *attr, attr.prop())
-                            .name;
+                        let field = gen.add_field(expr.stream).attr(var, *attr, attr.prop());
+
+                        if attr.name == "DefaultValue" {
+                            field.once();
+                        }
+
+                        let value = field.name;
 
                         if let Some(abi) = attr.abi {
                             writeln!(el, "{var}.{name}={value};");
                             el.args.push(JsArgument::with_abi(value, abi))
                         }
                     }
+                    AttributeType::Namespaced(ns) => {
+                        el.hoisted = true;
+
+                        let prop = call(
+                            "::kobold::attribute::NamespacedName::new",
+                            (Literal::string(ns), ',', Literal::string(&name.label)),
+                        );
+                        let attr = Attr::new("NamespacedName");
+
+                        gen.add_field(expr.stream).attr(var, attr, prop);
+                    }
                     AttributeType::Unknown => {
                         el.hoisted = true;
 
@@ -157,6 +216,29 @@ impl IntoGenerator for HtmlElement {
                         gen.add_field(expr.stream).attr(var, attr, prop);
                     }
                 },
+                AttributeValue::Optional(expr) => {
+                    el.hoisted = true;
+
+                    let value = call("::kobold::attribute::Optional", expr.stream);
+
+                    // Conditional attributes are always set via `setAttribute`, even
+                    // if Kobold provides a property for them
+                    let (attr, prop) = match attr_type {
+                        AttributeType::Namespaced(ns) => (
+                            Attr::new("NamespacedName"),
+                            call(
+                                "::kobold::attribute::NamespacedName::new",
+                                (Literal::string(ns), ',', Literal::string(&name.label)),
+                            ),
+                        ),
+                        _ => (
+                            Attr::new("&AttributeName"),
+                            (Literal::string(&name.label), ".into()").tokenize(),
+                        ),
+                    };
+
+                    gen.add_field(value).attr(var, attr, prop);
+                }
             };
 
             match attr_type {
@@ -177,6 +259,9 @@ impl IntoGenerator for HtmlElement {
                 AttributeType::Provided(attr) => {
                     gen.add_attr_hint(name.ident, "", attr.name);
                 }
+                AttributeType::Namespaced(_) => {
+                    gen.add_attr_hint(name.ident, "", "NamespacedName");
+                }
                 AttributeType::Unknown => {
                     gen.add_attr_hint(name.ident, "&'static", "AttributeName");
                 }
@@ -185,7 +270,13 @@ impl IntoGenerator for HtmlElement {
 
         if let Some(children) = self.children {
             let append = append(gen, &mut el.code, &mut el.args, children);
-            writeln!(el, "{var}.{append};");
+
+            // Children of `<template>` go into its inert content fragment
+            if el.tag == ElementTag::Template {
+                writeln!(el, "{var}.content.{append};");
+            } else {
+                writeln!(el, "{var}.{append};");
+            }
         }
 
         DomNode::Element(el)
@@ -221,6 +312,8 @@ impl InlineAbi {
 enum AttributeType {
     Provided(Attr),
     Event(&'static str),
+    /// Attribute with a known namespace prefix, such as `xlink:href`
+    Namespaced(&'static str),
     Unknown,
 }
 
@@ -266,10 +359,28 @@ fn is_inline_closure(out: &mut TokenStream) -> bool {
     is_closure
 }
 
+/// Remove the `interactive` attribute, returning `true` if it was set to `true`.
+///
+/// Interactive elements get `role="button"` and `tabindex="0"` (unless set explicitly),
+/// and trigger `click` events when Enter or Space is pressed while they are focused.
+fn take_interactive(attributes: &mut Vec<Attribute>) -> bool {
+    let Some(idx) = attributes
+        .iter()
+        .position(|a| a.name.label == "interactive")
+    else {
+        return false;
+    };
+
+    // Any other value is rejected when parsing the attribute
+    matches!(attributes.remove(idx).value, AttributeValue::Boolean(b) if b.one_of(["true"]))
+}
+
 fn attribute_name(attr: &str) -> &str {
     match attr {
         "html" => "innerHTML",
         "view_box" => "viewBox",
+        "view-transition-name" => "style.viewTransitionName",
+        "default-value" => "defaultValue",
         name => name,
     }
 }
@@ -279,6 +390,10 @@ fn attribute_type(attr: &str) -> AttributeType {
         return AttributeType::Event(event_js_type(&attr[2..]));
     }
 
+    if let Some(namespace) = attribute_namespace(attr) {
+        return AttributeType::Namespaced(namespace);
+    }
+
     let attr = match attr {
         "checked" => Attr {
             name: "Checked",
@@ -296,16 +411,129 @@ fn attribute_type(attr: &str) -> AttributeType {
             name: "Style",
             abi: Some(InlineAbi::Str),
         },
+        "view-transition-name" => Attr {
+            name: "ViewTransitionName",
+            abi: Some(InlineAbi::Str),
+        },
         "value" => Attr {
             name: "Value",
             abi: None,
         },
+        "default-value" => Attr {
+            name: "DefaultValue",
+            abi: None,
+        },
         _ => return AttributeType::Unknown,
     };
 
     AttributeType::Provided(attr)
 }
 
+/// Rust literal as a JS expression, without Rust type suffixes or digit separators.
+/// Integers become decimal strings, so `max=0xff` is set as `"255"`. Floats are kept
+/// as JS numbers for JS to stringify them, so `step=0.5f32` is set as `"0.5"` and
+/// `step=1e3` as `"1000"`.
+fn js_literal(lit: &Literal) -> String {
+    const INT_SUFFIXES: [&str; 12] = [
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+    ];
+
+    let lit = lit.to_string();
+
+    if !lit.starts_with(|c: char| c.is_ascii_digit()) {
+        return lit;
+    }
+
+    let mut num = lit.replace('_', "");
+
+    let radix = match num.get(..2) {
+        Some("0x") => 16,
+        Some("0o") => 8,
+        Some("0b") => 2,
+        _ => 10,
+    };
+
+    let suffix = INT_SUFFIXES
+        .iter()
+        .chain(if radix == 10 {
+            &["f32", "f64"][..]
+        } else {
+            &[]
+        })
+        .find(|suffix| num.ends_with(*suffix));
+
+    let mut float = radix == 10 && num.contains(['.', 'e', 'E']);
+
+    if let Some(suffix) = suffix {
+        float |= suffix.starts_with('f');
+        num.truncate(num.len() - suffix.len());
+    }
+
+    if float {
+        return num;
+    }
+
+    if radix != 10 {
+        if let Ok(int) = u128::from_str_radix(&num[2..], radix) {
+            num = int.to_string();
+        }
+    }
+
+    format!("\"{num}\"")
+}
+
+/// DOM property reflecting an attribute that can be set to `true` or `false` directly.
+#[rustfmt::skip]
+fn boolean_property(attr: &str) -> Option<&'static str> {
+    Some(match attr {
+        "allowfullscreen" => "allowFullscreen",
+        "async" => "async",
+        "autofocus" => "autofocus",
+        "autoplay" => "autoplay",
+        "checked" => "checked",
+        "contenteditable" => "contentEditable",
+        "controls" => "controls",
+        "default" => "default",
+        "default-checked" => "defaultChecked",
+        "default-muted" => "defaultMuted",
+        "default-selected" => "defaultSelected",
+        "defer" => "defer",
+        "disabled" => "disabled",
+        "draggable" => "draggable",
+        "formnovalidate" => "formNoValidate",
+        "hidden" => "hidden",
+        "indeterminate" => "indeterminate",
+        "inert" => "inert",
+        "ismap" => "isMap",
+        "loop" => "loop",
+        "multiple" => "multiple",
+        "muted" => "muted",
+        "nomodule" => "noModule",
+        "novalidate" => "noValidate",
+        "open" => "open",
+        "playsinline" => "playsInline",
+        "readonly" => "readOnly",
+        "required" => "required",
+        "reversed" => "reversed",
+        "selected" => "selected",
+        "spellcheck" => "spellcheck",
+        "translate" => "translate",
+        _ => return None,
+    })
+}
+
+/// Namespace URI for prefixed attribute names, such as `xlink:href`.
+fn attribute_namespace(attr: &str) -> Option<&'static str> {
+    let (prefix, _) = attr.split_once(':')?;
+
+    match prefix {
+        "xlink" => Some("http://www.w3.org/1999/xlink"),
+        "xml" => Some("http://www.w3.org/XML/1998/namespace"),
+        "xmlns" => Some("http://www.w3.org/2000/xmlns/"),
+        _ => None,
+    }
+}
+
 #[rustfmt::skip]
 fn event_js_type(event: &str) -> &'static str {
     match event {
@@ -322,6 +550,45 @@ fn event_js_type(event: &str) -> &'static str {
         "keydown"
         | "keyup"
         | "keypress" => "KeyboardEvent",
+
+        "wheel" => "WheelEvent",
+
+        "pointerdown"
+        | "pointerup"
+        | "pointermove"
+        | "pointerover"
+        | "pointerout"
+        | "pointerenter"
+        | "pointerleave"
+        | "pointercancel"
+        | "gotpointercapture"
+        | "lostpointercapture" => "PointerEvent",
+
+        "touchstart"
+        | "touchend"
+        | "touchmove"
+        | "touchcancel" => "TouchEvent",
+
+        "drag"
+        | "dragstart"
+        | "dragend"
+        | "dragenter"
+        | "dragleave"
+        | "dragover"
+        | "drop" => "DragEvent",
+
+        "animationstart"
+        | "animationend"
+        | "animationiteration"
+        | "animationcancel" => "AnimationEvent",
+
+        "transitionstart"
+        | "transitionend"
+        | "transitionrun"
+        | "transitioncancel" => "TransitionEvent",
+
+        "copy" | "cut" | "paste" => "ClipboardEvent",
+
         _ => "Event",
     }
 }
@@ -339,3 +606,525 @@ fn element_js_type(tag: &str) -> &'static str {
         _ => "HtmlElement",
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::dom::Node;
+    use crate::gen::FieldKind;
+
+    fn element_code(view: &str) -> String {
+        let mut nodes = crate::dom::parse(view.parse().unwrap()).unwrap();
+
+        let Node::HtmlElement(el) = nodes.remove(0) else {
+            panic!("Expected an element");
+        };
+
+        match el.into_gen(&mut Generator::default()) {
+            DomNode::Element(el) => el.code,
+            _ => panic!("Expected an element"),
+        }
+    }
+
+    fn attribute_fields(view: &str) -> Vec<(String, &'static str)> {
+        let mut nodes = crate::dom::parse(view.parse().unwrap()).unwrap();
+        let mut gen = Generator::default();
+
+        nodes.remove(0).into_gen(&mut gen);
+
+        gen.out
+            .fields
+            .into_iter()
+            .filter_map(|field| match field.kind {
+                FieldKind::Attribute { attr, .. } => Some((field.value.to_string(), attr.name)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn global_attributes() {
+        let code = element_code(
+            r#"<div lang="en" dir="rtl" accesskey="k" inputmode="numeric" enterkeyhint="send"
+                   spellcheck=false translate=false readonly=true aria-hidden=true
+                   data-open=false nowrap=true itemscope=false>"#,
+        );
+
+        for expected in [
+            r#".setAttribute("lang","en");"#,
+            r#".setAttribute("dir","rtl");"#,
+            r#".setAttribute("accesskey","k");"#,
+            r#".setAttribute("inputmode","numeric");"#,
+            r#".setAttribute("enterkeyhint","send");"#,
+            ".spellcheck=false;",
+            ".translate=false;",
+            ".readOnly=true;",
+            r#".setAttribute("aria-hidden","true");"#,
+            r#".setAttribute("data-open","false");"#,
+            r#".setAttribute("nowrap","");"#,
+        ] {
+            assert!(code.contains(expected), "{expected} in {code}");
+        }
+
+        assert!(!code.contains("itemscope"), "{code}");
+
+        // Properties without an attribute counterpart
+        let code = element_code(
+            r#"<input type="checkbox" indeterminate=true default-checked=true default-muted=false>"#,
+        );
+
+        for expected in [
+            ".indeterminate=true;",
+            ".defaultChecked=true;",
+            ".defaultMuted=false;",
+        ] {
+            assert!(code.contains(expected), "{expected} in {code}");
+        }
+
+        let fields = attribute_fields("<div {lang} dir={dir} spellcheck={check} tabindex={0}>");
+
+        assert!(fields.iter().all(|(_, attr)| *attr == "&AttributeName"));
+        assert_eq!(fields.len(), 4);
+    }
+
+    #[test]
+    fn static_and_dynamic_classes() {
+        let code = element_code("<div.main.{hidden}>");
+        let fields = attribute_fields("<div.main.{hidden}>");
+
+        // Static classes are added once, dynamic ones are diffed via `classList`
+        assert!(code.contains(r#".classList.add("main");"#));
+        assert!(!code.contains("className"));
+        assert_eq!(fields, [("hidden".to_string(), "Class")]);
+    }
+
+    #[test]
+    fn class_toggle_shorthand() {
+        let fields = attribute_fields(r#"<li.todo.{entry.editing => "editing"}.{active}>"#);
+
+        assert_eq!(fields.len(), 2);
+        assert!(fields[0].0.starts_with(":: kobold :: class !"));
+        assert!(fields[0].0.contains(r#""editing" if entry . editing"#));
+        assert_eq!(fields[1].0, "active");
+    }
+
+    #[test]
+    fn class_list_shorthand() {
+        let fields = attribute_fields(r#"<div.main.{["active", maybe]}>"#);
+
+        assert_eq!(fields.len(), 1);
+        assert!(fields[0].0.starts_with(":: kobold :: attribute :: classes"));
+        assert!(fields[0].0.contains(r#"(["active" , maybe])"#));
+        assert_eq!(fields[0].1, "Class");
+    }
+
+    #[test]
+    fn only_dynamic_classes() {
+        let code = element_code("<div.{active}.{hidden}>");
+
+        assert!(!code.contains("classList.add"));
+        assert_eq!(attribute_fields("<div.{active}.{hidden}>").len(), 2);
+    }
+
+    #[test]
+    fn if_expression_attribute() {
+        let fields = attribute_fields(r#"<div class={if selected { "selected" } else { "" }}>"#);
+
+        assert_eq!(fields.len(), 1);
+        assert!(fields[0].0.starts_with("if selected"));
+        assert_eq!(fields[0].1, "ClassName");
+    }
+
+    #[test]
+    fn match_expression_attribute() {
+        let fields = attribute_fields(
+            r#"<div title={match state { State::Done => "done", _ => "pending" }}>"#,
+        );
+
+        assert_eq!(fields.len(), 1);
+        assert!(fields[0].0.starts_with("match state"));
+        assert_eq!(fields[0].1, "&AttributeName");
+    }
+
+    #[test]
+    fn method_call_attribute() {
+        let fields = attribute_fields("<a href={link.url.as_str()} style={style.trim()}>");
+
+        assert_eq!(fields.len(), 2);
+        assert!(fields[0].0.contains("as_str"));
+        assert_eq!(fields[0].1, "Href");
+        assert!(fields[1].0.contains("trim"));
+        assert_eq!(fields[1].1, "Style");
+    }
+
+    #[test]
+    fn namespaced_attribute() {
+        let code = element_code(r##"<use xlink:href="#icon">"##);
+
+        assert!(code.contains(
+            r##".setAttributeNS("http://www.w3.org/1999/xlink","xlink:href","#icon");"##
+        ));
+
+        let fields = attribute_fields("<use xlink:href={icon}>");
+
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].1, "NamespacedName");
+    }
+
+    #[test]
+    fn numeric_attributes() {
+        let code = element_code(
+            r#"<input type="number" min=0 max=0xff_u8 step=0.5f32 low=1e3 high=1.0 value=2f64>"#,
+        );
+
+        assert!(code.contains(r#".setAttribute("min","0");"#));
+        assert!(code.contains(r#".setAttribute("max","255");"#));
+        // Floats are stringified by JS: "0.5", "1000", "1" and "2"
+        assert!(code.contains(r#".setAttribute("step",0.5);"#));
+        assert!(code.contains(r#".setAttribute("low",1e3);"#));
+        assert!(code.contains(r#".setAttribute("high",1.0);"#));
+        assert!(code.contains(r#".setAttribute("value",2);"#));
+
+        let fields = attribute_fields(r#"<input type="number" min={0} max={max} step={0.5}>"#);
+
+        assert_eq!(
+            fields,
+            [
+                ("0".to_string(), "&AttributeName"),
+                ("max".to_string(), "&AttributeName"),
+                ("0.5".to_string(), "&AttributeName"),
+            ]
+        );
+    }
+
+    #[test]
+    fn interactive_element() {
+        let code = element_code("<div interactive=true onclick={f}>\"Click\"</div>");
+
+        assert!(code.contains(r#".setAttribute("role","button");"#));
+        assert!(code.contains(".tabIndex=0;"));
+        assert!(code.contains("if(e.target!==e.currentTarget)return;"));
+        assert!(code.contains(".click();"));
+        assert!(!code.contains("interactive"));
+    }
+
+    #[test]
+    fn interactive_keys_click() {
+        let code = element_code("<div interactive=true onclick={f}>\"Click\"</div>");
+
+        // Run the generated code against a minimal element mock, counting clicks
+        let script = format!(
+            r#"
+            let handlers = {{}}, clicks = 0;
+            let e0 = {{
+                setAttribute() {{}},
+                append() {{}},
+                addEventListener(name, f) {{ handlers[name] = f; }},
+                click() {{ handlers.click(); }},
+            }};
+            let a = () => clicks++;
+            {code}
+            let press = (key, target = e0, repeat = false) => {{
+                let prevented = false;
+                handlers.keydown({{ key, target, currentTarget: e0, repeat, preventDefault() {{ prevented = true; }} }});
+                return prevented;
+            }};
+            let log = [];
+            log.push(press("Enter"), clicks);
+            log.push(press(" "), clicks);
+            log.push(press(" ", e0, true), clicks);
+            log.push(press("a"), clicks);
+            log.push(press(" ", {{}}), press("Enter", {{}}), clicks);
+            console.log(log.join(","));
+            "#
+        );
+
+        let out = match std::process::Command::new("node")
+            .args(["-e", &script])
+            .output()
+        {
+            Ok(out) => out,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                eprintln!("node not found, skipping interactive_keys_click");
+                return;
+            }
+            Err(err) => panic!("{err}"),
+        };
+
+        assert!(
+            out.status.success(),
+            "{}",
+            String::from_utf8_lossy(&out.stderr)
+        );
+
+        // Enter and Space click once each, held Space and other keys don't click,
+        // and keys pressed on children are left alone
+        assert_eq!(
+            String::from_utf8_lossy(&out.stdout).trim(),
+            "true,1,true,2,true,2,false,2,false,false,2"
+        );
+    }
+
+    #[test]
+    fn interactive_keeps_explicit_attributes() {
+        let code = element_code(r#"<div interactive=true role="link" tabindex="-1">"#);
+
+        assert!(!code.contains(r#""role","button""#));
+        assert!(!code.contains(".tabIndex=0;"));
+        assert!(code.contains(r#".setAttribute("role","link");"#));
+    }
+
+    #[test]
+    fn not_interactive() {
+        let code = element_code("<div interactive=false onclick={f}>");
+
+        assert!(!code.contains("role"));
+        assert!(!code.contains("keydown"));
+    }
+
+    #[test]
+    fn interactive_requires_literal() {
+        for view in [
+            "<div interactive={clickable}>",
+            "<div interactive=?{clickable}>",
+            r#"<div interactive="true">"#,
+        ] {
+            assert!(crate::dom::parse(view.parse().unwrap()).is_err(), "{view}");
+        }
+    }
+
+    #[test]
+    fn view_transition_name() {
+        let code = element_code(r#"<div view-transition-name="card">"#);
+
+        assert!(code.contains(r#".style.viewTransitionName="card";"#));
+
+        let code = element_code("<div view-transition-name={name}>");
+
+        assert!(code.contains(".style.viewTransitionName="));
+        assert!(!code.contains("setAttribute"));
+    }
+
+    #[test]
+    fn template_content() {
+        let code = element_code(r#"<template><p.note>"Saved"</p></template>"#);
+
+        assert!(code.contains(".content.append("));
+        assert!(
+            crate::dom::parse(r#"<template><p>{ name }</p></template>"#.parse().unwrap()).is_err()
+        );
+        assert!(crate::dom::parse(
+            r#"<template><a href={url}>"x"</a></template>"#.parse().unwrap()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn optional_child() {
+        let values = |view: &str| {
+            let mut nodes = crate::dom::parse(view.parse().unwrap()).unwrap();
+            let mut gen = Generator::default();
+
+            nodes.remove(0).into_gen(&mut gen);
+
+            gen.out
+                .fields
+                .iter()
+                .map(|f| f.value.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            values("<p>{ ?entry }</p>"),
+            [":: kobold :: keywords :: optional (entry)"]
+        );
+        // Trailing `?` is the regular try operator
+        assert_eq!(values("<p>{ entry? }</p>"), ["entry ?"]);
+        assert!(crate::dom::parse("<p>{ ? }</p>".parse().unwrap()).is_err());
+    }
+
+    #[test]
+    fn event_types() {
+        assert_eq!(event_js_type("click"), "MouseEvent");
+        assert_eq!(event_js_type("keydown"), "KeyboardEvent");
+        assert_eq!(event_js_type("wheel"), "WheelEvent");
+        assert_eq!(event_js_type("pointermove"), "PointerEvent");
+        assert_eq!(event_js_type("touchstart"), "TouchEvent");
+        assert_eq!(event_js_type("drop"), "DragEvent");
+        assert_eq!(event_js_type("animationend"), "AnimationEvent");
+        assert_eq!(event_js_type("transitionend"), "TransitionEvent");
+        assert_eq!(event_js_type("paste"), "ClipboardEvent");
+        assert_eq!(event_js_type("cut"), "ClipboardEvent");
+        assert_eq!(event_js_type("copy"), "ClipboardEvent");
+        assert_eq!(event_js_type("scroll"), "Event");
+        assert_eq!(event_js_type("change"), "Event");
+    }
+
+    #[test]
+    fn responsive_picture() {
+        let nodes = crate::dom::parse(
+            r#"
+            <picture>
+                <source media="(min-width: 800px)" srcset="large.webp 1x, large@2x.webp 2x">
+                <source srcset={srcset} sizes="(max-width: 600px) 100vw, 50vw">
+                <img src="small.jpg" alt="Cat" sizes={sizes} />
+            </picture>
+            "#
+            .parse()
+            .unwrap(),
+        )
+        .unwrap();
+
+        let out = crate::gen::generate(nodes);
+        let attrs: Vec<_> = out.fields.iter().map(|f| f.value.to_string()).collect();
+
+        // Media queries and candidate lists are passed through as plain strings
+        assert!(out.js.code.contains(
+            r#"e1.setAttribute("media","(min-width: 800px)");
+e1.setAttribute("srcset","large.webp 1x, large@2x.webp 2x");"#
+        ));
+        assert!(out
+            .js
+            .code
+            .contains(r#"e2.setAttribute("sizes","(max-width: 600px) 100vw, 50vw");"#));
+        assert!(out.js.code.contains("e0.append(e1,e2,e3);"));
+        assert_eq!(attrs, ["srcset", "sizes"]);
+
+        let closed = crate::dom::parse(
+            r#"<picture><source srcset="a.webp"></source></picture>"#
+                .parse()
+                .unwrap(),
+        );
+
+        assert!(closed.unwrap_err().msg.contains("void element"));
+    }
+
+    #[test]
+    fn explicit_fragments() {
+        let code = element_code(r#"<ul><><li>"a"</li><><li>"b"</li><li>{ c }</li></></></ul>"#);
+
+        assert!(code.contains("e0.append(e1,e2,e3);"));
+
+        let parse = |view: &str| crate::dom::parse(view.parse().unwrap());
+
+        assert_eq!(parse(r#"<><p>"a"</p><p>"b"</p></>"#).unwrap().len(), 2);
+        assert!(parse("<div><></></div>").unwrap_err().msg.contains("Empty"));
+        assert!(parse(r#"<div><><p>"a"</p></div>"#)
+            .unwrap_err()
+            .msg
+            .contains("</>"));
+        assert!(parse(r#"<div>"a"</></div>"#).is_err());
+    }
+
+    #[test]
+    fn dynamic_id_shorthand() {
+        let code = element_code(r#"<li #{format!("item-{}", entry.id)}.item>"#);
+        let fields = attribute_fields(r#"<li #{format!("item-{}", entry.id)}.item>"#);
+
+        assert!(!code.contains("setAttribute(\"id\""));
+        assert_eq!(
+            fields,
+            [(
+                r#"format ! ("item-{}" , entry . id)"#.to_string(),
+                "&AttributeName"
+            )]
+        );
+
+        let code = element_code("<input #toggle-all>");
+
+        assert!(code.contains(r#"setAttribute("id","toggle-all")"#));
+
+        // Class shorthands don't apply to ids
+        let fields = attribute_fields(r#"<input #{["a", "b"][idx]}>"#);
+
+        assert_eq!(
+            fields,
+            [(r#"["a" , "b"] [idx]"#.to_string(), "&AttributeName")]
+        );
+    }
+
+    #[test]
+    fn default_value_is_set_once() {
+        let mut nodes =
+            crate::dom::parse("<input value={a} default-value={b}>".parse().unwrap()).unwrap();
+        let mut gen = Generator::default();
+
+        nodes.remove(0).into_gen(&mut gen);
+
+        let fields: Vec<_> = gen
+            .out
+            .fields
+            .into_iter()
+            .filter_map(|field| match field.kind {
+                FieldKind::Attribute { attr, once, .. } => Some((attr.name, once)),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(fields, [("Value", false), ("DefaultValue", true)]);
+
+        let code = element_code(r#"<input default-value="draft">"#);
+
+        assert!(code.contains(r#".defaultValue="draft";"#));
+    }
+
+    #[test]
+    fn conditional_attributes() {
+        let view = "<button disabled=?{busy} title=?{hint} xlink:href=?{href}>";
+        let code = element_code(view);
+        let fields = attribute_fields(view);
+
+        // Presence is toggled by the `Optional` attribute at runtime, not in JS
+        assert!(!code.contains("disabled"));
+        assert_eq!(
+            fields,
+            [
+                (
+                    ":: kobold :: attribute :: Optional (busy)".to_string(),
+                    "&AttributeName"
+                ),
+                (
+                    ":: kobold :: attribute :: Optional (hint)".to_string(),
+                    "&AttributeName"
+                ),
+                (
+                    ":: kobold :: attribute :: Optional (href)".to_string(),
+                    "NamespacedName"
+                ),
+            ]
+        );
+
+        let parse = |view: &str| crate::dom::parse(view.parse().unwrap());
+
+        assert!(parse("<button onclick=?{handler}>").is_err());
+        assert!(parse(r#"<button title=?"hint">"#).is_err());
+    }
+
+    #[test]
+    fn paste_handler_gets_clipboard_event() {
+        let mut nodes = crate::dom::parse(
+            "<textarea onpaste={|e| sanitize(e)}></textarea>"
+                .parse()
+                .unwrap(),
+        )
+        .unwrap();
+        let mut gen = Generator::default();
+
+        nodes.remove(0).into_gen(&mut gen);
+
+        let field = &gen.out.fields[0];
+
+        assert!(matches!(
+            field.kind,
+            FieldKind::Event {
+                event: "ClipboardEvent",
+                target: "HtmlTextAreaElement"
+            }
+        ));
+        // Inline closures are hinted with the event type
+        assert!(field.value.to_string().contains(
+            ":: kobold :: event :: ClipboardEvent <:: kobold :: reexport :: web_sys :: HtmlTextAreaElement >"
+        ));
+    }
+}
@@ -138,7 +138,7 @@ impl Tokenize for Transient {
             field.declare(&mut declare);
 
             match field.kind {
-                FieldKind::StaticView => (),
+                FieldKind::StaticView | FieldKind::Attribute { once: true, .. } => (),
                 _ => {
                     let _ = write!(product_generics, "{typ},");
                     let _ = write!(product_generics_binds, "{typ}::Product,");
@@ -278,17 +278,6 @@ impl Debug for JsModule {
     }
 }
 
-pub struct JsAttrConstructor(pub JsFnName);
-
-impl Tokenize for JsAttrConstructor {
-    fn tokenize_in(self, stream: &mut TokenStream) {
-        let name = self.0;
-        stream.write(format_args!(
-            "fn {name}() -> ::kobold::reexport::web_sys::Node;"
-        ));
-    }
-}
-
 #[derive(Debug)]
 pub struct JsFunction {
     pub name: JsFnName,
@@ -390,6 +379,8 @@ pub enum FieldKind {
         el: Short,
         attr: Attr,
         prop: TokenStream,
+        /// Set on build, never updated
+        once: bool,
     },
 }
 
@@ -429,7 +420,20 @@ impl Field {
     }
 
     pub fn attr(&mut self, el: Short, attr: Attr, prop: TokenStream) -> &mut Self {
-        self.kind = FieldKind::Attribute { el, attr, prop };
+        self.kind = FieldKind::Attribute {
+            el,
+            attr,
+            prop,
+            once: false,
+        };
+        self
+    }
+
+    /// Only set the attribute when the view is built, ignoring it on updates.
+    pub fn once(&mut self) -> &mut Self {
+        if let FieldKind::Attribute { once, .. } = &mut self.kind {
+            *once = true;
+        }
         self
     }
 
@@ -509,6 +513,14 @@ impl Field {
                     "let mut {name} = ::kobold::init!(_p.{name} @ self.{name}.build(_p));"
                 );
             }
+            FieldKind::Attribute {
+                el,
+                prop,
+                once: true,
+                ..
+            } => {
+                let _ = write!(post, "self.{name}.build_in({prop}, &{el});");
+            }
             FieldKind::Attribute { attr, .. } if attr.abi.is_some() => {
                 let _ = write!(post, "::kobold::init!(_p.{name} = self.{name}.build());");
             }
@@ -525,7 +537,7 @@ impl Field {
         let Field { name, kind, .. } = self;
 
         match kind {
-            FieldKind::StaticView => (),
+            FieldKind::StaticView | FieldKind::Attribute { once: true, .. } => (),
             FieldKind::View | FieldKind::Event { .. } => {
                 let _ = write!(buf, "self.{name}.update(&mut p.{name});");
             }
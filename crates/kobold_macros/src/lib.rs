@@ -17,11 +17,15 @@ use arrayvec::ArrayString;
 
 mod branching;
 mod class;
+mod css;
+mod derive_view;
 mod dom;
 mod fn_component;
+mod from_form;
 mod gen;
 mod itertools;
 mod parse;
+mod should_render;
 mod syntax;
 mod tokenize;
 
@@ -36,7 +40,7 @@ macro_rules! unwrap_err {
     };
 }
 
-#[allow(clippy::let_and_return)]
+#[allow(clippy::let_and_return, clippy::useless_conversion)]
 #[proc_macro_attribute]
 pub fn component(args: TokenStream, input: TokenStream) -> TokenStream {
     let args = unwrap_err!(fn_component::args(args.into()));
@@ -48,7 +52,7 @@ pub fn component(args: TokenStream, input: TokenStream) -> TokenStream {
     out.into()
 }
 
-#[allow(clippy::let_and_return)]
+#[allow(clippy::let_and_return, clippy::useless_conversion)]
 #[proc_macro]
 pub fn view(body: TokenStream) -> TokenStream {
     let nodes = unwrap_err!(dom::parse(body.into()));
@@ -64,7 +68,7 @@ pub fn view(body: TokenStream) -> TokenStream {
     out.into()
 }
 
-#[allow(clippy::let_and_return)]
+#[allow(clippy::let_and_return, clippy::useless_conversion)]
 #[proc_macro]
 pub fn class(stream: TokenStream) -> TokenStream {
     let out = unwrap_err!(class::parse(stream.into()));
@@ -72,6 +76,45 @@ pub fn class(stream: TokenStream) -> TokenStream {
     out.into()
 }
 
+#[allow(clippy::let_and_return, clippy::useless_conversion)]
+#[proc_macro]
+pub fn css(stream: TokenStream) -> TokenStream {
+    let out = unwrap_err!(css::parse(stream.into()));
+
+    out.into()
+}
+
+/// Derive `ShouldRender` for an enum, see `kobold::stateful::ShouldRender`.
+#[allow(clippy::useless_conversion)]
+#[proc_macro_derive(ShouldRender, attributes(render, no_render))]
+pub fn should_render(input: TokenStream) -> TokenStream {
+    match should_render::derive(input.into()) {
+        Ok(out) => out.into(),
+        // Derives expand to items, so the error block needs an item around it
+        Err(err) => ("const _: i32 =", err, ';').tokenize().into(),
+    }
+}
+
+/// Derive `View` for a field-less enum, see `kobold::View`.
+#[allow(clippy::useless_conversion)]
+#[proc_macro_derive(View, attributes(view))]
+pub fn derive_view(input: TokenStream) -> TokenStream {
+    match derive_view::derive(input.into()) {
+        Ok(out) => out.into(),
+        Err(err) => ("const _: i32 =", err, ';').tokenize().into(),
+    }
+}
+
+/// Derive `FromForm` for a struct, see `kobold::form::FromForm`.
+#[allow(clippy::useless_conversion)]
+#[proc_macro_derive(FromForm, attributes(form))]
+pub fn from_form(input: TokenStream) -> TokenStream {
+    match from_form::derive(input.into()) {
+        Ok(out) => out.into(),
+        Err(err) => ("const _: i32 =", err, ';').tokenize().into(),
+    }
+}
+
 fn unique() -> ArrayString<8> {
     use std::sync::atomic::{AtomicUsize, Ordering};
 
@@ -262,42 +262,18 @@ impl IteratorExt for ParseStream {
 
 pub trait TokenTreeExt {
     fn is(&self, pattern: impl Pattern) -> bool;
-
-    fn map_ident<F, R>(&self, f: F) -> Option<R>
-    where
-        F: Fn(&str) -> R;
 }
 
 impl TokenTreeExt for TokenTree {
     fn is(&self, pattern: impl Pattern) -> bool {
         pattern.matches(self)
     }
-
-    fn map_ident<F, R>(&self, f: F) -> Option<R>
-    where
-        F: Fn(&str) -> R,
-    {
-        match self {
-            TokenTree::Ident(ident) => Some(ident.with_str(f)),
-            _ => None,
-        }
-    }
 }
 
 impl TokenTreeExt for Option<TokenTree> {
     fn is(&self, pattern: impl Pattern) -> bool {
         self.as_ref().map(|tt| pattern.matches(tt)).unwrap_or(false)
     }
-
-    fn map_ident<F, R>(&self, f: F) -> Option<R>
-    where
-        F: Fn(&str) -> R,
-    {
-        match self {
-            Some(TokenTree::Ident(ident)) => Some(ident.with_str(f)),
-            _ => None,
-        }
-    }
 }
 
 mod util {
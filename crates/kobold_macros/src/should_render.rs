@@ -0,0 +1,176 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! `#[derive(ShouldRender)]` for enums
+
+use tokens::{Ident, TokenStream, TokenTree};
+
+use crate::parse::prelude::*;
+use crate::tokenize::prelude::*;
+
+struct Variant {
+    name: Ident,
+    render: bool,
+}
+
+pub fn derive(stream: TokenStream) -> Result<TokenStream, ParseError> {
+    let mut stream = stream.parse_stream();
+
+    skip_attributes(&mut stream)?;
+
+    if stream.allow_consume("pub").is_some() {
+        stream.allow_consume('(');
+    }
+
+    if stream.allow_consume("enum").is_none() {
+        return Err(ParseError::new(
+            "ShouldRender can only be derived for enums",
+            stream.next(),
+        ));
+    }
+
+    let name: Ident = stream.parse()?;
+
+    if stream.allow('<') {
+        return Err(ParseError::new(
+            "ShouldRender can't be derived for generic enums",
+            stream.next(),
+        ));
+    }
+
+    let mut body = match stream.expect('{')? {
+        TokenTree::Group(body) => body.stream().parse_stream(),
+        _ => unreachable!(),
+    };
+
+    let mut variants = Vec::new();
+
+    while !body.end() {
+        variants.push(variant(&mut body)?);
+    }
+
+    let arms = each(variants.iter().map(
+        |Variant {
+             name: variant,
+             render,
+         }| { format_args!("{name}::{variant} {{ .. }} => {render},").tokenize() },
+    ));
+
+    Ok((
+        format_args!("impl ::kobold::stateful::ShouldRender for {name}"),
+        block((
+            "fn should_render(self) -> bool",
+            block(("match self", block(arms))),
+        )),
+    )
+        .tokenize())
+}
+
+/// Skip outer attributes, such as doc comments or other derives
+fn skip_attributes(stream: &mut ParseStream) -> Result<(), ParseError> {
+    while stream.allow_consume('#').is_some() {
+        stream.expect('[')?;
+    }
+    Ok(())
+}
+
+fn variant(stream: &mut ParseStream) -> Result<Variant, ParseError> {
+    let mut render = None;
+
+    while let Some(hash) = stream.allow_consume('#') {
+        let TokenTree::Group(attr) = stream.expect('[')? else {
+            unreachable!();
+        };
+
+        let mut attr = attr.stream().parse_stream();
+
+        let value = match attr.next() {
+            Some(TokenTree::Ident(ident)) if ident.eq_str("render") => true,
+            Some(TokenTree::Ident(ident)) if ident.eq_str("no_render") => false,
+            _ => continue,
+        };
+
+        if let Some(tt) = attr.next() {
+            return Err(ParseError::new(
+                "#[render] and #[no_render] don't take any arguments",
+                tt,
+            ));
+        }
+
+        if render.replace(value).is_some() {
+            return Err(ParseError::new(
+                "Variant can only have one #[render] or #[no_render] attribute",
+                hash,
+            ));
+        }
+    }
+
+    let name = stream.parse()?;
+
+    // Fields and explicit discriminants don't matter for rendering
+    for tt in stream.by_ref() {
+        if tt.is(',') {
+            break;
+        }
+    }
+
+    Ok(Variant {
+        name,
+        // Unmarked variants render, same as closures returning `()`
+        render: render.unwrap_or(true),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn derive_str(input: &str) -> Result<String, String> {
+        derive(input.parse().unwrap())
+            .map(|out| out.to_string())
+            .map_err(|err| err.msg.to_string())
+    }
+
+    #[test]
+    fn multi_variant_enum() {
+        let out = derive_str(
+            "
+            /// Outcome of an edit
+            #[derive(Debug)]
+            pub(crate) enum Edit {
+                #[render]
+                Changed(usize),
+                #[no_render]
+                Unchanged,
+                /// Input was rejected
+                #[no_render]
+                Rejected { reason: String },
+                Reset = 4,
+            }
+            ",
+        )
+        .unwrap();
+
+        assert!(out.starts_with("impl :: kobold :: stateful :: ShouldRender for Edit"));
+        assert!(out.contains("Edit :: Changed { .. } => true ,"));
+        assert!(out.contains("Edit :: Unchanged { .. } => false ,"));
+        assert!(out.contains("Edit :: Rejected { .. } => false ,"));
+        assert!(out.contains("Edit :: Reset { .. } => true ,"));
+    }
+
+    #[test]
+    fn invalid_input() {
+        let err = derive_str("struct Edit;").unwrap_err();
+        assert!(err.contains("only be derived for enums"));
+
+        let err = derive_str("enum Edit<T> { A(T) }").unwrap_err();
+        assert!(err.contains("generic"));
+
+        let err = derive_str("enum Edit { #[render] #[no_render] A }").unwrap_err();
+        assert!(err.contains("only have one"));
+
+        let err = derive_str("enum Edit { #[render(true)] A }").unwrap_err();
+        assert!(err.contains("arguments"));
+    }
+}
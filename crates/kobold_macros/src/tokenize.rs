@@ -257,6 +257,7 @@ pub trait TokenStreamExt {
 
     fn parse_stream(self) -> ParseStream;
 
+    #[allow(dead_code)]
     fn write_fmt(&mut self, args: Arguments);
 }
 
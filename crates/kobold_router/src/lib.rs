@@ -33,6 +33,12 @@ pub enum ParamError {
     CouldNotParseParam,
 }
 
+impl Default for Router {
+    fn default() -> Self {
+        Router::new()
+    }
+}
+
 /// Implement of [Router]
 impl Router {
     pub fn new() -> Self {
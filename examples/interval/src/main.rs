@@ -1,4 +1,5 @@
-use gloo_timers::callback::Interval;
+use std::time::Duration;
+
 use kobold::prelude::*;
 
 #[component]
@@ -11,12 +12,8 @@ fn elapsed(seconds: u32) -> impl View {
         }
     })
     .once(|signal| {
-        // `signal` is an owned `Signal<u32>` and can be safely moved.
-        //
-        // `Interval` is returned here and will be safely dropped with the component.
-        Interval::new(1000, move || {
-            signal.update(|seconds| *seconds += 1);
-        })
+        // The interval is cleared automatically when the component is dropped.
+        signal.set_interval(Duration::from_secs(1), |seconds| *seconds += 1);
     })
 }
 
@@ -1,5 +1,4 @@
 use kobold::prelude::*;
-use kobold::reexport::web_sys::HtmlTextAreaElement;
 use kobold_qr::qr;
 
 #[component]
@@ -3,7 +3,6 @@ use kobold::View;
 use kobold_router::{link, Params, Router};
 use wasm_bindgen::JsValue;
 use web_sys::console::error_1;
-use web_sys::HtmlInputElement;
 
 #[component]
 fn inventory(params: Params) -> impl View + 'static {
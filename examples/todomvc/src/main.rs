@@ -42,7 +42,7 @@ fn app(state: &Hook<State>) -> impl View + '_ {
                         <!filter by={Filter::Active} {state}>
                         <!filter by={Filter::Completed} {state}>
                     </ul>
-                    <button.clear-completed.{completed_hidden} onclick={do state.clear()}> "Clear completed"
+                    <button.clear-completed.{completed_hidden} onclick={state.dispatch(|_| Action::Clear)}> "Clear completed"
             </section>
             <footer.info>
                 <p> "Double-click to edit a todo"
@@ -71,7 +71,7 @@ fn toggle_all(active_count: usize, state: &Hook<State>) -> impl View + '_ {
         <input #toggle-all.toggle-all
             type="checkbox"
             checked={active_count == 0}
-            onclick={do state.set_all(active_count != 0)}
+            onclick={state.dispatch(move |_| Action::SetAll(active_count != 0))}
         >
         <label for="toggle-all">
     }
@@ -96,25 +96,23 @@ fn entry<'a>(idx: usize, entry: &'a Entry, state: &'a Hook<State>) -> impl View
         view! {
             <input.edit
                 type="text"
-                value={static &entry.description}
+                default-value={&entry.description}
                 onmouseover={|event| event.current_target().focus()}
+                onfocus={|event| event.current_target().select()}
                 {onkeypress}
                 {onblur}
             >
         }
     });
 
-    let editing = class!("editing" if entry.editing);
-    let completed = class!("completed" if entry.completed);
-
     view! {
-        <li.todo.{editing}.{completed}>
+        <li.todo.{entry.editing => "editing"}.{entry.completed => "completed"}>
             <div.view>
-                <input.toggle type="checkbox" checked={entry.completed} onchange={do state.toggle(idx)}>
-                <label ondblclick={do state.edit_entry(idx)} >
+                <input.toggle type="checkbox" checked={entry.completed} onchange={state.dispatch(move |_| Action::Toggle(idx))}>
+                <label ondblclick={state.dispatch(move |_| Action::Edit(idx))} >
                     { ref entry.description }
                 </label>
-                <button.destroy onclick={do state.remove(idx)}>
+                <button.destroy onclick={state.dispatch(move |_| Action::Remove(idx))}>
             </div>
             { input }
     }
@@ -126,7 +124,7 @@ fn filter(by: Filter, state: &Hook<State>) -> impl View + '_ {
 
     view! {
         <li>
-            <a {class} onclick={do state.filter = by} href={static by.href()}>
+            <a {class} onclick={state.dispatch(move |_| Action::Filter(by))} href={static by.href()}>
                 { static by.label() }
     }
 }
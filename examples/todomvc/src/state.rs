@@ -1,4 +1,5 @@
 use gloo_storage::{LocalStorage, Storage};
+use kobold::stateful::{Reducer, Then};
 use wasm_bindgen::UnwrapThrowExt;
 
 const KEY: &str = "kobold.todomvc.example";
@@ -9,6 +10,17 @@ pub struct State {
     pub editing: Option<usize>,
 }
 
+/// Actions dispatched by buttons and checkboxes, adding and updating entries
+/// from inputs reads their values directly
+pub enum Action {
+    SetAll(bool),
+    Clear,
+    Edit(usize),
+    Remove(usize),
+    Toggle(usize),
+    Filter(Filter),
+}
+
 pub struct Entry {
     pub description: String,
     pub completed: bool,
@@ -102,19 +114,19 @@ impl State {
             .filter(|(_, e)| e.filter(self.filter))
     }
 
-    pub fn set_all(&mut self, completed: bool) {
+    fn set_all(&mut self, completed: bool) {
         for entry in self.entries.iter_mut() {
             entry.completed = completed;
         }
     }
 
-    pub fn clear(&mut self) {
+    fn clear(&mut self) {
         self.entries.retain(|entry| !entry.completed);
 
         self.store();
     }
 
-    pub fn edit_entry(&mut self, idx: usize) {
+    fn edit_entry(&mut self, idx: usize) {
         if let Some(entry) = self.editing.and_then(|idx| self.entries.get_mut(idx)) {
             entry.editing = false;
         }
@@ -134,7 +146,7 @@ impl State {
         self.store();
     }
 
-    pub fn remove(&mut self, idx: usize) {
+    fn remove(&mut self, idx: usize) {
         self.entries.remove(idx);
 
         self.store();
@@ -153,7 +165,7 @@ impl State {
         }
     }
 
-    pub fn toggle(&mut self, idx: usize) {
+    fn toggle(&mut self, idx: usize) {
         if let Some(entry) = self.entries.get_mut(idx) {
             entry.completed ^= true;
             self.store();
@@ -161,6 +173,23 @@ impl State {
     }
 }
 
+impl Reducer for State {
+    type Action = Action;
+
+    fn reduce(&mut self, action: Action) -> Then {
+        match action {
+            Action::SetAll(completed) => self.set_all(completed),
+            Action::Clear => self.clear(),
+            Action::Edit(idx) => self.edit_entry(idx),
+            Action::Remove(idx) => self.remove(idx),
+            Action::Toggle(idx) => self.toggle(idx),
+            Action::Filter(filter) if filter == self.filter => return Then::Stop,
+            Action::Filter(filter) => self.filter = filter,
+        }
+        Then::Render
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Filter {
     All,